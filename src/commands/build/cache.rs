@@ -0,0 +1,417 @@
+// Content-hash build cache
+// The inputs of a project, its tracked files, lock files and framework config along with the
+// settings and environment it is built with, are hashed and the staged output of successful
+// builds stored under ~/.doctavious/cache/<hash>. When a later build produces the same hash the
+// output is restored from the cache rather than rebuilding which is helpful in CI when nothing
+// within the docs has changed.
+// Similar to turborepo's cache - https://turbo.build/repo/docs/core-concepts/caching
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use git2::{Repository, Status, StatusOptions};
+use glob::glob;
+use serde::Serialize;
+use serde_derive::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::commands::build::staging::{BuildResult, stage_output};
+use crate::constants::DEFAULT_BUILD_CACHE_DIR;
+use crate::doctavious_error::{DoctaviousError, Result as DoctaviousResult};
+use crate::DOCTAVIOUS_DIR;
+
+const CACHE_ENTRY_FILE_NAME: &str = "entry.json";
+const CACHE_OUTPUT_DIR: &str = "output";
+
+/// Metadata of a cached build stored alongside its output
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CacheEntry {
+    pub key: String,
+    pub created_at: DateTime<Utc>,
+    /// Updated whenever the entry is restored and used to determine which entries to prune
+    pub last_used_at: DateTime<Utc>,
+    pub result: BuildResult,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PruneResult {
+    pub entries: u64,
+    /// Total size, in bytes, of the pruned entries
+    pub total_size: u64,
+}
+
+#[derive(Clone)]
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+}
+
+impl BuildCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Cache within the doctavious directory of the user, ex: ~/.doctavious/cache
+    pub fn user() -> Self {
+        Self::new(DOCTAVIOUS_DIR.join(DEFAULT_BUILD_CACHE_DIR))
+    }
+
+    /// Restores cached output, for the key, into `destination` returning the result of the build
+    /// that produced it or None when there is no entry for the key
+    pub fn restore(&self, key: &str, destination: &Path) -> DoctaviousResult<Option<BuildResult>> {
+        let entry_dir = self.dir.join(key);
+        let mut entry = match read_entry(&entry_dir) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        stage_output(&entry_dir.join(CACHE_OUTPUT_DIR), destination)?;
+
+        entry.last_used_at = Utc::now();
+        fs::write(entry_dir.join(CACHE_ENTRY_FILE_NAME), serde_json::to_string_pretty(&entry)?)?;
+
+        Ok(Some(entry.result))
+    }
+
+    /// Stores the staged output of a successful build
+    pub fn store(&self, key: &str, source: &Path, result: &BuildResult) -> DoctaviousResult<()> {
+        let entry_dir = self.dir.join(key);
+        if entry_dir.exists() {
+            fs::remove_dir_all(&entry_dir)?;
+        }
+
+        stage_output(source, &entry_dir.join(CACHE_OUTPUT_DIR))?;
+
+        // entry is written last so that partially stored entries are never restored
+        let now = Utc::now();
+        let entry = CacheEntry {
+            key: key.to_string(),
+            created_at: now,
+            last_used_at: now,
+            result: result.clone(),
+        };
+        fs::write(entry_dir.join(CACHE_ENTRY_FILE_NAME), serde_json::to_string_pretty(&entry)?)?;
+
+        Ok(())
+    }
+
+    /// Removes entries which haven't been used within `max_age`, or all entries when None.
+    /// Incomplete entries are always removed.
+    pub fn prune(&self, max_age: Option<Duration>) -> DoctaviousResult<PruneResult> {
+        let mut pruned = PruneResult::default();
+        if !self.dir.is_dir() {
+            return Ok(pruned);
+        }
+
+        let now = Utc::now();
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            let expired = match (read_entry(&path), max_age) {
+                (Some(entry), Some(max_age)) => now - entry.last_used_at > max_age,
+                _ => true,
+            };
+
+            if expired {
+                pruned.total_size += dir_size(&path);
+                pruned.entries += 1;
+                fs::remove_dir_all(&path)?;
+            }
+        }
+
+        Ok(pruned)
+    }
+}
+
+fn read_entry(entry_dir: &Path) -> Option<CacheEntry> {
+    let content = fs::read_to_string(entry_dir.join(CACHE_ENTRY_FILE_NAME)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Computes the cache key for the files within the roots along with the fingerprint which should
+/// capture everything, other than files, that influences the build output.
+///
+/// When `inputs` globs are provided, files matching them relative to each root are hashed.
+/// Otherwise the files tracked by git, along with untracked files that aren't ignored, within each
+/// root are hashed, falling back to all files
+/// when the root isn't within a git repository.
+/// `additional_files`, such as lock files, are always hashed when they exist.
+pub(crate) fn compute_cache_key<T: Serialize>(
+    roots: &[&Path],
+    inputs: Option<&Vec<String>>,
+    additional_files: &[PathBuf],
+    fingerprint: &T,
+) -> DoctaviousResult<String> {
+    // paths are canonicalized so the same file reached through different paths, ex: ./yarn.lock
+    // and the yarn.lock of the repository workdir, is only hashed once
+    let mut files = BTreeSet::new();
+    for root in roots {
+        for file in collect_inputs(root, inputs)? {
+            files.insert(canonicalize_file(&file)?);
+        }
+    }
+    for file in additional_files.iter().filter(|f| f.is_file()) {
+        files.insert(canonicalize_file(file)?);
+    }
+
+    // paths are hashed relative to their root, or to the repository workdir for files outside
+    // of the roots, so that the key doesn't depend on where the repository is checked out
+    let mut base_dirs = vec![];
+    for root in roots {
+        base_dirs.push(root.canonicalize()?);
+    }
+    for root in roots {
+        let workdir = Repository::discover(root).ok()
+            .and_then(|r| r.workdir().and_then(|w| w.canonicalize().ok()));
+        if let Some(workdir) = workdir {
+            base_dirs.push(workdir);
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(&sort_keys(serde_json::to_value(fingerprint)?))?);
+    let mut buffer = Vec::new();
+    for file in files {
+        let path = base_dirs.iter()
+            .find_map(|dir| file.strip_prefix(dir).ok())
+            .unwrap_or(&file);
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update([0]);
+
+        buffer.clear();
+        fs::File::open(&file)?.read_to_end(&mut buffer)?;
+        hasher.update(&buffer);
+        hasher.update([0]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// only the directory is canonicalized so that a symlinked file keeps its own name
+fn canonicalize_file(file: &Path) -> DoctaviousResult<PathBuf> {
+    let dir = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.canonicalize()?,
+        _ => Path::new(".").canonicalize()?,
+    };
+    Ok(match file.file_name() {
+        Some(name) => dir.join(name),
+        None => dir,
+    })
+}
+
+// settings contain HashMaps whose iteration order differs between runs so keys are sorted to
+// keep the serialized fingerprint stable
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sort_keys(v))).collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+fn collect_inputs(root: &Path, inputs: Option<&Vec<String>>) -> DoctaviousResult<BTreeSet<PathBuf>> {
+    if let Some(inputs) = inputs {
+        let mut files = BTreeSet::new();
+        for pattern in inputs {
+            let pattern = root.join(pattern);
+            for path in glob(&pattern.to_string_lossy())? {
+                let path = path.map_err(|e| DoctaviousError::Msg(e.to_string()))?;
+                if path.is_file() {
+                    files.insert(path);
+                }
+            }
+        }
+        return Ok(files);
+    }
+
+    if let Some(files) = collect_tracked_files(root) {
+        return Ok(files);
+    }
+
+    let mut files = BTreeSet::new();
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_ignored(e.file_name().to_str()));
+    for entry in walker {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.insert(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+// files in the index along with untracked files, which aren't ignored, so that new sources such
+// as a page which hasn't been committed yet are part of the key. Paths are relative to the
+// repository workdir so we only keep those within the root
+fn collect_tracked_files(root: &Path) -> Option<BTreeSet<PathBuf>> {
+    let repo = Repository::discover(root).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let prefix = root.canonicalize().ok()?.strip_prefix(&workdir).ok()?.to_path_buf();
+
+    let index = repo.index().ok()?;
+    let mut paths: Vec<PathBuf> = index.iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .map(PathBuf::from)
+        .collect();
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+    paths.extend(
+        statuses.iter()
+            .filter(|entry| entry.status().contains(Status::WT_NEW))
+            .filter_map(|entry| entry.path().map(PathBuf::from))
+    );
+
+    let files = paths.into_iter()
+        .filter_map(|path| path.strip_prefix(&prefix).ok().map(|p| root.join(p)))
+        .filter(|path| path.is_file())
+        .collect();
+    Some(files)
+}
+
+fn is_ignored(file_name: Option<&str>) -> bool {
+    matches!(file_name, Some(".doctavious") | Some(".git") | Some("node_modules"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use chrono::Duration;
+    use git2::Repository;
+    use tempfile::TempDir;
+    use crate::commands::build::cache::{BuildCache, compute_cache_key};
+    use crate::commands::build::staging::BuildResult;
+
+    #[test]
+    fn cache_key_should_include_untracked_files() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "book/\n").unwrap();
+        fs::write(dir.path().join("intro.md"), "# Intro").unwrap();
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new("intro.md")).unwrap();
+        index.write().unwrap();
+
+        let key = || compute_cache_key(&[dir.path()], None, &[], &"production").unwrap();
+        let original = key();
+
+        fs::create_dir_all(dir.path().join("book")).unwrap();
+        fs::write(dir.path().join("book/index.html"), "<html></html>").unwrap();
+        assert_eq!(original, key());
+
+        fs::create_dir_all(dir.path().join("guides")).unwrap();
+        fs::write(dir.path().join("guides/install.md"), "# Install").unwrap();
+        assert_ne!(original, key());
+    }
+
+    #[test]
+    fn cache_key_should_not_depend_on_checkout_path() {
+        let key = |dir: &Path| {
+            fs::create_dir_all(dir).unwrap();
+            Repository::init(dir).unwrap();
+            fs::create_dir_all(dir.join("docs")).unwrap();
+            fs::write(dir.join("docs/intro.md"), "# Intro").unwrap();
+            fs::write(dir.join("package-lock.json"), "{}").unwrap();
+
+            let root = dir.join("docs");
+            let additional_files = vec![
+                root.join("package-lock.json"),
+                dir.join("package-lock.json"),
+            ];
+            compute_cache_key(&[root.as_path()], None, &additional_files, &"production").unwrap()
+        };
+
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        assert_eq!(key(first.path()), key(&second.path().join("checkout")));
+    }
+
+    #[test]
+    fn cache_key_should_change_with_inputs() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("book.toml"), "[book]").unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/intro.md"), "# Intro").unwrap();
+
+        let inputs = vec![String::from("src/**/*.md")];
+        let additional_files = vec![dir.path().join("book.toml"), dir.path().join("yarn.lock")];
+        let key = |fingerprint: &str| {
+            compute_cache_key(&[dir.path()], Some(&inputs), &additional_files, &fingerprint).unwrap()
+        };
+
+        let original = key("production");
+        assert_eq!(original, key("production"));
+        assert_ne!(original, key("preview"));
+
+        fs::write(dir.path().join("src/intro.md"), "# Introduction").unwrap();
+        assert_ne!(original, key("production"));
+
+        // files not matching the inputs are ignored
+        let updated = key("production");
+        fs::write(dir.path().join("notes.txt"), "notes").unwrap();
+        assert_eq!(updated, key("production"));
+
+        fs::write(dir.path().join("book.toml"), "[book]\ntitle = \"docs\"").unwrap();
+        assert_ne!(updated, key("production"));
+    }
+
+    #[test]
+    fn should_store_restore_and_prune() {
+        let dir = TempDir::new().unwrap();
+        let cache = BuildCache::new(dir.path().join("cache"));
+
+        let output = dir.path().join("output/docs");
+        fs::create_dir_all(&output).unwrap();
+        fs::write(output.join("index.html"), "<html></html>").unwrap();
+
+        let destination = dir.path().join("restored");
+        assert!(cache.restore("abc", &destination).unwrap().is_none());
+
+        let result = BuildResult {
+            project: String::from("docs"),
+            framework: Some(String::from("mdBook")),
+            success: true,
+            ..Default::default()
+        };
+        cache.store("abc", &output, &result).unwrap();
+
+        let restored = cache.restore("abc", &destination).unwrap().unwrap();
+        assert_eq!(restored.framework, Some(String::from("mdBook")));
+        assert!(destination.join("index.html").exists());
+
+        // incomplete entries are pruned regardless of age
+        fs::create_dir_all(dir.path().join("cache/partial")).unwrap();
+        let pruned = cache.prune(Some(Duration::days(7))).unwrap();
+        assert_eq!(pruned.entries, 1);
+        assert!(cache.restore("abc", &dir.path().join("again")).unwrap().is_some());
+
+        let pruned = cache.prune(None).unwrap();
+        assert_eq!(pruned.entries, 1);
+        assert!(pruned.total_size > 0);
+        assert!(cache.restore("abc", &destination).unwrap().is_none());
+    }
+}
@@ -0,0 +1,218 @@
+// Build contexts allow for settings to differ depending on where the build is going to be
+// deployed, for example, to use a different base URL for preview deploys.
+// Contexts are configured in doctavious.toml and override the top level build settings
+//
+// [build.context.preview]
+// command = "hugo --buildDrafts"
+// base_url = "https://preview.example.com"
+// environment = { HUGO_ENV = "preview" }
+//
+// The branch context can also be configured for a specific branch, which takes precedence
+// over the generic branch context. Branch names are prefixed with "branch:" so that they can't
+// clash with the production, preview and branch contexts
+//
+// [build.context."branch:feature-docs"]
+// command = "hugo --buildFuture"
+//
+// https://docs.netlify.com/configure-builds/file-based-configuration/#deploy-contexts
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use clap::ValueEnum;
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use crate::git::{current_branch, head_commit_sha};
+use crate::settings::{BuildContextSettings, BuildSettings};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BuildContext {
+    Production,
+    Preview,
+    Branch,
+}
+
+impl BuildContext {
+    pub(crate) fn value(&self) -> &'static str {
+        return match self {
+            BuildContext::Production => "production",
+            BuildContext::Preview => "preview",
+            BuildContext::Branch => "branch",
+        };
+    }
+}
+
+impl Default for BuildContext {
+    fn default() -> Self {
+        BuildContext::Production
+    }
+}
+
+impl Display for BuildContext {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// Git information made available to build commands
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct GitInfo {
+    pub branch: Option<String>,
+    pub commit_sha: Option<String>,
+}
+
+impl GitInfo {
+    /// Resolves the branch and commit of the repository containing `cwd`. Fields are left empty
+    /// when `cwd` isn't within a git repository
+    pub fn discover(cwd: &Path) -> Self {
+        match Repository::discover(cwd) {
+            Ok(repo) => Self {
+                branch: current_branch(&repo),
+                commit_sha: head_commit_sha(&repo),
+            },
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// Returns the settings for the context.
+///
+/// For the branch context, settings for the specific branch, keyed by `branch:<name>`, take
+/// precedence over the generic branch context.
+pub(crate) fn get_context_settings<'a>(
+    context: BuildContext,
+    branch: Option<&str>,
+    build_settings: Option<&'a BuildSettings>,
+) -> Option<&'a BuildContextSettings> {
+    let contexts = build_settings.and_then(|s| s.context.as_ref())?;
+    if context == BuildContext::Branch {
+        if let Some(settings) = branch.and_then(|b| contexts.get(&branch_context_key(b))) {
+            return Some(settings);
+        }
+    }
+
+    contexts.get(context.value())
+}
+
+/// Key of the context settings for a specific branch
+fn branch_context_key(branch: &str) -> String {
+    format!("branch:{}", branch)
+}
+
+/// Environment variables passed to install and build commands.
+///
+/// Variables from the context take precedence over those from the top level build settings
+/// which in turn can override the variables doctavious provides.
+pub(crate) fn get_build_environment(
+    context: BuildContext,
+    git: &GitInfo,
+    build_settings: Option<&BuildSettings>,
+    context_settings: Option<&BuildContextSettings>,
+) -> HashMap<String, String> {
+    let mut environment = HashMap::new();
+    environment.insert(String::from("DOCTAVIOUS"), String::from("true"));
+    environment.insert(String::from("DOCTAVIOUS_CONTEXT"), context.value().to_string());
+    if let Some(branch) = &git.branch {
+        environment.insert(String::from("DOCTAVIOUS_BRANCH"), branch.to_string());
+    }
+    if let Some(commit_sha) = &git.commit_sha {
+        environment.insert(String::from("DOCTAVIOUS_COMMIT_SHA"), commit_sha.to_string());
+    }
+
+    let base_url = context_settings.and_then(|c| c.base_url.as_ref())
+        .or(build_settings.and_then(|s| s.base_url.as_ref()));
+    if let Some(base_url) = base_url {
+        environment.insert(String::from("DOCTAVIOUS_BASE_URL"), base_url.to_string());
+    }
+
+    if let Some(variables) = build_settings.and_then(|s| s.environment.as_ref()) {
+        environment.extend(variables.clone());
+    }
+
+    if let Some(variables) = context_settings.and_then(|c| c.environment.as_ref()) {
+        environment.extend(variables.clone());
+    }
+
+    environment
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::build::context::{
+        BuildContext, GitInfo, get_build_environment, get_context_settings
+    };
+    use crate::settings::BuildSettings;
+
+    fn settings() -> BuildSettings {
+        toml::from_str(r#"
+            command = "hugo"
+            base_url = "https://example.com"
+            environment = { HUGO_ENV = "production", API_URL = "https://api.example.com" }
+
+            [context.preview]
+            command = "hugo --buildDrafts"
+            base_url = "https://preview.example.com"
+            environment = { HUGO_ENV = "preview" }
+
+            [context.branch]
+            command = "hugo --buildFuture"
+
+            [context."branch:feature-docs"]
+            command = "hugo --buildExpired"
+
+            [context."branch:preview"]
+            command = "hugo --buildFuture --minify"
+        "#).unwrap()
+    }
+
+    #[test]
+    fn should_resolve_context_settings() {
+        let settings = settings();
+
+        assert!(get_context_settings(BuildContext::Production, None, Some(&settings)).is_none());
+
+        let preview = get_context_settings(BuildContext::Preview, None, Some(&settings)).unwrap();
+        assert_eq!(preview.command, Some(String::from("hugo --buildDrafts")));
+
+        let branch = get_context_settings(BuildContext::Branch, Some("feature-docs"), Some(&settings)).unwrap();
+        assert_eq!(branch.command, Some(String::from("hugo --buildExpired")));
+
+        let branch = get_context_settings(BuildContext::Branch, Some("main"), Some(&settings)).unwrap();
+        assert_eq!(branch.command, Some(String::from("hugo --buildFuture")));
+
+        // a branch named after a context doesn't pick up the settings of that context
+        let branch = get_context_settings(BuildContext::Branch, Some("preview"), Some(&settings)).unwrap();
+        assert_eq!(branch.command, Some(String::from("hugo --buildFuture --minify")));
+        let branch = get_context_settings(BuildContext::Branch, Some("production"), Some(&settings)).unwrap();
+        assert_eq!(branch.command, Some(String::from("hugo --buildFuture")));
+        let preview = get_context_settings(BuildContext::Preview, Some("feature-docs"), Some(&settings)).unwrap();
+        assert_eq!(preview.command, Some(String::from("hugo --buildDrafts")));
+    }
+
+    #[test]
+    fn context_environment_should_override_build_environment() {
+        let settings = settings();
+        let git = GitInfo {
+            branch: Some(String::from("main")),
+            commit_sha: Some(String::from("2a3c5b9")),
+        };
+
+        let context_settings = get_context_settings(BuildContext::Preview, None, Some(&settings));
+        let environment = get_build_environment(BuildContext::Preview, &git, Some(&settings), context_settings);
+        assert_eq!(environment.get("DOCTAVIOUS_CONTEXT").unwrap(), "preview");
+        assert_eq!(environment.get("DOCTAVIOUS_BRANCH").unwrap(), "main");
+        assert_eq!(environment.get("DOCTAVIOUS_COMMIT_SHA").unwrap(), "2a3c5b9");
+        assert_eq!(environment.get("DOCTAVIOUS_BASE_URL").unwrap(), "https://preview.example.com");
+        assert_eq!(environment.get("HUGO_ENV").unwrap(), "preview");
+        assert_eq!(environment.get("API_URL").unwrap(), "https://api.example.com");
+
+        let environment = get_build_environment(BuildContext::Production, &GitInfo::default(), Some(&settings), None);
+        assert_eq!(environment.get("DOCTAVIOUS_CONTEXT").unwrap(), "production");
+        assert_eq!(environment.get("DOCTAVIOUS_BASE_URL").unwrap(), "https://example.com");
+        assert_eq!(environment.get("HUGO_ENV").unwrap(), "production");
+        assert!(environment.get("DOCTAVIOUS_BRANCH").is_none());
+    }
+}
@@ -141,7 +141,7 @@ pub enum RequiredDependencies {
 
 // TODO: change name?
 /// Matching strategies to match on a framework
-#[derive(Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum FrameworkMatchingStrategy {
     /// Strategy that requires all detectors to match for the framework to be detected
     All,
@@ -182,7 +182,20 @@ pub trait FrameworkSupport {
 
     fn get_info(&self) -> &FrameworkInfo;
 
-    fn get_output_dir(&self) -> String {
+    /// Semver requirement, ex: `>=3`, the detected version of the framework must satisfy.
+    /// Allows separate implementations for major versions of a framework that require different
+    /// build settings. Frameworks without a detected version are not filtered out.
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the command used to build the project within the project root
+    fn get_build_command(&self, _cwd: &Path) -> String {
+        self.get_info().build.command.to_string()
+    }
+
+    /// Returns the output directory, relative to the project root, that the build is written to
+    fn get_output_dir(&self, _cwd: &Path) -> String {
         self.get_info().build.output_directory.to_string()
     }
 }
@@ -212,13 +225,14 @@ pub trait ConfigurationFileDeserialization: for<'a> Deserialize<'a> {
     }
 }
 
-pub(crate) fn read_config_files<T>(files: &Vec<&'static str>) -> DoctaviousResult<T>
+/// Reads the first config file that exists, with config files resolved against `cwd`
+pub(crate) fn read_config_files<T>(cwd: &Path, files: &Vec<&'static str>) -> DoctaviousResult<T>
     where T: ConfigurationFileDeserialization
 {
     for file in files {
-        let path = Path::new(&file);
+        let path = cwd.join(file);
         if let Some(extension) = path.extension() {
-            if let Ok(content) = fs::read_to_string(&file) {
+            if let Ok(content) = fs::read_to_string(&path) {
                 return match extension.to_str() {
                     Some("json") => T::from_json(content.as_str()),
                     Some("yaml") | Some("yml") => T::from_yaml(content.as_str()),
@@ -1,43 +1,68 @@
 use std::fs;
+use std::path::Path;
 use regex::{Error, Regex, RegexBuilder};
 use serde_json::Value;
 use serde_derive::{Serialize};
 
 use crate::commands::build::projects::csproj::CSProj;
 use crate::commands::build::framework::{FrameworkDetectionItem, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport};
+use crate::commands::build::lock_file::{resolve_dependency_version, version_matches};
+use crate::commands::build::package_manager::PackageManager;
 use crate::commands::build::projects::project_file::{Proj, ProjectFile};
-use crate::doctavious_error::Result as DoctaviousResult;
+use crate::doctavious_error::{DoctaviousError, Result as DoctaviousResult};
 
 // Return matched Framework
 // which should have framework info
 // as well as project
-pub(crate) struct MatchedFramework<'a> {
-    pub framework_info: &'a FrameworkInfo,
-    pub project: Option<ProjectFile>
+pub(crate) struct MatchedFramework {
+    pub framework: Box<dyn FrameworkSupport>,
+    pub project: Option<ProjectFile>,
+    pub detected_version: Option<String>
 }
 
-#[derive(Clone, Copy, Serialize)]
+#[derive(Clone, Serialize)]
 pub(crate) struct MatchResult {
-    pub project: Option<ProjectFile>
+    pub project: Option<ProjectFile>,
     // dependency -- could also do a dependency/version struct tuple and have an array of them
-    // detected_version: String
+    /// Version of the dependency, resolved from lock files, that matched
+    pub detected_version: Option<String>
 }
 
 
-pub(crate) fn detect_framework<'a>(frameworks: Vec<Box<dyn FrameworkSupport>>) -> Option<Box<dyn FrameworkSupport>> {
+/// Detects the framework used by the project within `cwd`
+pub(crate) fn detect_framework(
+    cwd: &Path,
+    frameworks: Vec<Box<dyn FrameworkSupport>>
+) -> Option<MatchedFramework> {
     for framework in frameworks {
-        println!("{:?}", serde_json::to_string(framework.get_info()));
-        let m = matches(framework.get_info());
-        // TODO: return MatchResult?
-        if m.is_some() {
-            println!("we found something");
-            return Some(framework);
+        if let Some(m) = matches(cwd, framework.get_info()) {
+            if let (Some(requirement), Some(version)) = (framework.get_version_requirement(), &m.detected_version) {
+                if !version_matches(requirement, version) {
+                    continue;
+                }
+            }
+
+            println!(
+                "detected {} {}",
+                framework.get_info().name,
+                m.detected_version.as_deref().unwrap_or("(unknown version)")
+            );
+            return Some(MatchedFramework {
+                framework,
+                project: m.project,
+                detected_version: m.detected_version
+            });
         }
     }
 
     None
 }
 
+/// Returns the version of the framework when the project depends on it
+pub(crate) fn detect_framework_version(cwd: &Path, framework: &FrameworkInfo) -> Option<String> {
+    matches(cwd, framework).and_then(|m| m.detected_version)
+}
+
 // pub(crate) fn detect_framework(frameworks: &[FrameworkInfo]) -> Option<&FrameworkInfo> {
 //
 //     for framework in frameworks {
@@ -50,20 +75,20 @@ pub(crate) fn detect_framework<'a>(frameworks: Vec<Box<dyn FrameworkSupport>>) -
 //     None
 // }
 
-fn matches(framework: &FrameworkInfo) -> Option<MatchResult> {
+fn matches(cwd: &Path, framework: &FrameworkInfo) -> Option<MatchResult> {
     let mut results: Vec<Option<MatchResult>> = vec![];
 
     match &framework.detection.matching_strategy {
         FrameworkMatchingStrategy::All => {
             let a = framework.detection.detectors.iter()
-                .map(|item| check(&framework, item))
+                .map(|item| check(cwd, &framework, item))
                 .collect::<Vec<Option<MatchResult>>>();
             results.extend(a);
         }
         FrameworkMatchingStrategy::Any => {
             for item in &framework.detection.detectors {
                 println!("{:?}", item);
-                let result = check(&framework, item);
+                let result = check(cwd, &framework, item);
                 if result.is_some() {
                     results.push(result);
                     break;
@@ -75,9 +100,7 @@ fn matches(framework: &FrameworkInfo) -> Option<MatchResult> {
     for result in results {
         if result.is_some() {
             println!("match result found {:?}", serde_json::to_string(&result).unwrap());
-            return Some(MatchResult { project: None });
-        } else {
-
+            return result;
         }
     }
 
@@ -92,30 +115,14 @@ fn matches(framework: &FrameworkInfo) -> Option<MatchResult> {
     None
 }
 
-fn check(framework: &FrameworkInfo, item: &FrameworkDetectionItem) -> Option<MatchResult> {
+fn check(cwd: &Path, framework: &FrameworkInfo, item: &FrameworkDetectionItem) -> Option<MatchResult> {
     println!("checking {:?}", framework.name);
     match item {
         FrameworkDetectionItem::Config { content } => {
             if let Some(configs) = &framework.configs {
                 for config in configs {
-                    if let Ok(file_content) = fs::read_to_string(config) {
-                        if let Some(content) = content {
-                            let regex = RegexBuilder::new(content)
-                                .multi_line(true)
-                                .build();
-                            match regex {
-                                Ok(regex) => {
-
-                                    if regex.is_match(file_content.as_str()) {
-                                        return Some(MatchResult { project: None });
-                                    }
-                                }
-                                Err(e) => {
-                                    // TODO: log
-                                }
-                            }
-                        }
-                        return Some(MatchResult { project: None });
+                    if detector_matches(&framework.name, &cwd.join(config), *content) {
+                        return Some(MatchResult { project: None, detected_version: None });
                     }
                 }
             }
@@ -123,7 +130,7 @@ fn check(framework: &FrameworkInfo, item: &FrameworkDetectionItem) -> Option<Mat
         }
         FrameworkDetectionItem::Dependency { name: dependency } => {
             for p in framework.language.project_files() {
-                for path in p.get_project_paths() {
+                for path in p.get_project_paths(cwd) {
                     if !path.exists() {
                         // TODO: log
                         continue;
@@ -142,7 +149,10 @@ fn check(framework: &FrameworkInfo, item: &FrameworkDetectionItem) -> Option<Mat
                                 Ok(f) => {
                                     if f {
                                         println!("...found");
-                                        return Some(MatchResult { project: Some(*p) });
+                                        return Some(MatchResult {
+                                            project: Some(*p),
+                                            detected_version: resolve_dependency_version(cwd, p, dependency)
+                                        });
                                     } else {
                                         // TODO: log -- dependency not found
                                     }
@@ -166,31 +176,69 @@ fn check(framework: &FrameworkInfo, item: &FrameworkDetectionItem) -> Option<Mat
             path,
             content,
         } => {
-            if let Ok(file_content) = fs::read_to_string(path) {
-                if let Some(content) = content {
-                    println!("file {file_content} with content {content}");
-                    let regex = RegexBuilder::new(content)
-                        .multi_line(true)
-                        .build();
-                    match regex {
-                        Ok(regex) => {
-                            println!("regex matching");
-                            if regex.is_match(file_content.as_str()) {
-                                println!("match found");
-                                return Some(MatchResult { project: None });
-                            }
-                        }
-                        Err(e) => {
-                            // TODO: log
-                            println!("error with regex {e}")
-                        }
+            if detector_matches(&framework.name, &cwd.join(path), *content) {
+                return Some(MatchResult { project: None, detected_version: None });
+            }
+            None
+        }
+    }
+}
+
+/// Checks that the file exists and, when content is provided, that the content regex matches
+/// the contents of the file
+fn file_matches(path: &Path, content: Option<&str>) -> DoctaviousResult<bool> {
+    let file_content = match fs::read_to_string(path) {
+        Ok(file_content) => file_content,
+        Err(_) => return Ok(false),
+    };
+
+    match content {
+        Some(content) => {
+            let regex = RegexBuilder::new(content)
+                .multi_line(true)
+                .build()
+                .map_err(|e| DoctaviousError::Msg(format!(
+                    "invalid content regex {} for {}: {}", content, path.to_string_lossy(), e
+                )))?;
+            Ok(regex.is_match(file_content.as_str()))
+        }
+        None => Ok(true),
+    }
+}
+
+/// Reports invalid detectors, so they can be fixed, rather than failing the detection of every
+/// other framework or package manager
+fn detector_matches(name: &str, path: &Path, content: Option<&str>) -> bool {
+    file_matches(path, content).unwrap_or_else(|e| {
+        eprintln!("unable to check detector of {}: {}", name, e);
+        false
+    })
+}
+
+/// Detects which of the given package managers is used by the project within `dir`.
+///
+/// Explicit declarations, such as the `packageManager` field in package.json, take precedence
+/// over the presence of lock files as a project could have stale lock files lying around.
+pub(crate) fn detect_package_manager(
+    dir: &Path,
+    package_managers: &[PackageManager]
+) -> Option<PackageManager> {
+    // first pass only looks at detectors with content (declarations) and second pass at files
+    for declaration in [true, false] {
+        for package_manager in package_managers {
+            for item in package_manager.info().detection.detectors {
+                if let FrameworkDetectionItem::File { path, content } = item {
+                    if content.is_some() == declaration
+                        && detector_matches(package_manager.info().name, &dir.join(path), content)
+                    {
+                        return Some(*package_manager);
                     }
                 }
-                return Some(MatchResult { project: None });
             }
-            None
         }
     }
+
+    None
 }
 
 fn has_dependency(project_type: &ProjectFile, content: String, dependency: &str) -> DoctaviousResult<bool> {
@@ -240,3 +288,137 @@ fn has_dependency(project_type: &ProjectFile, content: String, dependency: &str)
 
     Ok(found)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::commands::build::framework_detection::{
+        detect_framework, detect_package_manager, file_matches
+    };
+    use crate::commands::build::frameworks::get_frameworks;
+    use crate::commands::build::language::Language;
+    use crate::commands::build::package_manager::PackageManager;
+
+    #[test]
+    fn should_detect_package_manager_from_lock_file() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "lockfileVersion: 5.4").unwrap();
+
+        let package_manager = detect_package_manager(
+            dir.path(),
+            Language::Javascript.get_package_managers()
+        );
+        assert_eq!(package_manager, Some(PackageManager::PNPM));
+    }
+
+    #[test]
+    fn package_manager_field_should_take_precedence_over_lock_file() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "packageManager": "yarn@3.2.3" }"#).unwrap();
+        fs::write(dir.path().join("package-lock.json"), "{}").unwrap();
+
+        let package_manager = detect_package_manager(
+            dir.path(),
+            Language::Javascript.get_package_managers()
+        );
+        assert_eq!(package_manager, Some(PackageManager::Yarn));
+    }
+
+    #[test]
+    fn should_detect_poetry() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("pyproject.toml"), "[tool.poetry]\nname = \"docs\"").unwrap();
+        fs::write(dir.path().join("requirements.txt"), "mkdocs==1.4.2").unwrap();
+
+        let package_manager = detect_package_manager(
+            dir.path(),
+            Language::Python.get_package_managers()
+        );
+        assert_eq!(package_manager, Some(PackageManager::Poetry));
+    }
+
+    #[test]
+    fn should_return_error_for_invalid_content_regex() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(&path, "{ \"packageManager\": \"pnpm@8.6.0\" }").unwrap();
+
+        assert!(file_matches(&path, Some("\"packageManager\": \"pnpm@")).unwrap());
+        assert!(file_matches(&path, Some("\"packageManager\": (pnpm")).is_err());
+        assert!(!file_matches(&dir.path().join("missing.json"), None).unwrap());
+    }
+
+    #[test]
+    fn should_not_detect_package_manager_without_files() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(detect_package_manager(dir.path(), PackageManager::ALL), None);
+    }
+
+    #[test]
+    fn should_detect_framework_relative_to_project_root() {
+        let dir = TempDir::new().unwrap();
+        let project_dir = dir.path().join("website");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(
+            project_dir.join("package.json"),
+            r#"{ "dependencies": { "@docusaurus/core": "2.2.0" } }"#
+        ).unwrap();
+
+        assert!(detect_framework(dir.path(), get_frameworks()).is_none());
+
+        let matched = detect_framework(&project_dir, get_frameworks()).unwrap();
+        assert_eq!(matched.framework.get_info().name, "Docusaurus 2");
+        assert_eq!(matched.detected_version, Some(String::from("2.2.0")));
+    }
+
+    #[test]
+    fn starlight_should_be_detected_over_astro() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "dependencies": { "@astrojs/starlight": "0.11.0", "astro": "3.2.0" } }"#
+        ).unwrap();
+
+        let matched = detect_framework(dir.path(), get_frameworks()).unwrap();
+        assert_eq!(matched.framework.get_info().name, "Starlight");
+    }
+
+    #[test]
+    fn should_detect_docsify_without_a_project_file() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(
+            dir.path().join("docs/index.html"),
+            "<script>window.$docsify = { name: 'docs' }</script>"
+        ).unwrap();
+
+        let matched = detect_framework(dir.path(), get_frameworks()).unwrap();
+        assert_eq!(matched.framework.get_info().name, "Docsify");
+        assert_eq!(matched.framework.get_output_dir(dir.path()), "docs");
+    }
+
+    #[test]
+    fn should_choose_framework_by_detected_version() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "dependencies": { "nuxt": "^3.4.0" } }"#).unwrap();
+        fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{ "lockfileVersion": 3, "packages": { "node_modules/nuxt": { "version": "3.4.1" } } }"#
+        ).unwrap();
+
+        let matched = detect_framework(dir.path(), get_frameworks()).unwrap();
+        assert_eq!(matched.framework.get_info().name, "Nuxt 3");
+        assert_eq!(matched.detected_version, Some(String::from("3.4.1")));
+
+        fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{ "lockfileVersion": 1, "dependencies": { "nuxt": { "version": "2.16.3" } } }"#
+        ).unwrap();
+
+        let matched = detect_framework(dir.path(), get_frameworks()).unwrap();
+        assert_eq!(matched.framework.get_info().name, "Nuxt");
+        assert_eq!(matched.detected_version, Some(String::from("2.16.3")));
+    }
+}
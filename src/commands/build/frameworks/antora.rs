@@ -6,6 +6,7 @@
 // antora generate <playbook> --to-dir <dir>
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -64,9 +65,9 @@ impl FrameworkSupport for Antora {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<AntoraConfig>(configs) {
+            match read_config_files::<AntoraConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(AntoraConfigOutputKeys {dir: Some(v)}) = c.output {
                         return v;
@@ -88,6 +89,7 @@ impl ConfigurationFileDeserialization for AntoraConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Antora;
 
@@ -97,7 +99,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/antora/antora-playbook.yaml"])
         );
 
-        let output = antora.get_output_dir();
+        let output = antora.get_output_dir(Path::new("."));
         assert_eq!(output, "./launch")
     }
 
@@ -5,6 +5,7 @@
 // defaults to "./dist"
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -58,9 +59,9 @@ impl FrameworkSupport for Astro {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<AstroConfig>(configs) {
+            match read_config_files::<AstroConfig>(cwd, configs) {
                 Ok(c) => {
                     return c.output
                 }
@@ -76,7 +77,7 @@ impl FrameworkSupport for Astro {
 }
 
 #[derive(Deserialize)]
-struct AstroConfig { output: String }
+pub(crate) struct AstroConfig { pub output: String }
 
 impl ConfigurationFileDeserialization for AstroConfig {
 
@@ -98,6 +99,7 @@ impl ConfigurationFileDeserialization for AstroConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Astro;
 
@@ -107,7 +109,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/astro/astro.config.mjs"])
         );
 
-        let output = astro.get_output_dir();
+        let output = astro.get_output_dir(Path::new("."));
         assert_eq!(output, "./build")
     }
 
@@ -0,0 +1,195 @@
+// User-defined frameworks declared in doctavious.toml via [[build.frameworks]]
+// This allows users to build with frameworks we don't yet support without having to wait on a
+// new release. User-defined frameworks are merged with built-in frameworks and override built-in
+// frameworks with the same name
+//
+// [[build.frameworks]]
+// name = "Cobalt"
+// configs = ["_cobalt.yml"]
+// language = "Rust"
+//
+// [build.frameworks.detection]
+// matching_strategy = "All"
+// detectors = [{ type = "config" }]
+//
+// [build.frameworks.build]
+// command = "cobalt build"
+// output_directory = "_site"
+// command_args = { output = { type = "option", short = "-d", long = "--destination" } }
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::commands::build::framework::{
+    FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem,
+    FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport
+};
+use crate::commands::build::language::Language;
+
+/// Framework declared in doctavious.toml which maps onto [`FrameworkInfo`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CustomFrameworkSettings {
+    /// Name of the framework. Matches a built-in framework with the same name, ignoring case,
+    /// will replace the built-in framework
+    pub name: String,
+
+    /// A URL to the official website of the framework
+    pub website: Option<String>,
+
+    /// List of potential config files
+    #[serde(default)]
+    pub configs: Vec<String>,
+
+    pub language: Language,
+
+    pub detection: CustomFrameworkDetectionSettings,
+
+    pub build: CustomFrameworkBuildSettings,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CustomFrameworkDetectionSettings {
+    pub matching_strategy: FrameworkMatchingStrategy,
+    pub detectors: Vec<CustomFrameworkDetectionItem>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CustomFrameworkDetectionItem {
+    File { path: String, content: Option<String> },
+    Config { content: Option<String> },
+    Dependency { name: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CustomFrameworkBuildSettings {
+    pub command: String,
+    pub command_args: Option<CustomFrameworkBuildArgs>,
+    pub output_directory: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CustomFrameworkBuildArgs {
+    pub source: Option<CustomFrameworkBuildArg>,
+    pub config: Option<CustomFrameworkBuildArg>,
+    pub output: Option<CustomFrameworkBuildArg>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CustomFrameworkBuildArg {
+    Arg { index: i8, default_value: Option<String> },
+    Option { short: String, long: String },
+}
+
+// FrameworkInfo borrows static strings so we borrow from settings which live for the duration
+// of the program given they are loaded via lazy_static
+pub struct CustomFramework { info: FrameworkInfo }
+
+impl CustomFramework {
+    pub fn new(settings: &'static CustomFrameworkSettings) -> Self {
+        let configs = if settings.configs.is_empty() {
+            None
+        } else {
+            Some(settings.configs.iter().map(|c| c.as_str()).collect())
+        };
+
+        Self {
+            info: FrameworkInfo {
+                name: &settings.name,
+                website: settings.website.as_deref(),
+                configs,
+                language: settings.language.clone(),
+                detection: FrameworkDetector {
+                    matching_strategy: settings.detection.matching_strategy.clone(),
+                    detectors: settings.detection.detectors.iter().map(to_detection_item).collect()
+                },
+                build: FrameworkBuildSettings {
+                    command: &settings.build.command,
+                    command_args: settings.build.command_args.as_ref().map(|args| FrameworkBuildArgs {
+                        source: args.source.as_ref().map(to_build_arg),
+                        config: args.config.as_ref().map(to_build_arg),
+                        output: args.output.as_ref().map(to_build_arg),
+                    }),
+                    output_directory: &settings.build.output_directory,
+                },
+            }
+        }
+    }
+}
+
+impl FrameworkSupport for CustomFramework {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+}
+
+fn to_detection_item(item: &'static CustomFrameworkDetectionItem) -> FrameworkDetectionItem {
+    match item {
+        CustomFrameworkDetectionItem::File { path, content } => FrameworkDetectionItem::File {
+            path,
+            content: content.as_deref()
+        },
+        CustomFrameworkDetectionItem::Config { content } => FrameworkDetectionItem::Config {
+            content: content.as_deref()
+        },
+        CustomFrameworkDetectionItem::Dependency { name } => FrameworkDetectionItem::Dependency {
+            name
+        },
+    }
+}
+
+fn to_build_arg(arg: &'static CustomFrameworkBuildArg) -> FrameworkBuildArg {
+    match arg {
+        CustomFrameworkBuildArg::Arg { index, default_value } => FrameworkBuildArg::Arg {
+            index: *index,
+            default_value: default_value.as_deref()
+        },
+        CustomFrameworkBuildArg::Option { short, long } => FrameworkBuildArg::Option {
+            short,
+            long
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::build::framework::{
+        FrameworkBuildArg, FrameworkDetectionItem, FrameworkMatchingStrategy, FrameworkSupport
+    };
+    use crate::commands::build::frameworks::custom::{CustomFramework, CustomFrameworkSettings};
+
+    #[test]
+    fn should_map_settings_to_framework_info() {
+        let settings: CustomFrameworkSettings = toml::from_str(r#"
+            name = "Cobalt"
+            configs = ["_cobalt.yml"]
+            language = "Rust"
+
+            [detection]
+            matching_strategy = "All"
+            detectors = [{ type = "config" }, { type = "file", path = "Cargo.toml", content = "cobalt" }]
+
+            [build]
+            command = "cobalt build"
+            output_directory = "_site"
+            command_args = { output = { type = "option", short = "-d", long = "--destination" } }
+        "#).unwrap();
+        let settings: &'static CustomFrameworkSettings = Box::leak(Box::new(settings));
+
+        let framework = CustomFramework::new(settings);
+        let info = framework.get_info();
+        assert_eq!(info.name, "Cobalt");
+        assert_eq!(info.configs, Some(vec!["_cobalt.yml"]));
+        assert!(matches!(info.detection.matching_strategy, FrameworkMatchingStrategy::All));
+        assert!(matches!(
+            info.detection.detectors[1],
+            FrameworkDetectionItem::File { path: "Cargo.toml", content: Some("cobalt") }
+        ));
+        assert_eq!(info.build.command, "cobalt build");
+        assert!(matches!(
+            info.build.command_args.as_ref().unwrap().output,
+            Some(FrameworkBuildArg::Option { short: "-d", long: "--destination" })
+        ));
+        assert_eq!(framework.get_output_dir(std::path::Path::new(".")), "_site");
+    }
+}
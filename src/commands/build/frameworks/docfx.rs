@@ -3,6 +3,7 @@
 // _site
 // docfx build [-o:<output_path>] [-t:<template folder>]
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -57,9 +58,9 @@ impl FrameworkSupport for DocFx {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<DocFxConfig>(configs) {
+            match read_config_files::<DocFxConfig>(cwd, configs) {
                 Ok(c) => {
                     return c.build.dest
                 }
@@ -78,6 +79,7 @@ impl ConfigurationFileDeserialization for DocFxConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use crate::commands::build::frameworks::docfx::DocFx;
 
@@ -87,7 +89,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/docfx/docfx.json"])
         );
 
-        let output = docfx.get_output_dir();
+        let output = docfx.get_output_dir(Path::new("."));
         assert_eq!(output, "dist")
     }
 
@@ -0,0 +1,81 @@
+// index.html containing window.$docsify
+// typically lives in docs/
+// docsify renders markdown at runtime so there is no build step and the directory containing
+// index.html is served as is
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport};
+use crate::commands::build::language::Language;
+
+pub struct Docsify { info: FrameworkInfo }
+
+impl Docsify {
+    fn new() -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Docsify",
+                website: Some("https://docsify.js.org/"),
+                configs: None,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::File {
+                            path: "index.html",
+                            content: Some(r"window\.\$docsify")
+                        },
+                        FrameworkDetectionItem::File {
+                            path: "docs/index.html",
+                            content: Some(r"window\.\$docsify")
+                        }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    // no build required
+                    command: "",
+                    command_args: None,
+                    output_directory: "docs",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Docsify {
+    fn default() -> Self {
+        Docsify::new()
+    }
+}
+
+impl FrameworkSupport for Docsify {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if cwd.join("index.html").is_file() {
+            return String::from(".");
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Docsify;
+
+    #[test]
+    fn test_docsify() {
+        let docsify = Docsify::default();
+
+        let output = docsify.get_output_dir(Path::new("tests/resources/framework_configs/docsify"));
+        assert_eq!(output, ".");
+
+        let output = docsify.get_output_dir(Path::new("tests/resources/framework_configs"));
+        assert_eq!(output, "docs")
+    }
+
+}
@@ -0,0 +1,111 @@
+// docusaurus v1
+// website/siteConfig.js
+// package.json is typically within the website directory
+// docusaurus-build
+
+// publish directory -> website/build/<projectName>
+// where projectName is the value you defined in your siteConfig.js
+
+use std::path::Path;
+use serde::{Deserialize};
+use swc_ecma_ast::{Program};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::js_module::{PropertyAccessor};
+use crate::commands::build::language::Language;
+use crate::doctavious_error::DoctaviousError;
+use crate::doctavious_error::{Result as DoctaviousResult};
+
+#[derive(Deserialize)]
+struct DocusaurusV1Config { project_name: String }
+
+pub struct DocusaurusV1 { info: FrameworkInfo }
+
+impl DocusaurusV1 {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Docusaurus",
+                website: Some("https://v1.docusaurus.io/"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "docusaurus" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "docusaurus-build",
+                    command_args: None,
+                    output_directory: "build",
+                },
+            }
+        }
+    }
+}
+
+impl Default for DocusaurusV1 {
+    fn default() -> Self {
+        DocusaurusV1::new(Some(Vec::from(["siteConfig.js", "website/siteConfig.js"])))
+    }
+}
+
+impl FrameworkSupport for DocusaurusV1 {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some("<2")
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<DocusaurusV1Config>(cwd, configs) {
+                Ok(c) => {
+                    return Path::new(self.info.build.output_directory)
+                        .join(c.project_name)
+                        .to_string_lossy()
+                        .to_string();
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for DocusaurusV1Config {
+
+    fn from_js_module(program: &Program) -> DoctaviousResult<Self> {
+        if let Some(module) = program.as_module() {
+            if let Some(project_name) = module.get_property_as_string("projectName") {
+                return Ok(Self {
+                    project_name
+                });
+            }
+        }
+        Err(DoctaviousError::Msg("invalid config".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::DocusaurusV1;
+
+    #[test]
+    fn test_docusaurus_v1() {
+        let docusaurus = DocusaurusV1::new(
+            Some(vec!["tests/resources/framework_configs/docusaurus1/siteConfig.js"])
+        );
+
+        let output = docusaurus.get_output_dir(Path::new("."));
+        assert_eq!(output, "build/test-site")
+    }
+}
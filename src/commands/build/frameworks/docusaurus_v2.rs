@@ -24,8 +24,7 @@
 // defaults to build
 
 
-// TODO: support monorepo
-
+use std::path::Path;
 use serde::{Deserialize};
 
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -92,9 +91,13 @@ impl FrameworkSupport for DocusaurusV2 {
         &self.info
     }
 
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some(">=2")
+    }
+
     // Vercel checks if there is a a single file (directory) under build and if so uses it
     // otherwise uses build
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, _cwd: &Path) -> String {
         // doesnt support overriding via configuration file
         // TODO: look at package.json scripts build
 
@@ -110,6 +113,7 @@ impl ConfigurationFileDeserialization for DocusaurusV2Config {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkBuildSettings, FrameworkInfo, FrameworkSupport};
     use super::DocusaurusV2;
 
@@ -120,7 +124,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/docusaurus2/docusaurus.config.js"])
         );
 
-        let output = docusaurus.get_output_dir();
+        let output = docusaurus.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 }
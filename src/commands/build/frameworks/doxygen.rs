@@ -0,0 +1,115 @@
+// Doxyfile
+// doxygen
+
+// html -> default which is relative to OUTPUT_DIRECTORY
+// can be changed via OUTPUT_DIRECTORY and HTML_OUTPUT
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport};
+use crate::commands::build::language::Language;
+
+pub struct Doxygen { info: FrameworkInfo }
+
+impl Doxygen {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Doxygen",
+                website: Some("https://www.doxygen.nl/"),
+                configs,
+                language: Language::Cpp,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        FrameworkDetectionItem::Config { content: None }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "doxygen",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: None,
+                        config: Some(FrameworkBuildArg::Arg { index: 1, default_value: Some("Doxyfile") }),
+                        output: None
+                    }),
+                    output_directory: "html",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Doxygen {
+    fn default() -> Self {
+        Doxygen::new(Some(Vec::from(["Doxyfile", "doxyfile"])))
+    }
+}
+
+impl FrameworkSupport for Doxygen {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    // Doxyfile isn't a format we have serde support for so we parse the tags we care about
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            for config in configs {
+                if let Ok(content) = fs::read_to_string(cwd.join(config)) {
+                    let tags = parse_doxyfile(&content);
+                    let html_output = tags.get("HTML_OUTPUT")
+                        .map(|o| o.as_str())
+                        .unwrap_or(self.info.build.output_directory);
+                    return match tags.get("OUTPUT_DIRECTORY") {
+                        Some(dir) => Path::new(dir).join(html_output).to_string_lossy().to_string(),
+                        None => html_output.to_string()
+                    };
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+/// Parses `TAG = value` pairs from a Doxyfile ignoring comments, empty values and continuation
+/// lines as we only care about single value tags
+fn parse_doxyfile(content: &str) -> HashMap<String, String> {
+    let mut tags = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((tag, value)) = line.split_once('=') {
+            let tag = tag.trim();
+            let value = value.trim().trim_matches('"');
+            if tag.is_empty() || tag.ends_with('+') || value.is_empty() {
+                continue;
+            }
+
+            tags.insert(tag.to_string(), value.to_string());
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Doxygen;
+
+    #[test]
+    fn test_doxygen() {
+        let doxygen = Doxygen::new(
+            Some(vec!["tests/resources/framework_configs/doxygen/Doxyfile"])
+        );
+
+        let output = doxygen.get_output_dir(Path::new("."));
+        assert_eq!(output, "build/html")
+    }
+
+}
@@ -8,6 +8,7 @@
 // defaults to _site
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -67,9 +68,9 @@ impl FrameworkSupport for Eleventy {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<EleventyConfig>(configs) {
+            match read_config_files::<EleventyConfig>(cwd, configs) {
                 Ok(c) => {
                     return c.output;
                 }
@@ -105,6 +106,7 @@ impl ConfigurationFileDeserialization for EleventyConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Eleventy;
 
@@ -114,7 +116,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/eleventy/.eleventy.js"])
         );
 
-        let output = eleventy.get_output_dir();
+        let output = eleventy.get_output_dir(Path::new("."));
         assert_eq!(output, String::from("dist"))
     }
 
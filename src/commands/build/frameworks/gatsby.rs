@@ -5,6 +5,7 @@
 
 // gatsby build
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -38,7 +39,7 @@ impl Gatsby {
                 build: FrameworkBuildSettings {
                     command: "gatsby build",
                     command_args: None,
-                    output_directory: "/public",
+                    output_directory: "public",
                 },
             }
         }
@@ -58,9 +59,9 @@ impl FrameworkSupport for Gatsby {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<GatsbyConfig>(configs) {
+            match read_config_files::<GatsbyConfig>(cwd, configs) {
                 Ok(c) => {
                     return c.output;
                 }
@@ -97,6 +98,7 @@ impl ConfigurationFileDeserialization for GatsbyConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Gatsby;
 
@@ -106,7 +108,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/gatsby/gatsby-config.js"])
         );
 
-        let output = gatsby.get_output_dir();
+        let output = gatsby.get_output_dir(Path::new("."));
         assert_eq!(output, "dist")
     }
 
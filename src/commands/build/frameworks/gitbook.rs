@@ -0,0 +1,115 @@
+// legacy GitBook via gitbook-cli
+// book.json
+// gitbook build [book] [output]
+
+// _book -> default
+// gitbook only accepts the output via the output argument so doctavious reads output from
+// book.json and passes it along
+
+use std::path::Path;
+use serde::{Deserialize};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::language::Language;
+
+#[derive(Deserialize)]
+struct GitBookConfig { output: Option<String> }
+
+pub struct GitBook { info: FrameworkInfo }
+
+impl GitBook {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "GitBook",
+                website: Some("https://github.com/GitbookIO/gitbook"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "gitbook-cli" },
+                        FrameworkDetectionItem::Config { content: None }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "gitbook build",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: Some(FrameworkBuildArg::Arg { index: 1, default_value: Some(".") }),
+                        config: None,
+                        output: Some(FrameworkBuildArg::Arg { index: 2, default_value: Some("_book") })
+                    }),
+                    output_directory: "_book",
+                },
+            }
+        }
+    }
+}
+
+impl Default for GitBook {
+    fn default() -> Self {
+        GitBook::new(Some(Vec::from(["book.json"])))
+    }
+}
+
+impl FrameworkSupport for GitBook {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_build_command(&self, cwd: &Path) -> String {
+        let output = self.get_output_dir(cwd);
+        if output == self.info.build.output_directory {
+            return self.info.build.command.to_string();
+        }
+
+        format!("{} . {}", self.info.build.command, output)
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<GitBookConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(output) = c.output {
+                        return output;
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for GitBookConfig {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::GitBook;
+
+    #[test]
+    fn test_gitbook() {
+        let gitbook = GitBook::new(
+            Some(vec!["tests/resources/framework_configs/gitbook/book.json"])
+        );
+
+        let output = gitbook.get_output_dir(Path::new("."));
+        assert_eq!(output, "site");
+        assert_eq!(gitbook.get_build_command(Path::new(".")), "gitbook build . site");
+    }
+
+    #[test]
+    fn test_gitbook_default_output() {
+        let gitbook = GitBook::new(None);
+
+        let output = gitbook.get_output_dir(Path::new("."));
+        assert_eq!(output, "_book");
+        assert_eq!(gitbook.get_build_command(Path::new(".")), "gitbook build");
+    }
+
+}
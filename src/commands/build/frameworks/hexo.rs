@@ -5,6 +5,7 @@
 // hexo generate
 // hexo --config custom.yml
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -58,9 +59,9 @@ impl FrameworkSupport for Hexo {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<HexoConfig>(configs) {
+            match read_config_files::<HexoConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dir) = c.public_dir {
                         return dir;
@@ -81,6 +82,7 @@ impl ConfigurationFileDeserialization for HexoConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Hexo;
 
@@ -90,7 +92,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/hexo/_config.yml"])
         );
 
-        let output = hexo.get_output_dir();
+        let output = hexo.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
@@ -8,6 +8,7 @@
 // /public
 // can be changed via publishDir
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -22,7 +23,7 @@ impl Hugo {
     fn new(configs: Option<Vec<&'static str>>) -> Self {
         Self {
             info: FrameworkInfo {
-                name: "Hexo",
+                name: "Hugo",
                 website: Some("https://gohugo.io/"),
                 configs,
                 language: Language::Go,
@@ -45,7 +46,7 @@ impl Hugo {
                             long: "--destination",
                         })
                     }),
-                    output_directory: "/public",
+                    output_directory: "public",
                 },
             }
         }
@@ -69,9 +70,9 @@ impl FrameworkSupport for Hugo {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<HugoConfig>(configs) {
+            match read_config_files::<HugoConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dir) = c.publish_dir {
                         return dir;
@@ -92,6 +93,7 @@ impl ConfigurationFileDeserialization for HugoConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Hugo;
 
@@ -101,7 +103,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/hugo/config.toml"])
         );
 
-        let output = hugo.get_output_dir();
+        let output = hugo.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
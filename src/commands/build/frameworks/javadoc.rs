@@ -0,0 +1,100 @@
+// javadoc via the project's build tool
+// pom.xml -> mvn javadoc:javadoc -> target/site/apidocs
+// build.gradle / build.gradle.kts -> gradle javadoc -> build/docs/javadoc
+// prefers the maven/gradle wrapper when present
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport};
+use crate::commands::build::language::Language;
+
+pub struct Javadoc { info: FrameworkInfo }
+
+impl Javadoc {
+    fn new() -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Javadoc",
+                website: Some("https://docs.oracle.com/en/java/javase/17/javadoc/javadoc.html"),
+                configs: None,
+                language: Language::Java,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::File { path: "pom.xml", content: None },
+                        FrameworkDetectionItem::File { path: "build.gradle", content: None },
+                        FrameworkDetectionItem::File { path: "build.gradle.kts", content: None }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "mvn javadoc:javadoc",
+                    command_args: None,
+                    output_directory: "target/site/apidocs",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Javadoc {
+    fn default() -> Self {
+        Javadoc::new()
+    }
+}
+
+impl FrameworkSupport for Javadoc {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_build_command(&self, cwd: &Path) -> String {
+        if is_gradle(cwd) {
+            if cwd.join("gradlew").is_file() {
+                return String::from("./gradlew javadoc");
+            }
+            return String::from("gradle javadoc");
+        }
+
+        if cwd.join("mvnw").is_file() {
+            return String::from("./mvnw javadoc:javadoc");
+        }
+
+        self.info.build.command.to_string()
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if is_gradle(cwd) {
+            return String::from("build/docs/javadoc");
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+fn is_gradle(cwd: &Path) -> bool {
+    cwd.join("build.gradle").is_file() || cwd.join("build.gradle.kts").is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Javadoc;
+
+    #[test]
+    fn test_javadoc() {
+        let javadoc = Javadoc::default();
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("pom.xml"), "<project></project>").unwrap();
+        assert_eq!(javadoc.get_build_command(dir.path()), "mvn javadoc:javadoc");
+        assert_eq!(javadoc.get_output_dir(dir.path()), "target/site/apidocs");
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("build.gradle.kts"), "plugins { java }").unwrap();
+        fs::write(dir.path().join("gradlew"), "").unwrap();
+        assert_eq!(javadoc.get_build_command(dir.path()), "./gradlew javadoc");
+        assert_eq!(javadoc.get_output_dir(dir.path()), "build/docs/javadoc");
+    }
+
+}
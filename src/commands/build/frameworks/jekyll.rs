@@ -5,6 +5,7 @@
 // destination: DIR
 // jekyll build -d, --destination DIR
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -64,9 +65,9 @@ impl FrameworkSupport for Jekyll {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<JekyllConfig>(configs) {
+            match read_config_files::<JekyllConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(destination) = c.destination {
                         return destination;
@@ -88,6 +89,7 @@ impl ConfigurationFileDeserialization for JekyllConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Jekyll;
 
@@ -97,7 +99,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/jekyll/_config.yml"])
         );
 
-        let output = jekyll.get_output_dir();
+        let output = jekyll.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
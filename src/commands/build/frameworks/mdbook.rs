@@ -2,6 +2,7 @@
 // ./book -> default
 // change be changed via build.build-dir
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
@@ -57,9 +58,9 @@ impl FrameworkSupport for MDBook {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<MDBookConfig>(configs) {
+            match read_config_files::<MDBookConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(MDBookBuildOptions {build_dir: Some(v)}) = c.build {
                         return v;
@@ -80,6 +81,7 @@ impl ConfigurationFileDeserialization for MDBookConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::MDBook;
 
@@ -89,7 +91,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/mdbook/book.toml"])
         );
 
-        let output = book.get_output_dir();
+        let output = book.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
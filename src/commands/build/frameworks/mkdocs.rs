@@ -2,12 +2,13 @@
 // site --> default
 // change be changed via site_dir
 
+use std::path::Path;
 use serde::{Deserialize};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
 use crate::commands::build::language::Language;
 
 #[derive(Deserialize)]
-struct MKDocsConfig { site_dir: Option<String> }
+pub(crate) struct MKDocsConfig { pub site_dir: Option<String> }
 
 pub struct MKDocs { info: FrameworkInfo }
 
@@ -57,9 +58,9 @@ impl FrameworkSupport for MKDocs {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<MKDocsConfig>(configs) {
+            match read_config_files::<MKDocsConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dir) = c.site_dir {
                         return dir;
@@ -80,6 +81,7 @@ impl ConfigurationFileDeserialization for MKDocsConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::MKDocs;
 
@@ -89,7 +91,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/mkdocs/mkdocs.yml"])
         );
 
-        let output = mkdocs.get_output_dir();
+        let output = mkdocs.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
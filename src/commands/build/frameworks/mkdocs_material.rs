@@ -0,0 +1,97 @@
+// Material for MkDocs is a theme for MkDocs which is enabled via mkdocs.yml
+// theme:
+//   name: material
+// mkdocs build
+
+// site --> default
+// change be changed via site_dir
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::frameworks::mkdocs::MKDocsConfig;
+use crate::commands::build::language::Language;
+
+pub struct MKDocsMaterial { info: FrameworkInfo }
+
+impl MKDocsMaterial {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Material for MkDocs",
+                website: Some("https://squidfunk.github.io/mkdocs-material/"),
+                configs,
+                language: Language::Python,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "mkdocs-material" },
+                        FrameworkDetectionItem::Config { content: Some(r#"^\s*name:\s*['"]?material"#) }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "mkdocs build",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: None,
+                        config: Some(FrameworkBuildArg::Option {
+                            short: "-f",
+                            long: "--config-file"
+                        }),
+                        output: Some(FrameworkBuildArg::Option {
+                            short: "-d",
+                            long: "--site-dir"
+                        })
+                    }),
+                    output_directory: "site",
+                },
+            }
+        }
+    }
+}
+
+impl Default for MKDocsMaterial {
+    fn default() -> Self {
+        MKDocsMaterial::new(Some(Vec::from(["mkdocs.yml"])))
+    }
+}
+
+impl FrameworkSupport for MKDocsMaterial {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<MKDocsConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(dir) = c.site_dir {
+                        return dir;
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::MKDocsMaterial;
+
+    #[test]
+    fn test_mkdocs_material() {
+        let mkdocs = MKDocsMaterial::new(
+            Some(vec!["tests/resources/framework_configs/mkdocs_material/mkdocs.yml"])
+        );
+
+        let output = mkdocs.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
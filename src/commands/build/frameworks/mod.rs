@@ -1,64 +1,195 @@
 use crate::commands::build::framework::FrameworkSupport;
 use crate::commands::build::frameworks::antora::Antora;
 use crate::commands::build::frameworks::astro::Astro;
+use crate::commands::build::frameworks::custom::{CustomFramework, CustomFrameworkSettings};
 use crate::commands::build::frameworks::docfx::DocFx;
+use crate::commands::build::frameworks::docsify::Docsify;
+use crate::commands::build::frameworks::docusaurus_v1::DocusaurusV1;
 use crate::commands::build::frameworks::docusaurus_v2::DocusaurusV2;
+use crate::commands::build::frameworks::doxygen::Doxygen;
 use crate::commands::build::frameworks::eleventy::Eleventy;
 use crate::commands::build::frameworks::gatsby::Gatsby;
+use crate::commands::build::frameworks::gitbook::GitBook;
 use crate::commands::build::frameworks::hexo::Hexo;
 use crate::commands::build::frameworks::hugo::Hugo;
+use crate::commands::build::frameworks::javadoc::Javadoc;
 use crate::commands::build::frameworks::jekyll::Jekyll;
 use crate::commands::build::frameworks::mdbook::MDBook;
 use crate::commands::build::frameworks::mkdocs::MKDocs;
+use crate::commands::build::frameworks::mkdocs_material::MKDocsMaterial;
 use crate::commands::build::frameworks::nextjs::NextJS;
+use crate::commands::build::frameworks::nextra::Nextra;
+use crate::commands::build::frameworks::nuxt_v3::Nuxt3JS;
 use crate::commands::build::frameworks::nuxtjs::NuxtJS;
+use crate::commands::build::frameworks::rspress::Rspress;
+use crate::commands::build::frameworks::rustdoc::Rustdoc;
 use crate::commands::build::frameworks::sphinx::Sphinx;
+use crate::commands::build::frameworks::starlight::Starlight;
 use crate::commands::build::frameworks::sveltekit::SvelteKit;
+use crate::commands::build::frameworks::typedoc::TypeDoc;
 use crate::commands::build::frameworks::vitepress::VitePress;
 use crate::commands::build::frameworks::vuepress::VuePress;
+use crate::commands::build::frameworks::vuepress_v2::VuePressV2;
+use crate::commands::build::frameworks::zola::Zola;
 
 mod antora;
 mod astro;
+pub(crate) mod custom;
 mod docfx;
+mod docsify;
+mod docusaurus_v1;
 mod docusaurus_v2;
+mod doxygen;
 mod eleventy;
 mod gatsby;
+mod gitbook;
 mod hugo;
+mod javadoc;
 mod jekyll;
 mod hexo;
 mod mdbook;
 mod mkdocs;
+mod mkdocs_material;
 mod nextjs;
+mod nextra;
 mod nuxtjs;
 mod nuxt_v3;
+mod rspress;
+mod rustdoc;
 mod sphinx;
+mod starlight;
 mod statiq;
 mod sveltekit;
+mod typedoc;
 mod vitepress;
 mod vuepress;
+mod vuepress_v2;
+mod zola;
 
 
 // I wish Box<dyn> hasnt necessary and maybe its not with a different structure
 // but I'm at a loss for how how to structure these frameworks and allow fn overrides,
 // so I suppose this will have to work until I or someone else comes up with something better
 pub fn get_frameworks() -> Vec<Box<dyn FrameworkSupport>> {
+    // frameworks are detected in order so frameworks built on top of another framework,
+    // for example Starlight on Astro, must come before the framework they are built on
     let mut frameworks = Vec::<Box<dyn FrameworkSupport>>::new();
     frameworks.push(Box::new(Antora::default()));
+    frameworks.push(Box::new(Starlight::default()));
     frameworks.push(Box::new(Astro::default()));
     frameworks.push(Box::new(DocFx::default()));
+    frameworks.push(Box::new(Docsify::default()));
+    frameworks.push(Box::new(DocusaurusV1::default()));
     frameworks.push(Box::new(DocusaurusV2::default()));
     frameworks.push(Box::new(Eleventy::default()));
     frameworks.push(Box::new(Gatsby::default()));
+    frameworks.push(Box::new(GitBook::default()));
     frameworks.push(Box::new(Hexo::default()));
     frameworks.push(Box::new(Hugo::default()));
     frameworks.push(Box::new(Jekyll::default()));
     frameworks.push(Box::new(MDBook::default()));
+    frameworks.push(Box::new(MKDocsMaterial::default()));
     frameworks.push(Box::new(MKDocs::default()));
+    frameworks.push(Box::new(Nextra::default()));
     frameworks.push(Box::new(NextJS::default()));
+    // when the version can't be detected we fallback to the first matching major version
     frameworks.push(Box::new(NuxtJS::default()));
+    frameworks.push(Box::new(Nuxt3JS::default()));
+    frameworks.push(Box::new(Rspress::default()));
     frameworks.push(Box::new(Sphinx::default()));
     frameworks.push(Box::new(SvelteKit::default()));
     frameworks.push(Box::new(VitePress::default()));
     frameworks.push(Box::new(VuePress::default()));
+    frameworks.push(Box::new(VuePressV2::default()));
+    frameworks.push(Box::new(Zola::default()));
+    // API reference generators are included so that they can be selected by name but are never
+    // detected, see is_api_reference_framework
+    frameworks.extend(get_api_reference_frameworks());
     frameworks
 }
+
+/// Returns frameworks that generate API reference documentation from source code
+pub fn get_api_reference_frameworks() -> Vec<Box<dyn FrameworkSupport>> {
+    let mut frameworks = Vec::<Box<dyn FrameworkSupport>>::new();
+    frameworks.push(Box::new(Doxygen::default()));
+    frameworks.push(Box::new(Javadoc::default()));
+    frameworks.push(Box::new(Rustdoc::default()));
+    frameworks.push(Box::new(TypeDoc::default()));
+    frameworks
+}
+
+/// Whether the framework generates API reference documentation. These are only built when
+/// configured, as the framework of a project or its api_reference, because their project files,
+/// such as Cargo.toml, are present in most repositories regardless of whether docs are wanted
+pub fn is_api_reference_framework(name: &str) -> bool {
+    get_api_reference_frameworks()
+        .iter()
+        .any(|f| f.get_info().name.eq_ignore_ascii_case(name))
+}
+
+/// Returns the built-in frameworks merged with user-defined frameworks from doctavious.toml.
+/// User-defined frameworks are detected first and replace built-in frameworks with the same name
+pub fn get_all_frameworks(
+    custom_frameworks: &'static [CustomFrameworkSettings]
+) -> Vec<Box<dyn FrameworkSupport>> {
+    let mut frameworks = Vec::<Box<dyn FrameworkSupport>>::new();
+    for settings in custom_frameworks {
+        frameworks.push(Box::new(CustomFramework::new(settings)));
+    }
+
+    for framework in get_frameworks() {
+        let overridden = frameworks
+            .iter()
+            .any(|f| f.get_info().name.eq_ignore_ascii_case(framework.get_info().name));
+        if !overridden {
+            frameworks.push(framework);
+        }
+    }
+
+    frameworks
+}
+
+/// Returns the framework with the given name, ignoring case
+pub fn get_framework(
+    name: &str,
+    frameworks: Vec<Box<dyn FrameworkSupport>>
+) -> Option<Box<dyn FrameworkSupport>> {
+    frameworks
+        .into_iter()
+        .find(|f| f.get_info().name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::build::frameworks::{get_all_frameworks, get_framework, get_frameworks};
+    use crate::commands::build::frameworks::custom::CustomFrameworkSettings;
+
+    #[test]
+    fn custom_frameworks_should_override_built_in_frameworks_by_name() {
+        let custom_frameworks: Vec<CustomFrameworkSettings> = vec![
+            toml::from_str(r#"
+                name = "hugo"
+                language = "Go"
+                detection = { matching_strategy = "Any", detectors = [{ type = "file", path = "hugo.toml" }] }
+                build = { command = "hugo --minify", output_directory = "public" }
+            "#).unwrap(),
+            toml::from_str(r#"
+                name = "Cobalt"
+                configs = ["_cobalt.yml"]
+                language = "Rust"
+                detection = { matching_strategy = "All", detectors = [{ type = "config" }] }
+                build = { command = "cobalt build", output_directory = "_site" }
+            "#).unwrap(),
+        ];
+        let custom_frameworks: &'static [CustomFrameworkSettings] = Box::leak(custom_frameworks.into_boxed_slice());
+
+        let frameworks = get_all_frameworks(custom_frameworks);
+        assert_eq!(frameworks.len(), get_frameworks().len() + 1);
+
+        let hugo = get_framework("Hugo", get_all_frameworks(custom_frameworks)).unwrap();
+        assert_eq!(hugo.get_info().build.command, "hugo --minify");
+
+        let cobalt = get_framework("cobalt", frameworks).unwrap();
+        assert_eq!(cobalt.get_info().build.output_directory, "_site");
+    }
+}
@@ -5,6 +5,7 @@
 // .next -> default directory
 // change be changed via distDir
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -14,7 +15,7 @@ use crate::doctavious_error::DoctaviousError;
 use crate::doctavious_error::{Result as DoctaviousResult};
 
 #[derive(Deserialize)]
-struct NextJSConfig { output: String }
+pub(crate) struct NextJSConfig { pub output: String }
 
 pub struct NextJS { info: FrameworkInfo }
 
@@ -53,9 +54,9 @@ impl FrameworkSupport for NextJS {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<NextJSConfig>(configs) {
+            match read_config_files::<NextJSConfig>(cwd, configs) {
                 Ok(c) => {
                     return c.output;
                 }
@@ -114,6 +115,7 @@ impl ConfigurationFileDeserialization for NextJSConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::NextJS;
 
@@ -125,7 +127,7 @@ mod tests {
         ] {
             let nextjs = NextJS::new(Some(vec![config]));
 
-            let output = nextjs.get_output_dir();
+            let output = nextjs.get_output_dir(Path::new("."));
             assert_eq!(output, String::from("build"))
         }
 
@@ -0,0 +1,85 @@
+// Nextra is a Next.js based site generator configured by wrapping the Next.js config
+// next.config.js / next.config.mjs
+// const withNextra = require('nextra')({ theme: 'nextra-theme-docs' })
+// module.exports = withNextra({})
+// next build
+
+// .next -> default directory
+// change be changed via distDir
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::frameworks::nextjs::NextJSConfig;
+use crate::commands::build::language::Language;
+
+pub struct Nextra { info: FrameworkInfo }
+
+impl Nextra {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Nextra",
+                website: Some("https://nextra.site/"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "nextra" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "next build",
+                    command_args: None,
+                    output_directory: ".next",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Nextra {
+    fn default() -> Self {
+        Nextra::new(Some(Vec::from(["next.config.js", "next.config.mjs"])))
+    }
+}
+
+impl FrameworkSupport for Nextra {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<NextJSConfig>(cwd, configs) {
+                Ok(c) => {
+                    return c.output;
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Nextra;
+
+    #[test]
+    fn test_nextra() {
+        let nextra = Nextra::new(
+            Some(vec!["tests/resources/framework_configs/nextra/next.config.js"])
+        );
+
+        let output = nextra.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize};
+use std::path::Path;
 use swc_ecma_ast::{Program};
 
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -21,8 +22,9 @@ impl Nuxt3JS {
                 configs,
                 language: Language::Javascript,
                 detection: FrameworkDetector {
-                    matching_strategy: FrameworkMatchingStrategy::All,
+                    matching_strategy: FrameworkMatchingStrategy::Any,
                     detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "nuxt"},
                         FrameworkDetectionItem::Dependency { name: "nuxt3"},
                     ]
                 },
@@ -48,9 +50,13 @@ impl FrameworkSupport for Nuxt3JS {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some(">=3")
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<Nuxt3JSConfig>(configs) {
+            match read_config_files::<Nuxt3JSConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dest) = c.output {
                         return dest;
@@ -96,6 +102,7 @@ impl ConfigurationFileDeserialization for Nuxt3JSConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Nuxt3JS;
 
@@ -104,7 +111,7 @@ mod tests {
         for config in ["tests/resources/framework_configs/nuxt3js/nuxt.config.ts"] {
             let nuxtjs = Nuxt3JS::new(Some(vec![config]));
 
-            let output = nuxtjs.get_output_dir();
+            let output = nuxtjs.get_output_dir(Path::new("."));
             assert_eq!(output, String::from("build"))
         }
 
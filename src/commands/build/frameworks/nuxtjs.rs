@@ -8,6 +8,7 @@
 // nuxt generate
 // dist/
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 
@@ -59,9 +60,13 @@ impl FrameworkSupport for NuxtJS {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some("<3")
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<NuxtJSConfig>(configs) {
+            match read_config_files::<NuxtJSConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dest) = c.output {
                         return dest;
@@ -107,6 +112,7 @@ impl ConfigurationFileDeserialization for NuxtJSConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::NuxtJS;
 
@@ -115,7 +121,7 @@ mod tests {
         for config in ["tests/resources/framework_configs/nuxtjs/nuxt.config.js"] {
             let nuxtjs = NuxtJS::new(Some(vec![config]));
 
-            let output = nuxtjs.get_output_dir();
+            let output = nuxtjs.get_output_dir(Path::new("."));
             assert_eq!(output, String::from("build"))
         }
 
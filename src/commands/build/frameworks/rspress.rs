@@ -0,0 +1,104 @@
+// rspress.config.ts / rspress.config.js / rspress.config.mjs
+// rspress build
+
+// doc_build -> default
+// can be changed via outDir
+
+use std::path::Path;
+use serde::{Deserialize};
+use swc_ecma_ast::{Program};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::js_module::{PropertyAccessor};
+use crate::commands::build::language::Language;
+use crate::doctavious_error::DoctaviousError;
+use crate::doctavious_error::{Result as DoctaviousResult};
+
+#[derive(Deserialize)]
+struct RspressConfig { output: String }
+
+pub struct Rspress { info: FrameworkInfo }
+
+impl Rspress {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Rspress",
+                website: Some("https://rspress.dev/"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "rspress" },
+                        FrameworkDetectionItem::Dependency { name: "@rspress/core" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "rspress build",
+                    command_args: None,
+                    output_directory: "doc_build",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Rspress {
+    fn default() -> Self {
+        Rspress::new(Some(Vec::from(["rspress.config.ts", "rspress.config.js", "rspress.config.mjs"])))
+    }
+}
+
+impl FrameworkSupport for Rspress {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<RspressConfig>(cwd, configs) {
+                Ok(c) => {
+                    return c.output;
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for RspressConfig {
+
+    fn from_js_module(program: &Program) -> DoctaviousResult<Self> {
+        if let Some(module) = program.as_module() {
+            if let Some(output) = module.get_property_as_string("outDir") {
+                return Ok(Self {
+                    output
+                });
+            }
+        }
+        Err(DoctaviousError::Msg("invalid config".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Rspress;
+
+    #[test]
+    fn test_rspress() {
+        let rspress = Rspress::new(
+            Some(vec!["tests/resources/framework_configs/rspress/rspress.config.ts"])
+        );
+
+        let output = rspress.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
@@ -0,0 +1,104 @@
+// cargo doc
+// Cargo.toml
+
+// target/doc -> default
+// target directory can be changed via build.target-dir in .cargo/config.toml or the
+// CARGO_TARGET_DIR environment variable
+
+use std::env;
+use std::path::Path;
+use serde::{Deserialize};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::language::Language;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CargoBuildOptions { target_dir: Option<String> }
+
+#[derive(Deserialize)]
+struct CargoConfig { build: Option<CargoBuildOptions> }
+
+pub struct Rustdoc { info: FrameworkInfo }
+
+impl Rustdoc {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "rustdoc",
+                website: Some("https://doc.rust-lang.org/rustdoc/"),
+                configs,
+                language: Language::Rust,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        FrameworkDetectionItem::File {
+                            path: "Cargo.toml",
+                            content: Some(r"^\[(package|workspace)\]")
+                        }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "cargo doc --no-deps",
+                    command_args: None,
+                    output_directory: "target/doc",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Rustdoc {
+    fn default() -> Self {
+        Rustdoc::new(Some(Vec::from([".cargo/config.toml"])))
+    }
+}
+
+impl FrameworkSupport for Rustdoc {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Ok(target_dir) = env::var("CARGO_TARGET_DIR") {
+            return Path::new(&target_dir).join("doc").to_string_lossy().to_string();
+        }
+
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<CargoConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(target_dir) = c.build.and_then(|b| b.target_dir) {
+                        return Path::new(&target_dir).join("doc").to_string_lossy().to_string();
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for CargoConfig {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Rustdoc;
+
+    #[test]
+    fn test_rustdoc() {
+        let rustdoc = Rustdoc::new(
+            Some(vec!["tests/resources/framework_configs/rustdoc/config.toml"])
+        );
+
+        let output = temp_env::with_var_unset("CARGO_TARGET_DIR", || {
+            rustdoc.get_output_dir(Path::new("."))
+        });
+        assert_eq!(output, "build/doc")
+    }
+
+}
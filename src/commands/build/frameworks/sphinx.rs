@@ -3,8 +3,10 @@
 // i dont see a way to configure this outside env var
 // we could just default it ourselves
 // BUILDDIR env var
+// API reference documentation via sphinx.ext.autodoc is generated as part of sphinx-build
 
 use std::env;
+use std::path::Path;
 use crate::commands::build::framework::{FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport};
 use crate::commands::build::language::Language;
 
@@ -46,7 +48,7 @@ impl Default for Sphinx {
     fn default() -> Self {
         // this is relative to source and i dont think we need it as it doesnt help with build
         // TODO: should we remove?
-        Sphinx::new(Some(vec!["conf.py"]))
+        Sphinx::new(Some(vec!["conf.py", "docs/conf.py", "docs/source/conf.py"]))
     }
 }
 
@@ -55,7 +57,25 @@ impl FrameworkSupport for Sphinx {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    // sphinx-build requires the source and output directories so we pass the directory
+    // containing conf.py along with our output directory
+    fn get_build_command(&self, cwd: &Path) -> String {
+        let source_dir = self.info.configs.as_ref()
+            .and_then(|configs| configs.iter().find(|c| cwd.join(c).is_file()))
+            .and_then(|c| Path::new(c).parent())
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| String::from("."));
+
+        format!(
+            "{} -b html {} {}",
+            self.info.build.command,
+            source_dir,
+            self.get_output_dir(cwd)
+        )
+    }
+
+    fn get_output_dir(&self, _cwd: &Path) -> String {
         if let Ok(build_dir) = env::var("BUILDDIR") {
             return build_dir;
         }
@@ -66,6 +86,7 @@ impl FrameworkSupport for Sphinx {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::Sphinx;
 
@@ -75,7 +96,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/sphinx/config.py"])
         );
 
-        let output = sphinx.get_output_dir();
+        let output = sphinx.get_output_dir(Path::new("."));
         assert_eq!(output, "docs/_build")
     }
 
@@ -86,9 +107,19 @@ mod tests {
                 Some(vec!["tests/resources/framework_configs/sphinx/config.py"])
             );
 
-            let output = sphinx.get_output_dir();
+            let output = sphinx.get_output_dir(Path::new("."));
             assert_eq!(output, "build")
         });
     }
 
+    #[test]
+    fn build_command_should_include_source_and_output_directories() {
+        temp_env::with_var_unset("BUILDDIR", || {
+            let sphinx = Sphinx::new(Some(vec!["sphinx/conf.py"]));
+
+            let command = sphinx.get_build_command(Path::new("tests/resources/framework_configs"));
+            assert_eq!(command, "sphinx-build -b html sphinx docs/_build")
+        });
+    }
+
 }
@@ -0,0 +1,90 @@
+// Starlight is a documentation theme built on top of Astro and is configured as an
+// integration within astro.config.mjs
+// astro build
+// outDir: './my-custom-build-directory'
+// defaults to "./dist"
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::frameworks::astro::AstroConfig;
+use crate::commands::build::language::Language;
+
+pub struct Starlight { info: FrameworkInfo }
+
+impl Starlight {
+
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Starlight",
+                website: Some("https://starlight.astro.build"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "@astrojs/starlight" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "astro build",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: None,
+                        config: Some(FrameworkBuildArg::Option {
+                            short: "",
+                            long: "--config",
+                        }),
+                        output: None,
+                    }),
+                    output_directory: "./dist",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Starlight {
+    fn default() -> Self {
+        Starlight::new(Some(Vec::from(["astro.config.mjs", "astro.config.ts"])))
+    }
+}
+
+impl FrameworkSupport for Starlight {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<AstroConfig>(cwd, configs) {
+                Ok(c) => {
+                    return c.output
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Starlight;
+
+    #[test]
+    fn test_starlight() {
+        let starlight = Starlight::new(
+            Some(vec!["tests/resources/framework_configs/starlight/astro.config.mjs"])
+        );
+
+        let output = starlight.get_output_dir(Path::new("."));
+        assert_eq!(output, "./build")
+    }
+
+}
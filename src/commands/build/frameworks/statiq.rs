@@ -11,6 +11,7 @@
 // https://www.statiq.dev/guide/configuration/settings#configuration-files
 
 
+use std::path::Path;
 use serde::{Serialize, Deserialize, de};
 use swc_ecma_ast::Program;
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -41,7 +42,7 @@ impl Statiq {
                 build: FrameworkBuildSettings {
                     command: "",
                     command_args: None,
-                    output_directory: "",
+                    output_directory: "output",
                 }
             }
         }
@@ -59,9 +60,9 @@ impl FrameworkSupport for Statiq {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<StatiqConfig>(configs) {
+            match read_config_files::<StatiqConfig>(cwd, configs) {
                 Ok(c) => {
                     return String::default();
                 }
@@ -81,6 +82,7 @@ impl ConfigurationFileDeserialization for StatiqConfig {}
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkBuildSettings, FrameworkInfo, FrameworkSupport};
     use super::Statiq;
 
@@ -88,7 +90,7 @@ mod tests {
     fn test_statiq() {
         let statiq = Statiq::new(Some(vec!["tests/resources/framework_configs/statiq/statiq.json"]));
 
-        let output = statiq.get_output_dir();
+        let output = statiq.get_output_dir(Path::new("."));
         assert_eq!(output, "output")
     }
 
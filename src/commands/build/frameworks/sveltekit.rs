@@ -4,6 +4,7 @@
 // dependency - adapter-static
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 
@@ -67,9 +68,9 @@ impl FrameworkSupport for SvelteKit {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<SvelteKitConfig>(configs) {
+            match read_config_files::<SvelteKitConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dest) = c.output {
                         return dest;
@@ -119,6 +120,7 @@ impl ConfigurationFileDeserialization for SvelteKitConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::SvelteKit;
 
@@ -128,7 +130,7 @@ mod tests {
             Some(vec!["tests/resources/framework_configs/sveltekit/svelte.config.js"])
         );
 
-        let output = sveltekit.get_output_dir();
+        let output = sveltekit.get_output_dir(Path::new("."));
         assert_eq!(output, "build")
     }
 
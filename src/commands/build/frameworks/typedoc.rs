@@ -0,0 +1,100 @@
+// typedoc.json
+// typedoc
+
+// docs -> default
+// can be changed via out
+
+use std::path::Path;
+use serde::{Deserialize};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::language::Language;
+
+#[derive(Deserialize)]
+struct TypeDocConfig { out: Option<String> }
+
+pub struct TypeDoc { info: FrameworkInfo }
+
+impl TypeDoc {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "TypeDoc",
+                website: Some("https://typedoc.org/"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::Config { content: None },
+                        FrameworkDetectionItem::Dependency { name: "typedoc" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "typedoc",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: None,
+                        config: Some(FrameworkBuildArg::Option {
+                            short: "",
+                            long: "--options"
+                        }),
+                        output: Some(FrameworkBuildArg::Option {
+                            short: "",
+                            long: "--out"
+                        })
+                    }),
+                    output_directory: "docs",
+                },
+            }
+        }
+    }
+}
+
+impl Default for TypeDoc {
+    fn default() -> Self {
+        TypeDoc::new(Some(Vec::from(["typedoc.json"])))
+    }
+}
+
+impl FrameworkSupport for TypeDoc {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<TypeDocConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(dir) = c.out {
+                        return dir;
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for TypeDocConfig {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::TypeDoc;
+
+    #[test]
+    fn test_typedoc() {
+        let typedoc = TypeDoc::new(
+            Some(vec!["tests/resources/framework_configs/typedoc/typedoc.json"])
+        );
+
+        let output = typedoc.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
@@ -8,6 +8,7 @@
 
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -64,9 +65,9 @@ impl FrameworkSupport for VitePress {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<VitePressConfig>(configs) {
+            match read_config_files::<VitePressConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dest) = c.output {
                         return dest;
@@ -127,6 +128,7 @@ impl ConfigurationFileDeserialization for VitePressConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::VitePress;
 
@@ -139,7 +141,7 @@ mod tests {
         for config in configs {
             let vitepress = VitePress::new(Some(vec![config]));
 
-            let output = vitepress.get_output_dir();
+            let output = vitepress.get_output_dir(Path::new("."));
             assert_eq!(output, String::from("build"))
         }
 
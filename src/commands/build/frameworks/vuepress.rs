@@ -15,6 +15,7 @@
 // .vuepress/config.ts
 
 
+use std::path::Path;
 use serde::{Deserialize};
 use swc_ecma_ast::{Program};
 use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
@@ -24,7 +25,7 @@ use crate::doctavious_error::DoctaviousError;
 use crate::doctavious_error::{Result as DoctaviousResult};
 
 #[derive(Deserialize)]
-struct VuePressConfig { dest: Option<String> }
+pub(crate) struct VuePressConfig { pub dest: Option<String> }
 
 pub struct VuePress { info: FrameworkInfo }
 
@@ -81,9 +82,13 @@ impl FrameworkSupport for VuePress {
         &self.info
     }
 
-    fn get_output_dir(&self) -> String {
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some("<2")
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
         if let Some(configs) = &self.info.configs {
-            match read_config_files::<VuePressConfig>(configs) {
+            match read_config_files::<VuePressConfig>(cwd, configs) {
                 Ok(c) => {
                     if let Some(dest) = c.dest {
                         return dest;
@@ -149,6 +154,7 @@ impl ConfigurationFileDeserialization for VuePressConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::framework::{FrameworkSupport};
     use super::VuePress;
 
@@ -162,7 +168,7 @@ mod tests {
         for config in configs {
             let vuepress = VuePress::new(Some(vec![config]));
 
-            let output = vuepress.get_output_dir();
+            let output = vuepress.get_output_dir(Path::new("."));
             assert_eq!(output, String::from("build"))
         }
 
@@ -0,0 +1,109 @@
+// VuePress 2 moved to defineUserConfig and requires a bundler package
+// docs/.vuepress/config.js
+// docs/.vuepress/config.ts
+// package.json -> "docs:build": "vuepress build docs"
+
+// vuepress build [sourceDir] -d, --dest <dest>
+
+// <sourceDir>/.vuepress/dist
+// can be configured via the dest field
+
+use std::path::Path;
+use crate::commands::build::framework::{FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::frameworks::vuepress::VuePressConfig;
+use crate::commands::build::language::Language;
+
+pub struct VuePressV2 { info: FrameworkInfo }
+
+impl VuePressV2 {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "VuePress 2",
+                website: Some("https://v2.vuepress.vuejs.org/"),
+                configs,
+                language: Language::Javascript,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::Any,
+                    detectors: vec![
+                        FrameworkDetectionItem::Dependency { name: "vuepress" },
+                        FrameworkDetectionItem::Dependency { name: "vuepress-vite" },
+                        FrameworkDetectionItem::Dependency { name: "vuepress-webpack" }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "vuepress build docs",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: Some(FrameworkBuildArg::Arg { index: 1, default_value: Some("docs") }),
+                        config: Some(FrameworkBuildArg::Option {
+                            short: "-c",
+                            long: "--config"
+                        }),
+                        output: Some(FrameworkBuildArg::Option {
+                            short: "-d",
+                            long: "--dest"
+                        })
+                    }),
+                    output_directory: "docs/.vuepress/dist",
+                },
+            }
+        }
+    }
+}
+
+impl Default for VuePressV2 {
+    fn default() -> Self {
+        VuePressV2::new(
+            Some(vec![
+                "docs/.vuepress/config.js",
+                "docs/.vuepress/config.ts"
+            ])
+        )
+    }
+}
+
+impl FrameworkSupport for VuePressV2 {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_version_requirement(&self) -> Option<&'static str> {
+        Some(">=2")
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<VuePressConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(dest) = c.dest {
+                        return dest;
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::VuePressV2;
+
+    #[test]
+    fn test_vuepress_v2() {
+        let vuepress = VuePressV2::new(
+            Some(vec!["tests/resources/framework_configs/vuepress2/config.ts"])
+        );
+
+        let output = vuepress.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
@@ -0,0 +1,101 @@
+// config.toml
+// zola build
+// zola build -o, --output-dir
+
+// public -> default
+// can be changed via output_dir
+
+use std::path::Path;
+use serde::{Deserialize};
+use crate::commands::build::framework::{ConfigurationFileDeserialization, FrameworkBuildArg, FrameworkBuildArgs, FrameworkBuildSettings, FrameworkDetectionItem, FrameworkDetector, FrameworkInfo, FrameworkMatchingStrategy, FrameworkSupport, read_config_files};
+use crate::commands::build::language::Language;
+
+#[derive(Deserialize)]
+struct ZolaConfig { output_dir: Option<String> }
+
+pub struct Zola { info: FrameworkInfo }
+
+impl Zola {
+    fn new(configs: Option<Vec<&'static str>>) -> Self {
+        Self {
+            info: FrameworkInfo {
+                name: "Zola",
+                website: Some("https://www.getzola.org/"),
+                configs,
+                language: Language::Rust,
+                detection: FrameworkDetector {
+                    matching_strategy: FrameworkMatchingStrategy::All,
+                    detectors: vec![
+                        // Hugo also uses config.toml but with baseURL rather than base_url
+                        FrameworkDetectionItem::Config { content: Some(r"^base_url\s*=") }
+                    ]
+                },
+                build: FrameworkBuildSettings {
+                    command: "zola build",
+                    command_args: Some(FrameworkBuildArgs {
+                        source: None,
+                        config: Some(FrameworkBuildArg::Option {
+                            short: "-c",
+                            long: "--config"
+                        }),
+                        output: Some(FrameworkBuildArg::Option {
+                            short: "-o",
+                            long: "--output-dir"
+                        })
+                    }),
+                    output_directory: "public",
+                },
+            }
+        }
+    }
+}
+
+impl Default for Zola {
+    fn default() -> Self {
+        Zola::new(Some(Vec::from(["config.toml"])))
+    }
+}
+
+impl FrameworkSupport for Zola {
+    fn get_info(&self) -> &FrameworkInfo {
+        &self.info
+    }
+
+    fn get_output_dir(&self, cwd: &Path) -> String {
+        if let Some(configs) = &self.info.configs {
+            match read_config_files::<ZolaConfig>(cwd, configs) {
+                Ok(c) => {
+                    if let Some(dir) = c.output_dir {
+                        return dir;
+                    }
+                }
+                Err(e) => {
+                    // log warning/error
+                    println!("{}", e.to_string());
+                }
+            }
+        }
+
+        self.info.build.output_directory.to_string()
+    }
+}
+
+impl ConfigurationFileDeserialization for ZolaConfig {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::commands::build::framework::{FrameworkSupport};
+    use super::Zola;
+
+    #[test]
+    fn test_zola() {
+        let zola = Zola::new(
+            Some(vec!["tests/resources/framework_configs/zola/config.toml"])
+        );
+
+        let output = zola.get_output_dir(Path::new("."));
+        assert_eq!(output, "build")
+    }
+
+}
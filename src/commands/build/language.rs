@@ -1,16 +1,18 @@
 use std::path::{Path, PathBuf};
 use glob::glob;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 use crate::commands::build::package_manager::{PackageManager, PackageManagerInfo};
 use crate::commands::build::projects::project_file::{Proj, ProjectFile};
 
 // TODO: We might need to determine python path in order to do python builds
 
 #[non_exhaustive]
-#[derive(Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Language {
+    Cpp,
     DotNet,
     Go,
+    Java,
     Javascript,
     Python,
     Ruby,
@@ -101,8 +103,11 @@ impl Language {
 
     pub const fn project_files(&self) -> &[ProjectFile] {
         match self {
+            // C/C++ and Java builds, via their build tools, handle their own dependencies
+            Language::Cpp => &[],
             Language::DotNet => &[ProjectFile::CSProj],
             Language::Go => &[ProjectFile::GoMod],
+            Language::Java => &[],
             Language::Javascript => &[ProjectFile::PackageJson],
             Language::Python => &[ProjectFile::PyProject, ProjectFile::PipFile, ProjectFile::RequirementsTxt],
             Language::Ruby => &[ProjectFile::GemFile],
@@ -112,8 +117,10 @@ impl Language {
 
     pub const fn get_package_managers(&self) -> &[PackageManager] {
         match self {
+            Language::Cpp => &[],
             Language::DotNet => &[PackageManager::Nuget],
             Language::Go => &[PackageManager::Go],
+            Language::Java => &[],
             Language::Javascript => &[PackageManager::NPM, PackageManager::PNPM, PackageManager::Yarn],
             Language::Python => &[PackageManager::Poetry, PackageManager::PIP],
             Language::Ruby => &[PackageManager::RubyGems],
@@ -0,0 +1,269 @@
+// Resolves the installed version of a dependency from lock files.
+// Project files typically declare a range (^2.2.0) which isn't enough to determine which major
+// version of a framework is used, for example Nuxt 2 vs Nuxt 3, so we prefer lock files and only
+// fallback to what is declared when the project hasn't been locked.
+// https://github.com/vercel/vercel/blob/main/packages/fs-detectors/src/detect-framework.ts
+
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use semver::{Version, VersionReq};
+use serde_json::Value;
+
+use crate::commands::build::projects::project_file::ProjectFile;
+
+/// Returns the version of the dependency resolved for the project file within `cwd`
+pub(crate) fn resolve_dependency_version(
+    cwd: &Path,
+    project_file: &ProjectFile,
+    dependency: &str
+) -> Option<String> {
+    match project_file {
+        ProjectFile::PackageJson => {
+            read(cwd, "package-lock.json").and_then(|c| from_package_lock(&c, dependency))
+                .or_else(|| read(cwd, "pnpm-lock.yaml").and_then(|c| from_pnpm_lock(&c, dependency)))
+                .or_else(|| read(cwd, "yarn.lock").and_then(|c| from_yarn_lock(&c, dependency)))
+                .or_else(|| read(cwd, "package.json").and_then(|c| from_package_json(&c, dependency)))
+        }
+        ProjectFile::PyProject | ProjectFile::PipFile | ProjectFile::RequirementsTxt => {
+            read(cwd, "poetry.lock").and_then(|c| from_poetry_lock(&c, dependency))
+                .or_else(|| read(cwd, "requirements.txt").and_then(|c| from_requirements(&c, dependency)))
+        }
+        ProjectFile::GemFile => {
+            read(cwd, "Gemfile.lock").and_then(|c| from_gemfile_lock(&c, dependency))
+        }
+        _ => None
+    }
+}
+
+/// Checks if the version satisfies the semver requirement.
+///
+/// Pre-release identifiers are ignored so that pre-releases, such as 2.0.0-beta.21, match the
+/// requirements of the version they lead up to.
+pub(crate) fn version_matches(requirement: &str, version: &str) -> bool {
+    let requirement = match VersionReq::parse(requirement) {
+        Ok(r) => r,
+        Err(_) => return false,
+    };
+
+    match parse_version(version) {
+        Some(v) => requirement.matches(&Version::new(v.major, v.minor, v.patch)),
+        None => false
+    }
+}
+
+/// Leniently parses versions found in lock files, ex: 2.2.0_react@18.2.0, v1.4, 4.3.2
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim().trim_start_matches(|c: char| c == 'v' || c == '=');
+    if let Ok(v) = Version::parse(version) {
+        return Some(v);
+    }
+
+    let core: String = version.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+    let mut parts = core.split('.').filter(|p| !p.is_empty()).map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+    let patch = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+    Some(Version::new(major, minor, patch))
+}
+
+fn read(cwd: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(cwd.join(file)).ok()
+}
+
+// lockfileVersion 2 and 3 contain "packages" keyed by path while version 1 only contains
+// "dependencies" keyed by name
+fn from_package_lock(content: &str, dependency: &str) -> Option<String> {
+    let root: Value = serde_json::from_str(content).ok()?;
+    root.get("packages")
+        .and_then(|p| p.get(format!("node_modules/{}", dependency)))
+        .or_else(|| root.get("dependencies").and_then(|d| d.get(dependency)))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+// lockfileVersion 5 maps dependencies to a version string whereas 6 maps to an object with
+// specifier and version. Both can include peer dependencies which we strip
+// 5 - 2.2.0_react@18.2.0
+// 6 - 2.2.0(react@18.2.0)
+fn from_pnpm_lock(content: &str, dependency: &str) -> Option<String> {
+    let root: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
+    let importer = root.get("importers")
+        .and_then(|i| i.get("."))
+        .unwrap_or(&root);
+
+    ["dependencies", "devDependencies"].iter()
+        .find_map(|section| importer.get(section).and_then(|d| d.get(dependency)))
+        .and_then(|d| match d.get("version") {
+            Some(v) => v.as_str(),
+            None => d.as_str()
+        })
+        .map(|v| v.split(|c: char| c == '_' || c == '(').next().unwrap_or(v).to_string())
+}
+
+// yarn v1
+// "@docusaurus/core@^2.2.0", "@docusaurus/core@^2.0.0":
+//   version "2.2.0"
+// yarn berry
+// "@docusaurus/core@npm:^2.2.0":
+//   version: 2.2.0
+fn from_yarn_lock(content: &str, dependency: &str) -> Option<String> {
+    let mut in_dependency = false;
+    for line in content.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(' ') {
+            in_dependency = line.trim_end_matches(':')
+                .split(", ")
+                .map(|spec| spec.trim().trim_matches('"'))
+                .any(|spec| yarn_spec_name(spec) == dependency);
+            continue;
+        }
+
+        if in_dependency {
+            let line = line.trim();
+            if let Some(version) = line.strip_prefix("version") {
+                return Some(version.trim_start_matches(':').trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    None
+}
+
+// name is everything before the version separator which, for scoped packages, isn't the leading @
+fn yarn_spec_name(spec: &str) -> &str {
+    match spec.char_indices().skip(1).find(|(_, c)| *c == '@') {
+        Some((i, _)) => &spec[..i],
+        None => spec
+    }
+}
+
+// fallback for projects without a lock file which uses the lowest version of the declared range
+fn from_package_json(content: &str, dependency: &str) -> Option<String> {
+    let root: Value = serde_json::from_str(content).ok()?;
+    ["dependencies", "devDependencies"].iter()
+        .find_map(|section| root.get(section).and_then(|d| d.get(dependency)))
+        .and_then(|v| v.as_str())
+        .map(|v| v.trim_start_matches(|c: char| c == '^' || c == '~' || c == '>' || c == '=' || c == ' '))
+        .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|v| v.to_string())
+}
+
+fn from_poetry_lock(content: &str, dependency: &str) -> Option<String> {
+    let root: toml::Value = toml::from_str(content).ok()?;
+    root.get("package")
+        .and_then(|p| p.as_array())
+        .and_then(|packages| packages.iter().find(|p| {
+            p.get("name")
+                .and_then(|n| n.as_str())
+                .filter(|n| n.eq_ignore_ascii_case(dependency))
+                .is_some()
+        }))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+fn from_requirements(content: &str, dependency: &str) -> Option<String> {
+    content.lines()
+        .filter_map(|line| line.split_once("=="))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(dependency))
+        .map(|(_, version)| version.trim().to_string())
+}
+
+// GEM
+//   remote: https://rubygems.org/
+//   specs:
+//     jekyll (4.3.2)
+//       addressable (~> 2.4)
+fn from_gemfile_lock(content: &str, dependency: &str) -> Option<String> {
+    let re = Regex::new(&format!(r"(?m)^ {{4}}{} \(([^)]+)\)$", regex::escape(dependency))).ok()?;
+    re.captures(content)
+        .and_then(|c| c.get(1))
+        .map(|v| v.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::commands::build::lock_file::{resolve_dependency_version, version_matches};
+    use crate::commands::build::projects::project_file::ProjectFile;
+
+    fn resolve(file: &str, content: &str, project_file: ProjectFile, dependency: &str) -> Option<String> {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(file), content).unwrap();
+        resolve_dependency_version(dir.path(), &project_file, dependency)
+    }
+
+    #[test]
+    fn should_resolve_from_package_lock() {
+        let v3 = r#"{ "lockfileVersion": 3, "packages": { "node_modules/nuxt": { "version": "3.4.1" } } }"#;
+        assert_eq!(resolve("package-lock.json", v3, ProjectFile::PackageJson, "nuxt"), Some(String::from("3.4.1")));
+
+        let v1 = r#"{ "lockfileVersion": 1, "dependencies": { "nuxt": { "version": "2.16.3" } } }"#;
+        assert_eq!(resolve("package-lock.json", v1, ProjectFile::PackageJson, "nuxt"), Some(String::from("2.16.3")));
+    }
+
+    #[test]
+    fn should_resolve_from_pnpm_lock() {
+        let v5 = "lockfileVersion: 5.4\ndependencies:\n  '@docusaurus/core': 2.2.0_react@18.2.0\n";
+        assert_eq!(
+            resolve("pnpm-lock.yaml", v5, ProjectFile::PackageJson, "@docusaurus/core"),
+            Some(String::from("2.2.0"))
+        );
+
+        let v6 = "lockfileVersion: '6.0'\nimporters:\n  .:\n    devDependencies:\n      vuepress:\n        specifier: ^2.0.0-beta.61\n        version: 2.0.0-beta.61(@vuepress/client@2.0.0-beta.61)\n";
+        assert_eq!(
+            resolve("pnpm-lock.yaml", v6, ProjectFile::PackageJson, "vuepress"),
+            Some(String::from("2.0.0-beta.61"))
+        );
+    }
+
+    #[test]
+    fn should_resolve_from_yarn_lock() {
+        let v1 = "# yarn lockfile v1\n\n\"@docusaurus/core-utils@^2.2.0\":\n  version \"2.1.0\"\n\n\"@docusaurus/core@2.2.0\", \"@docusaurus/core@^2.2.0\":\n  version \"2.2.0\"\n  resolved \"https://registry.yarnpkg.com/@docusaurus/core/-/core-2.2.0.tgz\"\n";
+        assert_eq!(
+            resolve("yarn.lock", v1, ProjectFile::PackageJson, "@docusaurus/core"),
+            Some(String::from("2.2.0"))
+        );
+
+        let berry = "__metadata:\n  version: 6\n\n\"nuxt@npm:^3.4.0\":\n  version: 3.4.1\n  resolution: \"nuxt@npm:3.4.1\"\n";
+        assert_eq!(resolve("yarn.lock", berry, ProjectFile::PackageJson, "nuxt"), Some(String::from("3.4.1")));
+    }
+
+    #[test]
+    fn should_resolve_from_poetry_lock() {
+        let lock = "[[package]]\nname = \"mkdocs\"\nversion = \"1.4.2\"\n\n[[package]]\nname = \"sphinx\"\nversion = \"6.1.3\"\n";
+        assert_eq!(resolve("poetry.lock", lock, ProjectFile::PyProject, "sphinx"), Some(String::from("6.1.3")));
+    }
+
+    #[test]
+    fn should_resolve_from_gemfile_lock() {
+        let lock = "GEM\n  remote: https://rubygems.org/\n  specs:\n    jekyll (4.3.2)\n      addressable (~> 2.4)\n    jekyll-feed (0.17.0)\n";
+        assert_eq!(resolve("Gemfile.lock", lock, ProjectFile::GemFile, "jekyll"), Some(String::from("4.3.2")));
+    }
+
+    #[test]
+    fn should_fallback_to_declared_version() {
+        let package_json = r#"{ "devDependencies": { "vuepress": "^1.9.9" } }"#;
+        assert_eq!(
+            resolve("package.json", package_json, ProjectFile::PackageJson, "vuepress"),
+            Some(String::from("1.9.9"))
+        );
+    }
+
+    #[test]
+    fn should_match_version_requirements() {
+        assert!(version_matches(">=3", "3.4.1"));
+        assert!(!version_matches(">=3", "2.16.3"));
+        assert!(version_matches(">=2", "2.0.0-beta.61"));
+        assert!(version_matches("<2", "1.9"));
+        assert!(!version_matches("<2", "not a version"));
+    }
+}
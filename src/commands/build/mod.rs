@@ -1,20 +1,43 @@
+pub(crate) mod cache;
+pub(crate) mod context;
 mod framework;
-mod frameworks;
+pub(crate) mod frameworks;
 mod js_module;
 mod language;
 mod package_manager;
 mod framework_detection;
+mod lock_file;
 mod projects;
+pub(crate) mod staging;
 
-use std::process::Command;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::process::{Command, ExitStatus};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use clap::Parser;
-use crate::commands::build::framework_detection::detect_framework;
-use crate::commands::build::frameworks::get_frameworks;
+use serde::Serialize;
+use crate::commands::build::cache::{BuildCache, compute_cache_key};
+use crate::commands::build::context::{
+    BuildContext, GitInfo, get_build_environment, get_context_settings
+};
+use crate::commands::build::framework_detection::{detect_framework, detect_framework_version, detect_package_manager};
+use crate::commands::build::framework::FrameworkSupport;
+use crate::commands::build::frameworks::{
+    get_all_frameworks, get_api_reference_frameworks, get_framework, is_api_reference_framework
+};
+use crate::commands::build::package_manager::PackageManager;
+use crate::commands::build::staging::{
+    ApiReferenceResult, BuildResult, BuildsManifest, clean_staging_dir, stage_output, write_manifest
+};
+use crate::constants::{DEFAULT_API_REFERENCE_PATH, DEFAULT_BUILD_OUTPUT_DIR};
 use crate::DOCTAVIOUS_DIR;
-use crate::doctavious_error::Result;
+use crate::doctavious_error::{DoctaviousError, Result};
 use crate::output::Output;
-use crate::settings::{SETTINGS, SETTINGS_FILE};
+use crate::settings::{
+    BuildApiReferenceSettings, BuildContextSettings, BuildProjectSettings, BuildSettings, SETTINGS,
+    SETTINGS_FILE
+};
+use crate::utils::slugify;
 
 // https://vercel.com/docs/project-configuration#project-configuration/install-command
 // https://github.com/vercel/vercel/search?p=3&q=InstallCommand
@@ -30,171 +53,577 @@ pub(crate) struct BuildCommand {
     // should this just find framework and show command it will run?
     #[arg(long, short, help = "Dry run: show instructions without running them")]
     pub dry: bool,
-    // context Specify a build_mod context or branch (contexts: "production", "deploy-preview", "branch-deploy", "dev") (default: "production")
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = BuildContext::Production,
+        help = "Build context used to select settings from [build.context.<name>] in doctavious.toml"
+    )]
+    pub context: BuildContext,
 
     // option can be used to provide a working directory (that can be different from the current directory) when running CLI commands.
-    // --cwd
-    // pub cwd: String
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Directory of the project to build. Defaults to the projects in doctavious.toml \
+                or the current directory when none are configured"
+    )]
+    pub cwd: Option<PathBuf>,
 
     // The --debug option, shorthand -d, can be used to provide a more verbose output when running Vercel CLI commands.
 
     // publish
+
+    #[arg(long, help = "Skip installing dependencies before building")]
+    pub skip_install: bool,
+
+    #[arg(long, help = "Always build rather than restoring output from the build cache")]
+    pub no_cache: bool,
 }
 
 pub(crate) fn handle_build_command(command: BuildCommand, output: Option<Output>) -> Result<()> {
-    // check if doctavious.toml is present
+    // vercel has concept of builders (@vercel/static) which is different than frameworks
+    let options = BuildOptions {
+        staging_dir: Path::new(DEFAULT_BUILD_OUTPUT_DIR),
+        context: command.context,
+        dry: command.dry,
+        skip_install: command.skip_install,
+        cache: if command.no_cache || command.dry { None } else { Some(BuildCache::user()) },
+    };
+    let manifest = build_projects(command.cwd.as_deref(), get_build_settings(), &options)?;
+    if command.dry {
+        return Ok(());
+    }
+
+    let failed = manifest.builds.iter().filter(|b| !b.success).count();
+    if failed > 0 {
+        return Err(DoctaviousError::BuildError(format!(
+            "{} of {} builds failed", failed, manifest.builds.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Build settings from doctavious.toml when present
+pub(crate) fn get_build_settings() -> Option<&'static BuildSettings> {
     if SETTINGS_FILE.exists() {
-        // run build command from settings if it exists otherwise return failure?
-        // probably wouldnt hurt to try to infer if build isnt present
-        if let Some(build_settings) = &SETTINGS.build_settings {
-            if build_settings.command.trim().is_empty() {
-                // return Err
-            }
+        SETTINGS.build_settings.as_ref()
+    } else {
+        None
+    }
+}
 
-            // execute and return - i think spawn and wait is preferred which should stream output
-            // output executes command as a child process waiting for it to finish and collecting all of its output
-            // spawn executes command as a child process returning a handle to it
-            let output = Command::new("").output();
-            match output {
-                Ok(o) => {
-                    // status
-                    // stderror
-                    // stdout
-                }
-                Err(e) => {
-
-                }
-            }
+/// Builds all projects, staging their output and writing builds.json unless it is a dry run.
+///
+/// Failed builds don't stop the remaining projects from building and are recorded, along with
+/// their error, in the returned manifest.
+pub(crate) fn build_projects(
+    cwd: Option<&Path>,
+    build_settings: Option<&'static BuildSettings>,
+    options: &BuildOptions,
+) -> Result<BuildsManifest> {
+    let projects = get_projects(cwd, build_settings);
+    validate_project_names(&projects)?;
+
+    // Delete output from potential previous builds so that stale files never end up deployed
+    if !options.dry {
+        clean_staging_dir(options.staging_dir)?;
+    }
+
+    let mut manifest = BuildsManifest::new(options.context);
+    for project in projects {
+        let result = build(&project, build_settings, options);
+        if let Some(error) = &result.error {
+            eprintln!("build of {} failed: {}", result.project, error);
         }
+        manifest.builds.push(result);
+    }
+
+    if !options.dry {
+        write_manifest(options.staging_dir, &manifest)?;
     }
 
-    // TODO
-    // Delete output directory from potential previous build
-    // also delete .doctavious/output -- assuming we are putting things here
-    // Create fresh new output directory
-    // write builds.json file  (includes errors)
-    // Store the build result to generate the final `config.json` after all builds have completed
+    Ok(manifest)
+}
 
+/// Determines which projects should be built.
+///
+/// A `--cwd` takes precedence and picks up the settings of the project with the same root when
+/// one is configured. Otherwise all configured projects are built, falling back to the current
+/// directory when none are configured.
+pub(crate) fn get_projects(
+    cwd: Option<&Path>,
+    build_settings: Option<&BuildSettings>
+) -> Vec<BuildProjectSettings> {
+    let projects = build_settings
+        .and_then(|s| s.projects.clone())
+        .unwrap_or_default();
+
+    if let Some(cwd) = cwd {
+        let project = projects
+            .into_iter()
+            .find(|p| Path::new(&p.root) == cwd)
+            .unwrap_or_else(|| BuildProjectSettings {
+                root: cwd.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        return vec![project];
+    }
 
-    // vercel has concept of builders (@vercel/static) which is different than frameworks
+    if projects.is_empty() {
+        return vec![BuildProjectSettings {
+            root: String::from("."),
+            ..Default::default()
+        }];
+    }
 
+    projects
+}
 
-    let detected_framework = detect_framework(get_frameworks());
-    if let Some(detected_framework) = detected_framework {
-        let info = detected_framework.get_info();
-        println!("build command {}", &info.build.command);
-
-        let build_command = info.build.command;
-        // let (program, args) = if build_command.contains(" ") {
-        //     let split_command: Vec<&str> = build_command.splitn(2, " ").collect();
-        //     (split_command[0], Some(split_command[1]))
-        // } else {
-        //     (build_command, None)
-        // };
-        //
-        // let mut build_process_command = Command::new(program);
-        // if let Some(args) = args {
-        //     build_process_command.arg(args);
-        // }
-        //
-        // let build_process = build_process_command.spawn();
-
-        // if (process.platform === 'win32') {
-        //     await spawnAsync('cmd.exe', ['/C', command], opts);
-        // } else {
-        //     await spawnAsync('sh', ['-c', command], opts);
-        // }
-
-        // let build_process = Command::new("sh").args(["-c", build_command]).spawn();
-        let build_process = if cfg!(target_os = "windows") {
-            Command::new("cmd").args(["/C", build_command]).spawn()
-        } else {
-            Command::new("sh").args(["-c", build_command]).spawn()
-        };
+/// Options which apply to the build of every project
+pub(crate) struct BuildOptions<'a> {
+    /// Directory the output of each project is copied to
+    pub staging_dir: &'a Path,
+    pub context: BuildContext,
+    pub dry: bool,
+    pub skip_install: bool,
+    /// Build cache used to restore and store output. None when caching is disabled
+    pub cache: Option<BuildCache>,
+}
 
-        match build_process {
-            Ok(mut process) => {
-                let result = process.wait();
-                match result {
-                    Ok(r) => {
-
-                    }
-                    Err(e) => {
-                        println!("child error {e}");
-                    }
-                }
+/// Everything, other than the files of the project, that influences the output of a build
+#[derive(Serialize)]
+struct BuildFingerprint<'a> {
+    doctavious_version: &'static str,
+    project: &'a BuildProjectSettings,
+    build_settings: Option<&'a BuildSettings>,
+    environment: BTreeMap<&'a String, &'a String>,
+}
+
+// https://github.com/vercel/vercel/blob/61de63d2859c740d49ba1d28288fb7242886420c/packages/cli/src/commands/build.ts#L659
+// https://github.com/vercel/vercel/blob/61de63d2859c740d49ba1d28288fb7242886420c/packages/static-build/src/index.ts#L1
+// supporting dir probably makes mono-repos with separate docs easier for end-user
+/// Builds a single project with all paths resolved against the project root and stages its
+/// output within the staging directory.
+/// Context settings take precedence over project settings which take precedence over the top
+/// level build settings.
+pub(crate) fn build(
+    project: &BuildProjectSettings,
+    build_settings: Option<&'static BuildSettings>,
+    options: &BuildOptions,
+) -> BuildResult {
+    let mut result = BuildResult {
+        project: get_project_name(project),
+        root: project.root.to_string(),
+        ..Default::default()
+    };
+
+    let git = GitInfo::discover(Path::new(&project.root));
+    let context_settings = get_context_settings(options.context, git.branch.as_deref(), build_settings);
+    let environment = get_build_environment(options.context, &git, build_settings, context_settings);
+
+    let start = Instant::now();
+    let cache_key = options.cache.as_ref().and_then(|_| {
+        get_cache_key(project, build_settings, &environment)
+            .map_err(|e| eprintln!("unable to compute cache key for {}: {}", result.project, e))
+            .ok()
+    });
+    if let (Some(cache), Some(key)) = (&options.cache, &cache_key) {
+        match cache.restore(key, &options.staging_dir.join(&result.project)) {
+            Ok(Some(cached)) => {
+                println!("restored {} from build cache", result.project);
+                return BuildResult {
+                    cached: true,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    ..cached
+                };
             }
-            Err(e) => {
-                println!("{:?}", e);
+            Ok(None) => {}
+            Err(e) => eprintln!("unable to restore {} from build cache: {}", result.project, e),
+        }
+    }
+
+    let outcome = build_project(project, build_settings, context_settings, &environment, options, &mut result)
+        .and_then(|_| match &project.api_reference {
+            Some(api_reference) => build_api_reference(
+                project, api_reference, build_settings, &environment, options, &mut result
+            ),
+            None => Ok(()),
+        });
+    match outcome {
+        Ok(_) => result.success = true,
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result.duration_ms = start.elapsed().as_millis() as u64;
+
+    // failing to cache output shouldn't fail an otherwise successful build
+    if let (Some(cache), Some(key), true) = (&options.cache, cache_key, result.success) {
+        result.cache_key = Some(key.to_string());
+        if let Err(e) = cache.store(&key, &options.staging_dir.join(&result.project), &result) {
+            eprintln!("unable to store {} in build cache: {}", result.project, e);
+        }
+    }
+
+    result
+}
+
+/// Build command from settings, if any. A command for the build context is the most specific so
+/// it overrides the project command, which in turn overrides the top level command
+fn get_configured_command(
+    project: &BuildProjectSettings,
+    build_settings: Option<&BuildSettings>,
+    context_settings: Option<&BuildContextSettings>,
+) -> Option<String> {
+    context_settings.and_then(|c| c.command.as_ref())
+        .or(project.command.as_ref())
+        .or(build_settings.map(|s| &s.command))
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+}
+
+fn build_project(
+    project: &BuildProjectSettings,
+    build_settings: Option<&'static BuildSettings>,
+    context_settings: Option<&BuildContextSettings>,
+    environment: &HashMap<String, String>,
+    options: &BuildOptions,
+    result: &mut BuildResult,
+) -> Result<()> {
+    let cwd = Path::new(&project.root);
+    if !cwd.is_dir() {
+        return Err(DoctaviousError::BuildError(format!(
+            "project root {} is not a directory", project.root
+        )));
+    }
+
+    // we still detect the framework when a build command is configured as its language
+    // determines which package managers we look for
+    let frameworks = get_available_frameworks(build_settings);
+    let framework = match &project.framework {
+        Some(name) => {
+            let framework = get_framework(name, frameworks).ok_or_else(|| {
+                DoctaviousError::BuildError(format!("unsupported framework {}", name))
+            })?;
+            result.framework_version = detect_framework_version(cwd, framework.get_info());
+            Some(framework)
+        }
+        None => {
+            let frameworks = frameworks
+                .into_iter()
+                .filter(|f| !is_api_reference_framework(f.get_info().name))
+                .collect();
+            detect_framework(cwd, frameworks).map(|matched| {
+                result.framework_version = matched.detected_version;
+                matched.framework
+            })
+        }
+    };
+    result.framework = framework.as_ref().map(|f| f.get_info().name.to_string());
+
+    // run build command from settings if it exists otherwise infer it from the framework
+    let build_command = match get_configured_command(project, build_settings, context_settings) {
+        Some(c) => c,
+        None => match &framework {
+            Some(framework) => framework.get_build_command(cwd),
+            None => {
+                return Err(DoctaviousError::BuildError(format!(
+                    "unable to detect framework for {}. Please provide a build command in doctavious.toml",
+                    project.root
+                )));
             }
         }
+    };
+    result.command = Some(build_command.to_string());
+
+    let output_directory = project.output_directory.as_ref()
+        .or(build_settings.and_then(|s| s.output_directory.as_ref()))
+        .map(|o| o.to_string())
+        .or(framework.as_ref().map(|f| f.get_output_dir(cwd)));
+    result.output_directory = output_directory.clone();
+
+    if !options.skip_install {
+        let install_command = project.install_command.as_ref()
+            .or(build_settings.and_then(|s| s.install_command.as_ref()));
+        let package_managers = match &framework {
+            Some(framework) => framework.get_info().language.get_package_managers(),
+            None => PackageManager::ALL,
+        };
+        install_dependencies(cwd, install_command, package_managers, environment, options.dry)?;
+    }
 
+    // some frameworks, such as Docsify, don't have a build step and their output is served as is
+    if build_command.is_empty() {
+        println!("no build required for {}", &project.root);
     } else {
+        println!("build command {} in {} ({} context)", &build_command, &project.root, options.context);
+    }
 
+    if options.dry {
+        return Ok(());
     }
 
-    // for framework in get_frameworks() {
-    //     // check if framework is supported / detected
-    //     println!("{}", serde_json::to_string(framework.get_info())?);
-    //     let info = framework.get_info();
-    //     if info.detected() {
-    //
-    //     }
-    //
-    // }
+    if !build_command.is_empty() {
+        let status = run_command(&build_command, cwd, environment)?;
+        result.exit_code = status.code();
+        if !status.success() {
+            return Err(DoctaviousError::BuildError(format!(
+                "build command `{}` failed with {}", build_command, status
+            )));
+        }
+    }
+
+    let output_directory = output_directory.ok_or_else(|| {
+        DoctaviousError::BuildError(format!(
+            "unable to determine output directory for {}. Please provide an output_directory in doctavious.toml",
+            project.root
+        ))
+    })?;
+    let staged = stage_output(&cwd.join(output_directory), &options.staging_dir.join(&result.project))?;
+    result.file_count = staged.file_count;
+    result.total_size = staged.total_size;
 
     Ok(())
 }
 
-// https://github.com/vercel/vercel/blob/61de63d2859c740d49ba1d28288fb7242886420c/packages/cli/src/commands/build.ts#L659
-// https://github.com/vercel/vercel/blob/61de63d2859c740d49ba1d28288fb7242886420c/packages/static-build/src/index.ts#L1
-// supporting dir probably makes mono-repos with separate docs easier for end-user
-pub(crate) fn build(
-    dir: Option<PathBuf> // maybe we always assume current working directory or we change how to get settings
+/// Builds the API reference of a project and copies it into the project's staged output so
+/// that API reference documentation is deployed along with the site
+fn build_api_reference(
+    project: &BuildProjectSettings,
+    api_reference: &BuildApiReferenceSettings,
+    build_settings: Option<&'static BuildSettings>,
+    environment: &HashMap<String, String>,
+    options: &BuildOptions,
+    result: &mut BuildResult,
 ) -> Result<()> {
-    // check if doctavious.toml is present
-    if SETTINGS_FILE.exists() {
-        // run build command from settings if it exists otherwise return failure?
-        // probably wouldnt hurt to try to infer if build isnt present
-        if let Some(build_settings) = &SETTINGS.build_settings {
-            if build_settings.command.trim().is_empty() {
-                // return Err
-            }
+    let root = api_reference.root.as_ref().unwrap_or(&project.root);
+    let cwd = Path::new(root);
+    if !cwd.is_dir() {
+        return Err(DoctaviousError::BuildError(format!(
+            "API reference root {} is not a directory", root
+        )));
+    }
+
+    let framework = match &api_reference.framework {
+        Some(name) => get_framework(name, get_available_frameworks(build_settings)).ok_or_else(|| {
+            DoctaviousError::BuildError(format!("unsupported framework {}", name))
+        })?,
+        None => detect_framework(cwd, get_api_reference_frameworks())
+            .map(|matched| matched.framework)
+            .ok_or_else(|| {
+                DoctaviousError::BuildError(format!(
+                    "unable to detect API reference generator for {}", root
+                ))
+            })?,
+    };
+
+    let command = api_reference.command.as_ref()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| framework.get_build_command(cwd));
+    let output_directory = api_reference.output_directory.clone()
+        .unwrap_or_else(|| framework.get_output_dir(cwd));
+    let path = api_reference.path.clone()
+        .unwrap_or_else(|| DEFAULT_API_REFERENCE_PATH.to_string());
+    result.api_reference = Some(ApiReferenceResult {
+        framework: framework.get_info().name.to_string(),
+        command: command.to_string(),
+        output_directory: output_directory.to_string(),
+        path: path.to_string(),
+    });
+
+    // dependencies have already been installed when the API reference shares the project root
+    if !options.skip_install && root != &project.root {
+        let package_managers = framework.get_info().language.get_package_managers();
+        install_dependencies(cwd, None, package_managers, environment, options.dry)?;
+    }
+
+    println!("API reference command {} in {}", &command, root);
+    if options.dry {
+        return Ok(());
+    }
+
+    let status = run_command(&command, cwd, environment)?;
+    if !status.success() {
+        return Err(DoctaviousError::BuildError(format!(
+            "API reference command `{}` failed with {}", command, status
+        )));
+    }
+
+    let staged = stage_output(
+        &cwd.join(output_directory),
+        &options.staging_dir.join(&result.project).join(path)
+    )?;
+    result.file_count += staged.file_count;
+    result.total_size += staged.total_size;
+
+    Ok(())
+}
 
-            // execute and return - i think spawn and wait is preferred which should stream output
-            // output executes command as a child process waiting for it to finish and collecting all of its output
-            // spawn executes command as a child process returning a handle to it
-            let output = Command::new("").output();
-            match output {
-                Ok(o) => {
-                    // status
-                    // stderror
-                    // stdout
-                }
-                Err(e) => {
-
-                }
+/// Computes the build cache key of the project.
+///
+/// Along with the project inputs we include lock files and framework configs, in the project
+/// root and repository root, as these are often outside of the configured input globs.
+/// Git variables are excluded from the environment as they change with every commit.
+fn get_cache_key(
+    project: &BuildProjectSettings,
+    build_settings: Option<&'static BuildSettings>,
+    environment: &HashMap<String, String>,
+) -> Result<String> {
+    let root = Path::new(&project.root);
+    let mut roots = vec![root];
+    if let Some(api_reference_root) = project.api_reference.as_ref().and_then(|a| a.root.as_ref()) {
+        roots.push(Path::new(api_reference_root));
+    }
+
+    let mut directories = vec![root.to_path_buf()];
+    if let Some(workdir) = git2::Repository::discover(root).ok().and_then(|r| r.workdir().map(|w| w.to_path_buf())) {
+        directories.push(workdir);
+    }
+
+    let mut additional_files = vec![];
+    for directory in &directories {
+        for package_manager in PackageManager::ALL {
+            additional_files.push(directory.join(package_manager.info().lock_file));
+        }
+    }
+    for framework in get_available_frameworks(build_settings) {
+        if let Some(configs) = &framework.get_info().configs {
+            additional_files.extend(configs.iter().map(|c| root.join(c)));
+        }
+    }
+
+    let inputs = project.inputs.as_ref()
+        .or(build_settings.and_then(|s| s.inputs.as_ref()));
+    let fingerprint = BuildFingerprint {
+        doctavious_version: env!("CARGO_PKG_VERSION"),
+        project,
+        build_settings,
+        environment: environment.iter()
+            .filter(|(k, _)| k.as_str() != "DOCTAVIOUS_BRANCH" && k.as_str() != "DOCTAVIOUS_COMMIT_SHA")
+            .collect(),
+    };
+
+    compute_cache_key(&roots, inputs, &additional_files, &fingerprint)
+}
+
+/// Built-in frameworks merged with user-defined frameworks from doctavious.toml
+fn get_available_frameworks(
+    build_settings: Option<&'static BuildSettings>
+) -> Vec<Box<dyn FrameworkSupport>> {
+    let custom_frameworks = build_settings
+        .and_then(|s| s.frameworks.as_deref())
+        .unwrap_or_default();
+    get_all_frameworks(custom_frameworks)
+}
+
+/// Ensures each project stages its output to its own directory, as two projects with the same
+/// name would otherwise overwrite each other's output
+fn validate_project_names(projects: &[BuildProjectSettings]) -> Result<()> {
+    let mut names: HashMap<String, &str> = HashMap::new();
+    for project in projects {
+        let name = get_project_name(project);
+        if let Some(other) = names.insert(name.to_string(), &project.root) {
+            return Err(DoctaviousError::BuildError(format!(
+                "projects {} and {} are both named {}. Set a unique name for one of them",
+                other, project.root, name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Name of the project which defaults to a slug of its root
+fn get_project_name(project: &BuildProjectSettings) -> String {
+    if let Some(name) = &project.name {
+        return name.to_string();
+    }
+
+    let name = slugify(&project.root);
+    if name.is_empty() {
+        String::from("default")
+    } else {
+        name
+    }
+}
+
+/// Installs dependencies prior to building.
+///
+/// A configured install command takes precedence, with an empty command skipping the install
+/// entirely, otherwise we fallback to the install command of the detected package manager.
+fn install_dependencies(
+    cwd: &Path,
+    install_command: Option<&String>,
+    package_managers: &[PackageManager],
+    environment: &HashMap<String, String>,
+    dry: bool
+) -> Result<()> {
+    let install_command = match install_command {
+        Some(install_command) => install_command.trim().to_string(),
+        None => match detect_package_manager(cwd, package_managers) {
+            Some(package_manager) => package_manager.info().install_command.to_string(),
+            None => {
+                // TODO: log
+                println!("no package manager detected. skipping install");
+                return Ok(());
             }
         }
+    };
+
+    if install_command.is_empty() {
+        return Ok(());
     }
 
-    for framework in get_frameworks() {
-        // check if framework is supported / detected
-        println!("{}", serde_json::to_string(framework.get_info())?);
+    println!("install command {}", &install_command);
+    if dry {
+        return Ok(());
     }
 
-    // infer build
-    // if not log and then try to infer build system
-    // log each attempt along with where its looking
-    // warn if no build system is found and return error
+    let status = run_command(&install_command, cwd, environment)?;
+    if !status.success() {
+        return Err(DoctaviousError::BuildError(format!(
+            "install command `{}` failed with {}", install_command, status
+        )));
+    }
 
     Ok(())
 }
 
+// execute and return - i think spawn and wait is preferred which should stream output
+// output executes command as a child process waiting for it to finish and collecting all of its output
+// spawn executes command as a child process returning a handle to it
+// environment variables are added to those inherited from the current process
+fn run_command(command: &str, cwd: &Path, environment: &HashMap<String, String>) -> Result<ExitStatus> {
+    // if (process.platform === 'win32') {
+    //     await spawnAsync('cmd.exe', ['/C', command], opts);
+    // } else {
+    //     await spawnAsync('sh', ['-c', command], opts);
+    // }
+    let mut process = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", command]).current_dir(cwd).envs(environment).spawn()?
+    } else {
+        Command::new("sh").args(["-c", command]).current_dir(cwd).envs(environment).spawn()?
+    };
+
+    Ok(process.wait()?)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use crate::commands::build::frameworks::get_frameworks;
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::commands::build::{
+        build, get_configured_command, get_project_name, get_projects, validate_project_names,
+        BuildOptions
+    };
+    use crate::commands::build::context::BuildContext;
     use crate::doctavious_error::Result as DoctaviousResult;
+    use crate::settings::{
+        BuildApiReferenceSettings, BuildContextSettings, BuildProjectSettings, BuildSettings
+    };
 
     #[test]
     fn should_iterate() -> DoctaviousResult<()> {
@@ -205,4 +634,177 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn should_default_to_current_directory() {
+        let projects = get_projects(None, None);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].root, ".");
+    }
+
+    #[test]
+    fn should_build_configured_projects() {
+        let settings = BuildSettings {
+            projects: Some(vec![
+                BuildProjectSettings { root: String::from("docs"), ..Default::default() },
+                BuildProjectSettings { root: String::from("packages/api/docs"), ..Default::default() },
+            ]),
+            ..Default::default()
+        };
+
+        let projects = get_projects(None, Some(&settings));
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[1].root, "packages/api/docs");
+    }
+
+    #[test]
+    fn cwd_should_use_matching_project_settings() {
+        let settings = BuildSettings {
+            projects: Some(vec![
+                BuildProjectSettings {
+                    root: String::from("docs"),
+                    framework: Some(String::from("mdbook")),
+                    ..Default::default()
+                },
+                BuildProjectSettings { root: String::from("website"), ..Default::default() },
+            ]),
+            ..Default::default()
+        };
+
+        let projects = get_projects(Some(Path::new("docs/")), Some(&settings));
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].framework, Some(String::from("mdbook")));
+
+        let projects = get_projects(Some(Path::new("guides")), Some(&settings));
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].root, "guides");
+        assert_eq!(projects[0].framework, None);
+    }
+
+    #[test]
+    fn project_name_should_default_to_slug_of_root() {
+        let project = BuildProjectSettings { root: String::from("packages/api/docs"), ..Default::default() };
+        assert_eq!(get_project_name(&project), "packages-api-docs");
+
+        let project = BuildProjectSettings { root: String::from("."), ..Default::default() };
+        assert_eq!(get_project_name(&project), "default");
+
+        let project = BuildProjectSettings {
+            name: Some(String::from("api")),
+            root: String::from("packages/api/docs"),
+            ..Default::default()
+        };
+        assert_eq!(get_project_name(&project), "api");
+    }
+
+    #[test]
+    fn should_reject_projects_with_the_same_name() {
+        let projects = vec![
+            BuildProjectSettings { root: String::from("api/docs"), ..Default::default() },
+            BuildProjectSettings { root: String::from("api-docs"), ..Default::default() },
+        ];
+        assert!(validate_project_names(&projects).is_err());
+
+        let projects = vec![
+            BuildProjectSettings { root: String::from("api/docs"), ..Default::default() },
+            BuildProjectSettings {
+                name: Some(String::from("api")),
+                root: String::from("api-docs"),
+                ..Default::default()
+            },
+        ];
+        assert!(validate_project_names(&projects).is_ok());
+    }
+
+    #[test]
+    fn context_command_should_override_project_command() {
+        let build_settings = BuildSettings {
+            command: String::from("hugo"),
+            ..Default::default()
+        };
+        let project = BuildProjectSettings {
+            root: String::from("docs"),
+            command: Some(String::from("hugo --minify")),
+            ..Default::default()
+        };
+        let context_settings = BuildContextSettings {
+            command: Some(String::from("hugo --buildDrafts")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_configured_command(&project, Some(&build_settings), Some(&context_settings)),
+            Some(String::from("hugo --buildDrafts"))
+        );
+        assert_eq!(
+            get_configured_command(&project, Some(&build_settings), None),
+            Some(String::from("hugo --minify"))
+        );
+        assert_eq!(
+            get_configured_command(&BuildProjectSettings::default(), Some(&build_settings), None),
+            Some(String::from("hugo"))
+        );
+        assert_eq!(get_configured_command(&BuildProjectSettings::default(), None, None), None);
+    }
+
+    #[test]
+    fn should_only_build_api_reference_when_configured() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"lib\"").unwrap();
+
+        let staging_dir = dir.path().join(".doctavious/output");
+        let options = BuildOptions {
+            staging_dir: &staging_dir,
+            context: BuildContext::Production,
+            dry: true,
+            skip_install: true,
+            cache: None,
+        };
+
+        let project = BuildProjectSettings {
+            root: dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let result = build(&project, None, &options);
+        assert!(!result.success);
+        assert_eq!(result.framework, None);
+
+        let project = BuildProjectSettings {
+            framework: Some(String::from("rustdoc")),
+            ..project
+        };
+        let result = build(&project, None, &options);
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.framework, Some(String::from("rustdoc")));
+    }
+
+    #[test]
+    fn should_detect_api_reference_alongside_site() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("book.toml"), "[book]\ntitle = \"docs\"").unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"docs\"").unwrap();
+
+        let project = BuildProjectSettings {
+            name: Some(String::from("docs")),
+            root: dir.path().to_string_lossy().to_string(),
+            api_reference: Some(BuildApiReferenceSettings::default()),
+            ..Default::default()
+        };
+
+        let staging_dir = dir.path().join(".doctavious/output");
+        let options = BuildOptions {
+            staging_dir: &staging_dir,
+            context: BuildContext::Production,
+            dry: true,
+            skip_install: true,
+            cache: None,
+        };
+        let result = build(&project, None, &options);
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.framework, Some(String::from("mdBook")));
+
+        let api_reference = result.api_reference.unwrap();
+        assert_eq!(api_reference.framework, "rustdoc");
+        assert_eq!(api_reference.command, "cargo doc --no-deps");
+        assert_eq!(api_reference.path, "api");
+    }
 }
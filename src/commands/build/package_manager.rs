@@ -10,7 +10,7 @@ use crate::commands::build::projects::csproj::CSProj;
 
 // TODO: could add PDM and Anaconda (Python)
 #[non_exhaustive]
-#[derive(Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub enum PackageManager {
     Cargo,
     Go,
@@ -26,6 +26,9 @@ pub enum PackageManager {
 #[derive(Serialize)]
 pub struct PackageManagerInfo {
     pub name: &'static str,
+
+    /// Command used to install dependencies prior to building.
+    /// Where possible this should be the command that respects the lock file
     pub install_command: &'static str,
 
     // TODO: do we want to change to known_project_files?
@@ -47,6 +50,7 @@ impl<'a> PackageManager {
         PackageManager::Cargo,
         PackageManager::Go,
         PackageManager::NPM,
+        PackageManager::Nuget,
         PackageManager::Poetry,
         PackageManager::PIP,
         PackageManager::PNPM,
@@ -59,7 +63,7 @@ impl<'a> PackageManager {
             PackageManager::Cargo => {
                 PackageManagerInfo {
                     name: "cargo",
-                    install_command: "cargo add",
+                    install_command: "cargo fetch",
                     lock_file: "Cargo.lock",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -73,7 +77,7 @@ impl<'a> PackageManager {
             PackageManager::Go => {
                 PackageManagerInfo {
                     name: "go",
-                    install_command: "go get",
+                    install_command: "go mod download",
                     // TODO: not sure this is appropriate for a lock file
                     lock_file: "go.sum",
                     detection: FrameworkDetector {
@@ -87,7 +91,7 @@ impl<'a> PackageManager {
             PackageManager::NPM => {
                 PackageManagerInfo {
                     name: "npm",
-                    install_command: "npm install",
+                    install_command: "npm ci",
                     lock_file: "package-lock.json",
                     // TODO: if package.json is found with no packageManager should we default
                     // to NPM? Which would mean we would be forced to make sure its at the end
@@ -98,7 +102,7 @@ impl<'a> PackageManager {
                             FrameworkDetectionItem::File { path: "package-lock.json", content: None },
                             FrameworkDetectionItem::File {
                                 path: "package.json",
-                                content: Some(r#""packageManager":\\s*"npm@.*"""#)
+                                content: Some(r#""packageManager":\s*"npm@.*""#)
                             }
                         ]
                     },
@@ -107,7 +111,7 @@ impl<'a> PackageManager {
             PackageManager::Nuget => {
                 PackageManagerInfo {
                     name: "nuget",
-                    install_command: "dotnet add",
+                    install_command: "dotnet restore",
                     lock_file: "packages.lock.json",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -130,7 +134,7 @@ impl<'a> PackageManager {
                             FrameworkDetectionItem::File { path: "poetry.lock", content: None },
                             FrameworkDetectionItem::File {
                                 path: "pyproject.toml",
-                                content: Some("[tool.poetry]")
+                                content: Some(r"\[tool\.poetry\]")
                             },
                         ]
                     },
@@ -139,7 +143,7 @@ impl<'a> PackageManager {
             PackageManager::PIP => {
                 PackageManagerInfo {
                     name: "pip",
-                    install_command: "pip install",
+                    install_command: "pip install -r requirements.txt",
                     lock_file: "pipfile.lock",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -155,7 +159,7 @@ impl<'a> PackageManager {
             PackageManager::PNPM => {
                 PackageManagerInfo {
                     name: "pnpm",
-                    install_command: "pnpm install",
+                    install_command: "pnpm install --frozen-lockfile",
                     lock_file: "pnpm-lock.yaml",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -164,7 +168,7 @@ impl<'a> PackageManager {
                             FrameworkDetectionItem::File { path: "pnpm-lock.yaml", content: None },
                             FrameworkDetectionItem::File {
                                 path: "package.json",
-                                content: Some(r#""packageManager":\\s*"pnpm@.*""#)
+                                content: Some(r#""packageManager":\s*"pnpm@.*""#)
                             },
                         ]
                     },
@@ -173,7 +177,7 @@ impl<'a> PackageManager {
             PackageManager::RubyGems => {
                 PackageManagerInfo {
                     name: "rubygems",
-                    install_command: "gem install",
+                    install_command: "bundle install",
                     lock_file: "Gemfile.lock",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -188,7 +192,7 @@ impl<'a> PackageManager {
             PackageManager::Yarn => {
                 PackageManagerInfo {
                     name: "yarn",
-                    install_command: "yarn install",
+                    install_command: "yarn install --immutable",
                     lock_file: "yarn.lock",
                     detection: FrameworkDetector {
                         matching_strategy: FrameworkMatchingStrategy::Any,
@@ -197,7 +201,7 @@ impl<'a> PackageManager {
                             FrameworkDetectionItem::File { path: "yarn.lock", content: None },
                             FrameworkDetectionItem::File {
                                 path: "package.json",
-                                content: Some(r#""packageManager":\\s*"yarn@.*""#)
+                                content: Some(r#""packageManager":\s*"yarn@.*""#)
                             },
                         ]
                     },
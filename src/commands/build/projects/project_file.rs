@@ -1,5 +1,5 @@
 use std::{env, fs};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use glob::glob;
 use serde_derive::{Serialize};
 use serde_json::Value;
@@ -132,10 +132,11 @@ impl ProjectFile {
     // }
 
 
-    pub fn get_project_paths(&self) -> Vec<PathBuf> {
+    /// Returns the paths, resolved against `cwd`, where the project file could be found
+    pub fn get_project_paths(&self, cwd: &Path) -> Vec<PathBuf> {
         match self {
             ProjectFile::CSProj => {
-                let glob_result = glob("**/*.csproj");
+                let glob_result = glob(&cwd.join("**/*.csproj").to_string_lossy());
                 match glob_result {
                     Ok(paths) => {
                         paths.into_iter().filter_map(|p| p.ok()).collect()
@@ -146,13 +147,13 @@ impl ProjectFile {
                     }
                 }
             },
-            ProjectFile::GoMod => vec![PathBuf::from("go.mod")],
-            ProjectFile::PackageJson => vec![PathBuf::from("package.json")],
-            ProjectFile::PipFile => vec![PathBuf::from("pipfile")],
-            ProjectFile::PyProject => vec![PathBuf::from("pyproject.toml")],
-            ProjectFile::RequirementsTxt => vec![PathBuf::from("requirements.txt")],
-            ProjectFile::GemFile => vec![PathBuf::from("Gemfile")],
-            ProjectFile::CargoToml => vec![PathBuf::from("cargo.toml")]
+            ProjectFile::GoMod => vec![cwd.join("go.mod")],
+            ProjectFile::PackageJson => vec![cwd.join("package.json")],
+            ProjectFile::PipFile => vec![cwd.join("pipfile")],
+            ProjectFile::PyProject => vec![cwd.join("pyproject.toml")],
+            ProjectFile::RequirementsTxt => vec![cwd.join("requirements.txt")],
+            ProjectFile::GemFile => vec![cwd.join("Gemfile")],
+            ProjectFile::CargoToml => vec![cwd.join("Cargo.toml")]
         }
     }
 
@@ -0,0 +1,252 @@
+// Staging of build output
+// Rather than having later steps, such as deploy, know where each framework writes its output we
+// copy the resolved output directory of each project into .doctavious/output/<project> and
+// record what happened in .doctavious/output/builds.json
+// similar to vercel's build output API - https://vercel.com/docs/build-output-api/v3
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::commands::build::context::BuildContext;
+use crate::constants::BUILDS_MANIFEST_NAME;
+use crate::doctavious_error::{DoctaviousError, Result as DoctaviousResult};
+
+/// Version of the builds.json schema. Bump when making breaking changes to the manifest
+pub(crate) const BUILDS_MANIFEST_VERSION: u8 = 1;
+
+/// Manifest, written to builds.json, describing the builds of a `doctavious build` run
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct BuildsManifest {
+    pub version: u8,
+    pub doctavious_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Build context the projects were built with
+    pub context: BuildContext,
+    pub builds: Vec<BuildResult>,
+}
+
+impl BuildsManifest {
+    pub fn new(context: BuildContext) -> Self {
+        Self {
+            version: BUILDS_MANIFEST_VERSION,
+            doctavious_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            context,
+            builds: vec![],
+        }
+    }
+}
+
+/// Result of building a single project. Failed builds are included along with their error
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct BuildResult {
+    /// Name of the project which is also the directory, within the staging directory, that the
+    /// output was copied to
+    pub project: String,
+    pub root: String,
+    pub framework: Option<String>,
+    /// Version of the framework resolved from lock files
+    pub framework_version: Option<String>,
+    pub command: Option<String>,
+    /// Output directory of the framework relative to the project root
+    pub output_directory: Option<String>,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    pub file_count: u64,
+    /// Total size of the output in bytes
+    pub total_size: u64,
+    pub api_reference: Option<ApiReferenceResult>,
+    /// Whether the output was restored from the build cache rather than built
+    pub cached: bool,
+    /// Hash of the build inputs used as the key of the build cache
+    pub cache_key: Option<String>,
+    pub error: Option<String>,
+}
+
+/// API reference built alongside a project
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct ApiReferenceResult {
+    pub framework: String,
+    pub command: String,
+    /// Output directory of the API reference generator relative to its root
+    pub output_directory: String,
+    /// Directory within the project output the API reference was copied to
+    pub path: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct StagedOutput {
+    pub file_count: u64,
+    pub total_size: u64,
+}
+
+/// Deletes output from potential previous builds and creates a fresh staging directory
+pub(crate) fn clean_staging_dir(staging_dir: &Path) -> DoctaviousResult<()> {
+    if staging_dir.exists() {
+        fs::remove_dir_all(staging_dir)?;
+    }
+    fs::create_dir_all(staging_dir)?;
+    Ok(())
+}
+
+/// Sibling of the staging directory that a rebuild is staged into so that the existing output can
+/// still be served until the rebuild succeeds
+pub(crate) fn next_staging_dir(staging_dir: &Path) -> PathBuf {
+    with_suffix(staging_dir, "next")
+}
+
+/// Replaces the staging directory with the output staged into `next`
+pub(crate) fn swap_staging_dir(next: &Path, staging_dir: &Path) -> DoctaviousResult<()> {
+    // renames are used so the staging directory is only briefly missing rather than emptied for
+    // the duration of a copy
+    let previous = with_suffix(staging_dir, "previous");
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    if staging_dir.exists() {
+        fs::rename(staging_dir, &previous)?;
+    }
+    fs::rename(next, staging_dir)?;
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Copies the output directory of a build into the staging directory
+pub(crate) fn stage_output(source: &Path, destination: &Path) -> DoctaviousResult<StagedOutput> {
+    if !source.is_dir() {
+        return Err(DoctaviousError::BuildError(format!(
+            "output directory {} does not exist", source.to_string_lossy()
+        )));
+    }
+
+    // frameworks without a build step can serve the project root so make sure we don't copy
+    // the staging directory into itself
+    let mut staged = StagedOutput::default();
+    let walker = WalkDir::new(source)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_excluded(e.file_name().to_str()));
+    for entry in walker {
+        let entry = entry?;
+        let relative_path = match entry.path().strip_prefix(source) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+
+        let target = destination.join(relative_path);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            staged.total_size += fs::copy(entry.path(), &target)?;
+            staged.file_count += 1;
+        }
+    }
+
+    Ok(staged)
+}
+
+fn is_excluded(file_name: Option<&str>) -> bool {
+    matches!(file_name, Some(".doctavious") | Some(".git"))
+}
+
+pub(crate) fn write_manifest(staging_dir: &Path, manifest: &BuildsManifest) -> DoctaviousResult<()> {
+    let content = serde_json::to_string_pretty(manifest)?;
+    fs::write(staging_dir.join(BUILDS_MANIFEST_NAME), content)?;
+    Ok(())
+}
+
+/// Reads builds.json written by a previous build
+pub(crate) fn read_manifest(staging_dir: &Path) -> DoctaviousResult<BuildsManifest> {
+    let content = fs::read_to_string(staging_dir.join(BUILDS_MANIFEST_NAME))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::commands::build::context::BuildContext;
+    use crate::commands::build::staging::{
+        BuildResult, BuildsManifest, clean_staging_dir, next_staging_dir, read_manifest,
+        stage_output, StagedOutput, swap_staging_dir, write_manifest
+    };
+
+    #[test]
+    fn should_stage_output() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("site");
+        fs::create_dir_all(source.join("assets")).unwrap();
+        fs::write(source.join("index.html"), "<html></html>").unwrap();
+        fs::write(source.join("assets/main.css"), "body {}").unwrap();
+
+        let destination = dir.path().join(".doctavious/output/docs");
+        let staged = stage_output(&source, &destination).unwrap();
+
+        assert_eq!(staged, StagedOutput { file_count: 2, total_size: 20 });
+        assert!(destination.join("index.html").exists());
+        assert!(destination.join("assets/main.css").exists());
+    }
+
+    #[test]
+    fn should_fail_when_output_directory_is_missing() {
+        let dir = TempDir::new().unwrap();
+        let result = stage_output(&dir.path().join("site"), &dir.path().join("output"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_clean_previous_output_and_write_manifest() {
+        let dir = TempDir::new().unwrap();
+        let staging_dir = dir.path().join(".doctavious/output");
+        fs::create_dir_all(staging_dir.join("old")).unwrap();
+
+        clean_staging_dir(&staging_dir).unwrap();
+        assert!(!staging_dir.join("old").exists());
+
+        let mut manifest = BuildsManifest::new(BuildContext::Preview);
+        manifest.builds.push(BuildResult {
+            project: String::from("docs"),
+            root: String::from("docs"),
+            error: Some(String::from("boom")),
+            ..Default::default()
+        });
+        write_manifest(&staging_dir, &manifest).unwrap();
+
+        let written = read_manifest(&staging_dir).unwrap();
+        assert_eq!(written.context, BuildContext::Preview);
+        assert_eq!(written.builds.len(), 1);
+        assert_eq!(written.builds[0].error, Some(String::from("boom")));
+    }
+
+    #[test]
+    fn should_swap_in_next_staging_dir() {
+        let dir = TempDir::new().unwrap();
+        let staging_dir = dir.path().join(".doctavious/output");
+        fs::create_dir_all(staging_dir.join("docs")).unwrap();
+        fs::write(staging_dir.join("docs/index.html"), "old").unwrap();
+
+        let next = next_staging_dir(&staging_dir);
+        assert_eq!(next, dir.path().join(".doctavious/output.next"));
+        fs::create_dir_all(next.join("docs")).unwrap();
+        fs::write(next.join("docs/index.html"), "new").unwrap();
+
+        swap_staging_dir(&next, &staging_dir).unwrap();
+        assert_eq!(fs::read_to_string(staging_dir.join("docs/index.html")).unwrap(), "new");
+        assert!(!next.exists());
+        assert!(!dir.path().join(".doctavious/output.previous").exists());
+    }
+}
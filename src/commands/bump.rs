@@ -1 +1,587 @@
+// Determines the next semantic version from the conventional commits made since the last release.
+// https://semver.org/
+// https://www.conventionalcommits.org/en/v1.0.0/#how-does-this-relate-to-semver
+//
+// Pre-release versions follow the maven like flow described in release.rs. Releasing a pre-release
+// drops the pre-release identifier while bumping with a pre-release identifier either increments
+// the pre-release number, ex: 1.2.0-rc.1 -> 1.2.0-rc.2, or starts a new pre-release for the next
+// version, ex: 1.1.0 -> 1.2.0-rc.1
 
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use git2::Repository;
+use lazy_static::lazy_static;
+use regex::Regex;
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::Serialize;
+
+use crate::commands::changelog::changelog::default_changelog_settings;
+use crate::commands::changelog::commit::Commit;
+use crate::constants::DEFAULT_CONFIG_NAME;
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::git;
+use crate::output::{get_output, print_output, Output};
+use crate::settings::{load_settings_from, ChangelogSettings, Settings};
+
+/// Version used when there are no previous releases
+const INITIAL_VERSION: Version = Version::new(0, 1, 0);
+
+/// Files, relative to the root, the version is written to when present and no
+/// version files are configured
+const DEFAULT_VERSION_FILES: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+lazy_static! {
+    static ref TAG_VERSION_RE: Regex = Regex::new(r"\d+\.\d+\.\d+\S*$").unwrap();
+    static ref TOML_TABLE_RE: Regex = Regex::new(r"^\s*\[([^\[\]]+)\]\s*(#.*)?$").unwrap();
+    static ref TOML_VERSION_RE: Regex = Regex::new(r#"^(\s*version\s*=\s*)(["'])[^"']*(["'])"#).unwrap();
+    // value following a "version" key, ex: : "1.2.3"
+    static ref JSON_VERSION_VALUE_RE: Regex = Regex::new(r#"^\s*:\s*"(?P<version>[^"\\]*)""#).unwrap();
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Determine the next version from conventional commits since the last tag")]
+pub(crate) struct BumpCommand {
+    #[arg(long, short, value_name = "PATH", help = "Repository to read commits and tags from")]
+    pub repository: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "IDENTIFIER",
+        help = "Pre-release identifier, ex: alpha or rc. Increments the pre-release number when \
+                the last version is a pre-release with the same identifier"
+    )]
+    pub pre_release: Option<String>,
+
+    #[arg(long, value_name = "METADATA", help = "Build metadata, ex: a build number or commit SHA")]
+    pub build: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Increment to apply instead of the one determined from commits"
+    )]
+    pub increment: Option<Increment>,
+
+    #[arg(
+        long,
+        short,
+        help = "Writes the version to Cargo.toml, package.json and pyproject.toml, when present, \
+                or the configured version files"
+    )]
+    pub write: bool,
+
+    #[arg(
+        long = "file",
+        value_name = "PATH",
+        help = "Version file to write to. Overrides configured version files. Can be provided \
+                multiple times"
+    )]
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Increment {
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BumpResult {
+    pub previous_tag: Option<String>,
+    pub previous_version: Option<String>,
+    pub version: String,
+    pub increment: Option<Increment>,
+    pub commits: usize,
+    pub files: Vec<String>,
+}
+
+impl Display for BumpResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+pub(crate) fn handle_bump_command(command: BumpCommand, output: Option<Output>) -> Result<()> {
+    let repository = match &command.repository {
+        Some(path) => Repository::open(path)?,
+        None => Repository::open(env::current_dir()?)?,
+    };
+
+    let settings = load_repository_settings(&repository, Path::new(DEFAULT_CONFIG_NAME));
+    let config = get_changelog_settings(settings.as_ref())?;
+    let mut result = bump(
+        &repository,
+        &config.git.tag_pattern,
+        command.increment,
+        command.pre_release.as_deref(),
+        command.build.as_deref(),
+    )?;
+
+    if command.write {
+        // configured and default version files belong to the repository being bumped
+        let root = match (&command.repository, repository.workdir()) {
+            (Some(_), Some(workdir)) => workdir.to_path_buf(),
+            _ => PathBuf::new(),
+        };
+        let version_files = settings.as_ref()
+            .and_then(|s| s.bump_settings.as_ref())
+            .and_then(|s| s.version_files.as_ref());
+        for file in get_version_files(&command.files, version_files, &root) {
+            if write_version(&file, &result.version)? {
+                result.files.push(file.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    print_output(get_output(output), result)
+}
+
+/// Settings from the config, ex: doctavious.toml, relative to the workdir of the repository
+pub(crate) fn load_repository_settings(repository: &Repository, config: &Path) -> Option<Settings> {
+    let workdir = repository.workdir()?;
+    load_settings_from(&workdir.join(config)).ok()
+}
+
+/// Changelog settings of the repository's config falling back to the default changelog settings.
+/// Both bump and release resolve the tag pattern from these so they agree on the next version
+pub(crate) fn get_changelog_settings(settings: Option<&Settings>) -> Result<ChangelogSettings> {
+    match settings.and_then(|s| s.changelog_settings.clone()) {
+        Some(config) => Ok(config),
+        None => default_changelog_settings(),
+    }
+}
+
+/// Computes the next version from the commits since the last tag matching the tag pattern
+pub(crate) fn bump(
+    repository: &Repository,
+    tag_pattern: &Option<String>,
+    increment: Option<Increment>,
+    pre_release: Option<&str>,
+    build: Option<&str>,
+) -> Result<BumpResult> {
+    let previous = get_last_version(repository, tag_pattern)?;
+
+    let range = previous.as_ref().map(|(tag, _)| format!("{}..HEAD", tag));
+    let messages: Vec<String> = git::commits(repository, range)?
+        .iter()
+        .map(|c| c.message().unwrap_or_default().to_string())
+        .collect();
+
+    let increment = increment.or_else(|| get_increment(&messages));
+    let version = match &previous {
+        Some((tag, version)) => {
+            let increment = increment.ok_or_else(|| DoctaviousError::Msg(
+                format!("no commits since {}", tag)
+            ))?;
+            next_version(version, increment, pre_release, build)?
+        }
+        None => initial_version(pre_release, build)?,
+    };
+
+    Ok(BumpResult {
+        previous_tag: previous.as_ref().map(|(tag, _)| tag.to_string()),
+        previous_version: previous.as_ref().map(|(_, version)| version.to_string()),
+        version: version.to_string(),
+        increment,
+        commits: messages.len(),
+        files: vec![],
+    })
+}
+
+/// Latest tag, by commit time, along with its version skipping tags that aren't semantic versions
+fn get_last_version(
+    repository: &Repository,
+    tag_pattern: &Option<String>,
+) -> Result<Option<(String, Version)>> {
+    let tags = git::tags(repository, tag_pattern)?;
+    Ok(tags
+        .values()
+        .rev()
+        .find_map(|tag| parse_tag_version(tag).map(|version| (tag.to_string(), version))))
+}
+
+/// Parses the version from a tag ignoring any prefix, ex: v1.2.3 or cli-v1.2.3
+pub(crate) fn parse_tag_version(tag: &str) -> Option<Version> {
+    TAG_VERSION_RE
+        .find(tag)
+        .and_then(|m| Version::parse(m.as_str()).ok())
+}
+
+/// Major for breaking changes, via `!` or a BREAKING CHANGE footer, minor for features and patch
+/// for everything else. None when there are no commits
+pub(crate) fn get_increment(messages: &[String]) -> Option<Increment> {
+    messages
+        .iter()
+        .map(|message| {
+            match Commit::new(String::new(), message.to_string()).into_conventional() {
+                Ok(commit) => match commit.conventional {
+                    Some(conventional) if conventional.breaking() => Increment::Major,
+                    Some(conventional) if conventional.type_() == git_conventional::Type::FEAT => {
+                        Increment::Minor
+                    }
+                    _ => Increment::Patch,
+                },
+                Err(_) => Increment::Patch,
+            }
+        })
+        .max()
+}
+
+pub(crate) fn next_version(
+    current: &Version,
+    increment: Increment,
+    pre_release: Option<&str>,
+    build: Option<&str>,
+) -> Result<Version> {
+    let mut next = Version::new(current.major, current.minor, current.patch);
+
+    // a pre-release precedes its version so that version already includes the increment when,
+    // for example, a feature is added to 1.2.0-rc.1
+    let satisfied = !current.pre.is_empty() && match increment {
+        Increment::Major => current.minor == 0 && current.patch == 0,
+        Increment::Minor => current.patch == 0,
+        Increment::Patch => true,
+    };
+
+    if !satisfied {
+        match increment {
+            Increment::Major => next = Version::new(current.major + 1, 0, 0),
+            Increment::Minor => next = Version::new(current.major, current.minor + 1, 0),
+            Increment::Patch => next = Version::new(current.major, current.minor, current.patch + 1),
+        }
+    }
+
+    if let Some(identifier) = pre_release {
+        let number = if satisfied {
+            get_pre_release_number(&current.pre, identifier).map(|n| n + 1).unwrap_or(1)
+        } else {
+            1
+        };
+        next.pre = parse_pre_release(&format!("{}.{}", identifier, number))?;
+    }
+
+    if let Some(build) = build {
+        next.build = parse_build(build)?;
+    }
+
+    Ok(next)
+}
+
+fn initial_version(pre_release: Option<&str>, build: Option<&str>) -> Result<Version> {
+    let mut version = INITIAL_VERSION;
+    if let Some(identifier) = pre_release {
+        version.pre = parse_pre_release(&format!("{}.1", identifier))?;
+    }
+    if let Some(build) = build {
+        version.build = parse_build(build)?;
+    }
+    Ok(version)
+}
+
+/// Number of the pre-release when it has the identifier, ex: 1 for rc.1 and the identifier rc
+fn get_pre_release_number(pre: &Prerelease, identifier: &str) -> Option<u64> {
+    let (name, number) = pre.as_str().rsplit_once('.')?;
+    if name == identifier {
+        number.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_pre_release(pre_release: &str) -> Result<Prerelease> {
+    Prerelease::new(pre_release).map_err(|e| {
+        DoctaviousError::Msg(format!("invalid pre-release {}: {}", pre_release, e))
+    })
+}
+
+fn parse_build(build: &str) -> Result<BuildMetadata> {
+    BuildMetadata::new(build).map_err(|e| {
+        DoctaviousError::Msg(format!("invalid build metadata {}: {}", build, e))
+    })
+}
+
+/// Files provided as arguments, otherwise the configured version files, otherwise the default
+/// version files that exist. Configured and default version files are relative to the root
+pub(crate) fn get_version_files(
+    files: &[PathBuf],
+    version_files: Option<&Vec<String>>,
+    root: &Path,
+) -> Vec<PathBuf> {
+    if !files.is_empty() {
+        return files.to_vec();
+    }
+
+    if let Some(files) = version_files {
+        return files.iter().map(|f| root.join(f)).collect();
+    }
+
+    DEFAULT_VERSION_FILES
+        .iter()
+        .map(|f| root.join(f))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+/// Prefix of the tag before its version, ex: v for v1.2.3 or cli-v for cli-v1.2.3
+pub(crate) fn get_tag_prefix(tag: &str) -> &str {
+    TAG_VERSION_RE.find(tag).map(|m| &tag[..m.start()]).unwrap_or(tag)
+}
+
+/// Writes the version to the file returning false when the file doesn't declare a version.
+pub(crate) fn write_version(path: &Path, version: &str) -> Result<bool> {
+    match replace_version(path, version)? {
+        Some(content) => {
+            fs::write(path, content)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Whether writing the version would change the file, ex: for a dry run
+pub(crate) fn version_changes(path: &Path, version: &str) -> Result<bool> {
+    Ok(match replace_version(path, version)? {
+        Some(content) => fs::read_to_string(path).map(|existing| existing != content).unwrap_or(true),
+        None => false,
+    })
+}
+
+/// Content of the file with the version replaced or None when the file doesn't declare a version.
+///
+/// Cargo.toml, package.json and pyproject.toml are updated in place preserving formatting.
+/// Any other file is treated as a plain text version file, ex: VERSION, and replaced.
+fn replace_version(path: &Path, version: &str) -> Result<Option<String>> {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    Ok(match file_name {
+        "Cargo.toml" => {
+            let content = fs::read_to_string(path)?;
+            replace_toml_version(&content, &["package", "workspace.package"], version)
+        }
+        "pyproject.toml" => {
+            let content = fs::read_to_string(path)?;
+            replace_toml_version(&content, &["project", "tool.poetry"], version)
+        }
+        "package.json" => {
+            let content = fs::read_to_string(path)?;
+            // parsed to report invalid JSON rather than silently not finding a version
+            serde_json::from_str::<serde_json::Value>(&content)?;
+            replace_json_version(&content, version)
+        }
+        _ => Some(format!("{}\n", version)),
+    })
+}
+
+/// Replaces the value of the top-level version key. Version keys of nested objects, ex: within
+/// engines or volta, are left as is
+fn replace_json_version(content: &str, version: &str) -> Option<String> {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b'"' => {
+                let end = json_string_end(bytes, i)?;
+                if depth == 1 && &content[i + 1..end] == "version" {
+                    if let Some(value) = JSON_VERSION_VALUE_RE
+                        .captures(&content[end + 1..])
+                        .and_then(|c| c.name("version"))
+                    {
+                        let start = end + 1 + value.start();
+                        let stop = end + 1 + value.end();
+                        return Some(format!("{}{}{}", &content[..start], version, &content[stop..]));
+                    }
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the quote closing the string which starts at `start`
+fn json_string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Replaces the version key within the first of the tables that declares one
+fn replace_toml_version(content: &str, tables: &[&str], version: &str) -> Option<String> {
+    let mut table = String::new();
+    let mut replaced = false;
+    let mut lines = vec![];
+    for line in content.split_inclusive('\n') {
+        if let Some(captures) = TOML_TABLE_RE.captures(line) {
+            table = captures[1].trim().to_string();
+        }
+
+        if !replaced && tables.contains(&table.as_str()) && TOML_VERSION_RE.is_match(line) {
+            let replacement = format!("${{1}}${{2}}{}${{3}}", version);
+            lines.push(TOML_VERSION_RE.replace(line, replacement.as_str()).to_string());
+            replaced = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+
+    if replaced {
+        Some(lines.concat())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use git2::{Repository, Signature, Time};
+    use semver::Version;
+    use tempfile::TempDir;
+
+    use crate::commands::bump::{
+        bump, get_changelog_settings, get_increment, load_repository_settings, next_version,
+        parse_tag_version, version_changes, write_version, Increment
+    };
+
+    #[test]
+    fn should_determine_increment_from_commits() {
+        let increment = |messages: &[&str]| {
+            get_increment(&messages.iter().map(|m| m.to_string()).collect::<Vec<String>>())
+        };
+
+        assert_eq!(increment(&[]), None);
+        assert_eq!(increment(&["fix: typo", "update readme"]), Some(Increment::Patch));
+        assert_eq!(increment(&["fix: typo", "feat(adr): add adr command"]), Some(Increment::Minor));
+        assert_eq!(increment(&["feat!: drop support for rfc"]), Some(Increment::Major));
+        assert_eq!(
+            increment(&["feat: new settings\n\nBREAKING CHANGE: settings have moved"]),
+            Some(Increment::Major)
+        );
+    }
+
+    #[test]
+    fn should_compute_next_version() {
+        let next = |current: &str, increment, pre: Option<&str>, build: Option<&str>| {
+            next_version(&Version::parse(current).unwrap(), increment, pre, build)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(next("1.2.3", Increment::Patch, None, None), "1.2.4");
+        assert_eq!(next("1.2.3", Increment::Minor, None, None), "1.3.0");
+        assert_eq!(next("1.2.3", Increment::Major, None, None), "2.0.0");
+        assert_eq!(next("1.2.3", Increment::Minor, Some("rc"), None), "1.3.0-rc.1");
+        assert_eq!(next("1.3.0-rc.1", Increment::Patch, Some("rc"), None), "1.3.0-rc.2");
+        assert_eq!(next("1.3.0-rc.1", Increment::Minor, Some("rc"), None), "1.3.0-rc.2");
+        assert_eq!(next("1.3.0-alpha.3", Increment::Patch, Some("beta"), None), "1.3.0-beta.1");
+        assert_eq!(next("1.3.0-rc.1", Increment::Major, Some("rc"), None), "2.0.0-rc.1");
+        assert_eq!(next("1.3.0-rc.2", Increment::Minor, None, None), "1.3.0");
+        assert_eq!(next("1.2.3", Increment::Patch, None, Some("build.42")), "1.2.4+build.42");
+
+        assert_eq!(parse_tag_version("v1.2.3-rc.1"), Version::parse("1.2.3-rc.1").ok());
+        assert_eq!(parse_tag_version("cli-v0.4.0"), Version::parse("0.4.0").ok());
+        assert_eq!(parse_tag_version("latest"), None);
+    }
+
+    #[test]
+    fn should_only_bump_from_tags_matching_default_pattern() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let commit = |path: &str, message: &str, time: i64, tag: Option<&str>| {
+            fs::write(dir.path().join(path), message).unwrap();
+            let mut index = repository.index().unwrap();
+            index.add_path(Path::new(path)).unwrap();
+            index.write().unwrap();
+            let signature = Signature::new("Doctavious", "doctavious@example.com", &Time::new(time, 0)).unwrap();
+            let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repository.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            let oid = repository.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+            if let Some(tag) = tag {
+                let object = repository.find_object(oid, None).unwrap();
+                repository.tag_lightweight(tag, &object, false).unwrap();
+            }
+        };
+
+        commit("lib.rs", "feat: add bump", 1_600_000_000, Some("v0.1.0"));
+        commit("docs.md", "docs: add guide", 1_600_000_050, Some("docs-2.0.0"));
+        commit("lib.rs", "feat: add release", 1_600_000_100, None);
+
+        // without a config the default changelog settings only match v tags
+        let settings = load_repository_settings(&repository, Path::new("doctavious.toml"));
+        assert!(settings.is_none());
+        let config = get_changelog_settings(settings.as_ref()).unwrap();
+        let result = bump(&repository, &config.git.tag_pattern, None, None, None).unwrap();
+        assert_eq!(result.previous_tag, Some(String::from("v0.1.0")));
+        assert_eq!(result.version, "0.2.0");
+    }
+
+    #[test]
+    fn should_write_version_files() {
+        let dir = TempDir::new().unwrap();
+
+        let cargo = dir.path().join("Cargo.toml");
+        fs::write(&cargo, "[package]\nname = \"doctavious\"\nversion = \"0.1.0\" # bumped\n\n[dependencies]\nserde = { version = \"1.0\" }\n").unwrap();
+        assert!(write_version(&cargo, "0.2.0").unwrap());
+        assert_eq!(
+            fs::read_to_string(&cargo).unwrap(),
+            "[package]\nname = \"doctavious\"\nversion = \"0.2.0\" # bumped\n\n[dependencies]\nserde = { version = \"1.0\" }\n"
+        );
+
+        let workspace = dir.path().join("workspace").join("Cargo.toml");
+        fs::create_dir_all(workspace.parent().unwrap()).unwrap();
+        fs::write(&workspace, "[workspace]\nmembers = [\"cli\"]\n").unwrap();
+        assert!(!write_version(&workspace, "0.2.0").unwrap());
+
+        let package = dir.path().join("package.json");
+        fs::write(&package, "{\n  \"name\": \"doctavious\",\n  \"version\": \"0.1.0\"\n}\n").unwrap();
+        assert!(write_version(&package, "0.2.0").unwrap());
+        assert_eq!(
+            fs::read_to_string(&package).unwrap(),
+            "{\n  \"name\": \"doctavious\",\n  \"version\": \"0.2.0\"\n}\n"
+        );
+
+        let nested = dir.path().join("nested").join("package.json");
+        fs::create_dir_all(nested.parent().unwrap()).unwrap();
+        fs::write(
+            &nested,
+            "{\n  \"engines\": { \"version\": \">=18\" },\n  \"description\": \"\\\"version\\\": \\\"x\\\"\",\n  \"version\": \"0.1.0\"\n}\n",
+        )
+        .unwrap();
+        assert!(write_version(&nested, "0.2.0").unwrap());
+        assert_eq!(
+            fs::read_to_string(&nested).unwrap(),
+            "{\n  \"engines\": { \"version\": \">=18\" },\n  \"description\": \"\\\"version\\\": \\\"x\\\"\",\n  \"version\": \"0.2.0\"\n}\n"
+        );
+
+        let pyproject = dir.path().join("pyproject.toml");
+        fs::write(&pyproject, "[build-system]\nrequires = [\"poetry\"]\n\n[tool.poetry]\nname = \"doctavious\"\nversion = '0.1.0'\n").unwrap();
+        assert!(write_version(&pyproject, "0.2.0").unwrap());
+        assert!(fs::read_to_string(&pyproject).unwrap().contains("version = '0.2.0'"));
+
+        let version = dir.path().join("VERSION");
+        assert!(version_changes(&version, "0.2.0").unwrap());
+        assert!(write_version(&version, "0.2.0").unwrap());
+        assert!(!version_changes(&version, "0.2.0").unwrap());
+        assert!(!version_changes(&workspace, "0.3.0").unwrap());
+        assert_eq!(fs::read_to_string(&version).unwrap(), "0.2.0\n");
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::Duration;
+use clap::Parser;
+
+use crate::commands::build::cache::BuildCache;
+use crate::doctavious_error::Result as DoctaviousResult;
+use crate::output::Output;
+
+#[derive(Parser, Debug)]
+#[command(about = "Build cache management commands")]
+pub(crate) struct CacheCommand {
+    #[command(subcommand)]
+    pub cache_command: CacheSubCommand,
+}
+
+#[derive(Parser, Debug)]
+pub(crate) enum CacheSubCommand {
+    Prune(PruneCache),
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Remove entries from the build cache")]
+pub(crate) struct PruneCache {
+    #[arg(
+        long,
+        value_name = "DAYS",
+        default_value_t = 7,
+        help = "Remove entries which haven't been used within the number of days"
+    )]
+    pub max_age: i64,
+
+    #[arg(long, help = "Remove all entries", conflicts_with = "max_age")]
+    pub all: bool,
+}
+
+pub(crate) fn handle_cache_command(
+    command: CacheCommand,
+    _output: Option<Output>,
+) -> DoctaviousResult<()> {
+    match command.cache_command {
+        CacheSubCommand::Prune(params) => {
+            let max_age = if params.all {
+                None
+            } else {
+                Some(Duration::days(params.max_age))
+            };
+
+            let pruned = BuildCache::user().prune(max_age)?;
+            println!(
+                "removed {} cache entries ({} bytes)",
+                pruned.entries, pruned.total_size
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -8,25 +8,33 @@ use std::{env, io};
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use chrono::Utc;
 use clap::Parser;
-use git2::Repository;
+use git2::{Commit as GitCommit, Repository};
+use glob::Pattern;
+use indexmap::IndexMap;
 use log::warn;
 
-use crate::commands::changelog::{ChangelogConfig, StripParts};
+use crate::commands::changelog::StripParts;
 use crate::commands::changelog::commit::Commit;
-use crate::commands::changelog::parse_strip_parts;
+use crate::commands::changelog::context::{ChangelogContext, SCHEMA_VERSION};
+use crate::commands::changelog::keep_a_changelog::KeepAChangelog;
 use crate::commands::changelog::release::Release;
 use crate::constants::DEFAULT_CONFIG_NAME;
 use crate::doctavious_error::{DoctaviousError, Result};
 use crate::git;
+use crate::output::Output;
 use crate::settings::{
-    AdrSettings, ChangelogSettings, load_settings, persist_settings,
-    RFDSettings, SETTINGS, TilSettings,
+    ChangelogSettings, load_settings, load_settings_from, persist_settings,
 };
 use crate::templates::{TemplateContext, Templates};
 
+pub(crate) const DEFAULT_CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+const DEFAULT_CHANGELOG_CONFIG: &str = include_str!("../../../templates/changelog/config.toml");
+
 #[derive(Parser, Debug)]
 #[command(about = "Gathers Changelog management commands")]
 pub(crate) struct ChangelogOpt {
@@ -38,6 +46,7 @@ pub(crate) struct ChangelogOpt {
 pub(crate) enum ChangelogCommand {
     Init(InitChangelog),
     Generate(GenerateChangeLog),
+    Release(ReleaseChangelog),
 }
 
 #[derive(Parser, Debug)]
@@ -45,7 +54,6 @@ pub(crate) enum ChangelogCommand {
 pub(crate) struct InitChangelog {
     #[arg(
         long,
-        short,
         help = "Header text that will be added to the beginning of the changelog."
     )]
     pub header: Option<String>,
@@ -72,6 +80,25 @@ pub(crate) struct InitChangelog {
     pub trim: bool,
 }
 
+#[derive(Parser, Debug)]
+#[command(about = "Move the unreleased changes under a new version")]
+pub(crate) struct ReleaseChangelog {
+    #[arg(help = "Version to release, ex: 1.2.0")]
+    pub version: String,
+
+    #[arg(
+        long,
+        short,
+        value_name = "PATH",
+        default_value = DEFAULT_CHANGELOG_FILE,
+        help = "The changelog file to update"
+    )]
+    pub file: PathBuf,
+
+    #[arg(long, short, value_name = "YYYY-MM-DD", help = "Release date. Defaults to today")]
+    pub date: Option<String>,
+}
+
 // rename_all_env = "screaming-snake"
 #[derive(Parser, Debug)]
 #[command(about = "Generate Changelog")]
@@ -89,10 +116,10 @@ pub(crate) struct GenerateChangeLog {
     #[arg(short, long, value_name = "PATH")]
     pub workdir: Option<PathBuf>,
 
-    // TODO: support multiple
-    /// Sets the repository to parse commits from.
+    /// Sets the repositories to parse commits from. Can be provided multiple times in which case
+    /// releases with the same version across repositories are merged.
     #[arg(short, long, value_name = "PATH")]
-    pub repository: Option<PathBuf>,
+    pub repository: Vec<PathBuf>,
     // defaults to current directory. env::current_dir()
 
     // TODO: this can just be a boolean
@@ -132,7 +159,7 @@ pub(crate) struct GenerateChangeLog {
         // possible_values = StripParts::possible_values(),
         value_parser,
         // value_parser = parse_strip_parts,
-        help = "The configuration file to use."
+        help = "Strips the given parts from the changelog."
     )]
     pub strip: Option<StripParts>,
 
@@ -147,12 +174,45 @@ pub(crate) struct GenerateChangeLog {
     /// Sets the commit range to process.
     #[arg(value_name = "RANGE")]
     pub range: Option<String>,
+
+    #[arg(
+        long = "include-path",
+        value_name = "GLOB",
+        help = "Only include commits which change paths matching the glob, ex: packages/api/**. \
+                Overrides include_paths from config. Can be provided multiple times"
+    )]
+    pub include_paths: Vec<String>,
+
+    #[arg(
+        long = "exclude-path",
+        value_name = "GLOB",
+        help = "Ignore changes to paths matching the glob when including commits. Overrides \
+                exclude_paths from config. Can be provided multiple times"
+    )]
+    pub exclude_paths: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Glob pattern for matching git tags, ex: api-v*. Overrides tag_pattern from config"
+    )]
+    pub tag_pattern: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["repository", "range", "latest", "unreleased", "tag"],
+        help = "Renders the changelog from a JSON or YAML context, as written by --output json or \
+                yaml, instead of reading commits from git. Use - to read from stdin"
+    )]
+    pub from_context: Option<PathBuf>,
 }
 
 /// Changelog generator.
 #[derive(Debug)]
 pub struct Changelog<'a> {
     releases: Vec<Release<'a>>,
+    context: ChangelogContext,
     template: Templates,
     // config:   &'a Config,
     // config: ChangelogConfig
@@ -165,8 +225,29 @@ impl<'a> Changelog<'a> {
         releases: Vec<Release<'a>>,
         config: &'a ChangelogSettings,
     ) -> Result<Self> {
-        let mut changelog = Self {
-            releases,
+        let mut changelog = Self::from_context(ChangelogContext::new(&[]), config)?;
+        changelog.releases = releases;
+        changelog.process_commits();
+        changelog.process_releases();
+        changelog.context = ChangelogContext::new(&changelog.releases);
+        Ok(changelog)
+    }
+
+    /// Constructs an instance from releases which have already been processed.
+    pub fn from_context(
+        context: ChangelogContext,
+        config: &'a ChangelogSettings,
+    ) -> Result<Self> {
+        if context.schema_version > SCHEMA_VERSION {
+            return Err(DoctaviousError::ChangelogError(format!(
+                "unsupported changelog context schema version {}. Expected {} or earlier",
+                context.schema_version, SCHEMA_VERSION
+            )));
+        }
+
+        Ok(Self {
+            releases: vec![],
+            context,
             template: Templates::new_with_templates({
                 let mut template = config.body.to_string();
                 if config.trim {
@@ -179,10 +260,12 @@ impl<'a> Changelog<'a> {
                 HashMap::from([("release", template)])
             })?,
             config,
-        };
-        changelog.process_commits();
-        changelog.process_releases();
-        Ok(changelog)
+        })
+    }
+
+    /// Processed releases, most recent first.
+    pub fn context(&self) -> &ChangelogContext {
+        &self.context
     }
 
     /// Processes the commits and omits the ones that doesn't match the
@@ -218,6 +301,7 @@ impl<'a> Changelog<'a> {
                     }
                 })
                 .collect::<Vec<Commit>>();
+            release.contributors = release.get_contributors();
         });
     }
 
@@ -259,84 +343,279 @@ impl<'a> Changelog<'a> {
     /// Generates the changelog and writes it to the given output.
     pub fn generate<W: Write>(&self, out: &mut W) -> Result<()> {
         log::debug!("Generating changelog...");
+        self.generate_releases(out)?;
+        if let Some(footer) = &self.config.footer {
+            write!(out, "{}", footer)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the header and releases without the footer.
+    fn generate_releases<W: Write>(&self, out: &mut W) -> Result<()> {
         if let Some(header) = &self.config.header {
             write!(out, "{}", header)?;
         }
-        for release in &self.releases {
+        for release in &self.context.releases {
             let s = self.template.render("release", &TemplateContext::from_serialize(release)?)?;
             write!(out, "{}", s)?;
         }
-        if let Some(footer) = &self.config.footer {
-            write!(out, "{}", footer)?;
-        }
         Ok(())
     }
 
-    /// Generates a changelog and prepends it to the given changelog.
+    /// Renders the releases without the header and footer, ex: for release notes.
+    pub fn render_releases(&self) -> Result<String> {
+        let mut out = Vec::new();
+        for release in &self.context.releases {
+            let s = self.template.render("release", &TemplateContext::from_serialize(release)?)?;
+            write!(out, "{}", s)?;
+        }
+        Ok(String::from_utf8_lossy(&out).to_string())
+    }
+
+    /// Generates a changelog and merges it into the given changelog.
+    ///
+    /// Only releases which changed are replaced, and new releases added, so prepending the same
+    /// releases multiple times doesn't duplicate them. Changelogs generated from templates which
+    /// don't render `## ` release headings are prepended without merging.
     pub fn prepend<W: Write>(
         &self,
         mut changelog: String,
         out: &mut W,
     ) -> Result<()> {
         log::debug!("Generating changelog and prepending...");
-        if let Some(header) = &self.config.header {
-            changelog = changelog.replacen(header, "", 1);
+        let mut generated = Vec::new();
+        self.generate_releases(&mut generated)?;
+        let generated = KeepAChangelog::parse(&String::from_utf8_lossy(&generated));
+
+        // output of templates without Keep a Changelog release headings can't be merged so is
+        // prepended as is
+        if generated.releases.is_empty() {
+            if let Some(header) = &self.config.header {
+                changelog = changelog.replacen(header, "", 1);
+            }
+            self.generate(out)?;
+            write!(out, "{}", changelog)?;
+            return Ok(());
+        }
+
+        if let Some(footer) = &self.config.footer {
+            changelog = changelog.replacen(footer, "", 1);
+        }
+        write!(out, "{}", KeepAChangelog::parse(&changelog).merge(generated))?;
+        if let Some(footer) = &self.config.footer {
+            write!(out, "{}", footer)?;
         }
-        self.generate(out)?;
-        write!(out, "{}", changelog)?;
         Ok(())
     }
 }
 
-pub(crate) fn init_changelog() {}
+pub(crate) fn handle_changelog_command(
+    command: ChangelogOpt,
+    output: Option<Output>,
+) -> Result<()> {
+    match command.changelog_command {
+        ChangelogCommand::Init(params) => init_changelog(params),
+        ChangelogCommand::Generate(params) => generate_changelog(params, output),
+        ChangelogCommand::Release(params) => release_changelog(params),
+    }
+}
 
-pub(crate) fn generate_changelog(args: GenerateChangeLog) -> Result<()> {
-    // TODO: get config/settings default if not present
+/// Moves the unreleased section of the changelog under a heading for the version
+pub(crate) fn release_changelog(params: ReleaseChangelog) -> Result<()> {
+    let mut changelog = KeepAChangelog::parse(&fs::read_to_string(&params.file)?);
+    let date = params.date.unwrap_or_else(|| Utc::now().format("%Y-%m-%d").to_string());
+    changelog.release(&params.version, &date)?;
+    fs::write(&params.file, changelog.to_string())?;
+    Ok(())
+}
 
-    // TODO: get repository from arg/env
-    // Initialize the git repository.
-    let repository =
-        Repository::init(args.repository.unwrap_or(env::current_dir()?))?;
+/// Writes the default changelog settings, along with any overrides, to the settings file
+pub(crate) fn init_changelog(params: InitChangelog) -> Result<()> {
+    let mut settings = match load_settings() {
+        Ok(settings) => settings,
+        Err(_) => Default::default(),
+    };
 
-    let config = load_settings()?.changelog_settings.ok_or(
+    let mut changelog_settings = default_changelog_settings()?;
+    if let Some(header) = params.header {
+        changelog_settings.header = Some(header);
+    }
+    if let Some(body) = params.body {
+        changelog_settings.body = body;
+    }
+    if let Some(footer) = params.footer {
+        changelog_settings.footer = Some(footer);
+    }
+    if params.trim {
+        changelog_settings.trim = true;
+    }
+
+    settings.changelog_settings = Some(changelog_settings);
+    persist_settings(settings)
+}
+
+/// Changelog settings from the `[changelog]` section of the default config template
+pub(crate) fn default_changelog_settings() -> Result<ChangelogSettings> {
+    #[derive(serde_derive::Deserialize)]
+    struct DefaultConfig {
+        changelog: ChangelogSettings,
+    }
+
+    let config: DefaultConfig = toml::from_str(DEFAULT_CHANGELOG_CONFIG)?;
+    Ok(config.changelog)
+}
+
+pub(crate) fn generate_changelog(args: GenerateChangeLog, output: Option<Output>) -> Result<()> {
+    if let Some(workdir) = &args.workdir {
+        env::set_current_dir(workdir)?;
+    }
+
+    let mut config = load_settings_from(&args.config)?.changelog_settings.ok_or(
         DoctaviousError::ChangelogError(String::from(
-            "changelog configuration not found",
+            "changelog configuration not found. Run `doctavious changelog init`",
         )),
     )?;
+    if let Some(body) = &args.body {
+        config.body = body.to_string();
+    }
+    match args.strip {
+        Some(StripParts::Header) => config.header = None,
+        Some(StripParts::Footer) => config.footer = None,
+        Some(StripParts::All) => {
+            config.header = None;
+            config.footer = None;
+        }
+        None => {}
+    }
+
+    let repositories = if args.from_context.is_some() {
+        vec![]
+    } else if args.repository.is_empty() {
+        vec![Repository::open(env::current_dir()?)?]
+    } else {
+        args.repository.iter().map(Repository::open).collect::<std::result::Result<_, _>>()?
+    };
+
+    // Generate changelog.
+    let changelog = if let Some(path) = &args.from_context {
+        Changelog::from_context(read_context(path)?, &config)?
+    } else if repositories.len() == 1 {
+        Changelog::new(get_releases(&repositories[0], &config, &args)?, &config)?
+    } else {
+        let mut releases = vec![];
+        for repository in &repositories {
+            releases.push(get_releases(repository, &config, &args)?);
+        }
+        Changelog::new(merge_releases(releases), &config)?
+    };
+
+    // only explicitly requested structured output as json is the default output for commands
+    if let Some(output @ (Output::Json | Output::Yaml)) = output {
+        if args.prepend.is_some() {
+            return Err(DoctaviousError::ChangelogError(String::from(
+                "prepend is not supported with json or yaml output",
+            )));
+        }
+        return match args.file {
+            Some(path) => write_context(changelog.context(), output, File::create(path)?),
+            None => write_context(changelog.context(), output, io::stdout()),
+        };
+    }
 
+    if let Some(path) = args.prepend {
+        let existing = match fs::read_to_string(&path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        changelog.prepend(existing, &mut File::create(path)?)
+    } else if let Some(path) = args.file {
+        changelog.generate(&mut File::create(path)?)
+    } else {
+        changelog.generate(&mut io::stdout())
+    }
+}
+
+/// Reads a changelog context, either JSON or YAML, from the file or stdin when the path is -
+fn read_context(path: &Path) -> Result<ChangelogContext> {
+    let content = if path == Path::new("-") {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(path)?
+    };
+
+    // YAML is a superset of JSON
+    Ok(serde_yaml::from_str(&content)?)
+}
+
+fn write_context<W: Write>(context: &ChangelogContext, output: Output, mut out: W) -> Result<()> {
+    match output {
+        Output::Yaml => serde_yaml::to_writer(out, context)?,
+        _ => {
+            serde_json::to_writer_pretty(&mut out, context)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Walks the commits, limited by the range, latest or unreleased options, splitting them into
+/// releases on tags matching the tag pattern
+pub(crate) fn get_releases<'a>(
+    repository: &'a Repository,
+    config: &ChangelogSettings,
+    args: &GenerateChangeLog,
+) -> Result<Vec<Release<'a>>> {
     // Parse tags.
-    let mut tags = git::tags(&repository, &config.git.tag_pattern)?;
+    let tag_pattern = args.tag_pattern.clone().or_else(|| config.git.tag_pattern.clone());
+    let mut tags = git::tags(repository, &tag_pattern)?;
+    let path_filter = PathFilter::new(
+        if args.include_paths.is_empty() {
+            config.git.include_paths.as_deref().unwrap_or_default()
+        } else {
+            args.include_paths.as_slice()
+        },
+        if args.exclude_paths.is_empty() {
+            config.git.exclude_paths.as_deref().unwrap_or_default()
+        } else {
+            args.exclude_paths.as_slice()
+        },
+    )?;
 
     // Parse commits.
-    let mut commit_range = args.range;
+    let mut commit_range = args.range.clone();
     if args.unreleased {
-        if let Some(last_tag) = tags.last().map(|(k, _)| k) {
+        if let Some(last_tag) = tags.last().map(|(_, v)| v) {
             commit_range = Some(format!("{}..HEAD", last_tag));
         }
     } else if args.latest {
-        if tags.len() < 2 {
+        if tags.is_empty() {
             return Err(DoctaviousError::ChangelogError(String::from(
-                "Latest tag cannot be processed",
+                "Latest tag cannot be processed as there are no tags",
             )));
+        } else if tags.len() == 1 {
+            // only tag so the latest release includes every commit up to it
+            commit_range = tags.last().map(|(_, v)| v.to_string());
         } else if let (Some(tag1), Some(tag2)) = (
-            tags.get_index(tags.len() - 2).map(|(k, _)| k),
-            tags.get_index(tags.len() - 1).map(|(k, _)| k),
+            tags.get_index(tags.len() - 2).map(|(_, v)| v),
+            tags.get_index(tags.len() - 1).map(|(_, v)| v),
         ) {
             commit_range = Some(format!("{}..{}", tag1, tag2));
         }
     }
 
-    let commits = git::commits(&repository, commit_range)?;
+    let commits = git::commits(repository, commit_range)?;
+    let repository_name = git::repository_name(repository);
 
     // Update tags.
-    if let Some(tag) = args.tag {
+    if let Some(tag) = &args.tag {
         if let Some(commit_id) = commits.first().map(|c| c.id().to_string()) {
             match tags.get(&commit_id) {
                 Some(tag) => {
                     warn!("There is already a tag ({}) for {}", tag, commit_id)
                 }
                 None => {
-                    tags.insert(commit_id, tag);
+                    tags.insert(commit_id, tag.to_string());
                 }
             }
         }
@@ -347,9 +626,13 @@ pub(crate) fn generate_changelog(args: GenerateChangeLog) -> Result<()> {
     let mut release_index = 0;
     let mut previous_release = Release::default();
     for git_commit in commits.into_iter().rev() {
-        let commit = Commit::from(&git_commit);
-        let commit_id = commit.id.to_string();
-        releases[release_index].commits.push(commit);
+        let commit_id = git_commit.id().to_string();
+        // commits outside of the paths are skipped but their tags still split releases
+        if path_filter.includes(repository, &git_commit)? {
+            let mut commit = Commit::from(&git_commit);
+            commit.repository = repository_name.clone();
+            releases[release_index].commits.push(commit);
+        }
         if let Some(tag) = tags.get(&commit_id) {
             releases[release_index].version = Some(tag.to_string());
             releases[release_index].commit_id = Some(commit_id);
@@ -363,7 +646,7 @@ pub(crate) fn generate_changelog(args: GenerateChangeLog) -> Result<()> {
     }
 
     // Set the previous release if needed.
-    if args.latest {
+    if args.latest && tags.len() > 1 {
         if let Some((commit_id, version)) = tags.get_index(tags.len() - 2) {
             let previous_release = Release {
                 commit_id: Some(commit_id.to_string()),
@@ -374,13 +657,372 @@ pub(crate) fn generate_changelog(args: GenerateChangeLog) -> Result<()> {
         }
     }
 
-    // Generate changelog.
-    let changelog = Changelog::new(releases, &config)?;
-    if let Some(path) = args.prepend {
-        changelog.prepend(fs::read_to_string(&path)?, &mut File::create(path)?)
-    } else if let Some(path) = args.file {
-        changelog.generate(&mut File::create(path)?)
-    } else {
-        changelog.generate(&mut io::stdout())
+    Ok(releases)
+}
+
+/// Merges the releases of multiple repositories into a single timeline. Releases with the same
+/// version are grouped together and ordered by their latest timestamp with the unreleased
+/// commits last.
+pub(crate) fn merge_releases<'a>(repository_releases: Vec<Vec<Release<'a>>>) -> Vec<Release<'a>> {
+    let mut merged: IndexMap<Option<String>, Release<'a>> = IndexMap::new();
+    for release in repository_releases.into_iter().flatten() {
+        match merged.get_mut(&release.version) {
+            Some(existing) => {
+                existing.commits.extend(release.commits);
+                existing.timestamp = existing.timestamp.max(release.timestamp);
+            }
+            None => {
+                merged.insert(release.version.clone(), release);
+            }
+        }
+    }
+
+    let mut releases: Vec<Release<'a>> = merged.into_iter().map(|(_, release)| release).collect();
+    for release in releases.iter_mut() {
+        release.commits.sort_by_key(|c| c.committer.timestamp);
+    }
+    releases.sort_by_key(|r| (r.version.is_none(), r.timestamp));
+
+    let mut previous_release = Release::default();
+    for release in releases.iter_mut() {
+        previous_release.previous = None;
+        release.previous = Some(Box::new(previous_release));
+        previous_release = release.clone();
+    }
+
+    releases
+}
+
+/// Limits commits to those changing paths which match the include globs, ignoring paths which
+/// match the exclude globs. Every commit is included when there are no globs.
+struct PathFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl PathFilter {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: include.iter().map(|p| Pattern::new(p)).collect::<std::result::Result<_, _>>()?,
+            exclude: exclude.iter().map(|p| Pattern::new(p)).collect::<std::result::Result<_, _>>()?,
+        })
+    }
+
+    fn includes(&self, repository: &Repository, commit: &GitCommit) -> Result<bool> {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return Ok(true);
+        }
+
+        Ok(git::changed_paths(repository, commit)?.iter().any(|path| self.matches(path)))
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let matches_pattern = |pattern: &Pattern| {
+            // a pattern without wildcards, ex: packages/api, also matches paths within it
+            pattern.matches_path(path) || path.starts_with(pattern.as_str().trim_end_matches('/'))
+        };
+        (self.include.is_empty() || self.include.iter().any(matches_pattern))
+            && !self.exclude.iter().any(matches_pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use git2::{Oid, Repository, Signature, Time};
+    use tempfile::TempDir;
+
+    use crate::commands::changelog::changelog::{
+        default_changelog_settings, get_releases, merge_releases, Changelog, GenerateChangeLog
+    };
+    use crate::commands::changelog::context::{ChangelogContext, SCHEMA_VERSION};
+
+    fn commit(repository: &Repository, message: &str, time: i64) -> Oid {
+        let signature = Signature::new("Doctavious", "doctavious@example.com", &Time::new(time, 0)).unwrap();
+        let tree = repository.find_tree(repository.index().unwrap().write_tree().unwrap()).unwrap();
+        let parent = repository.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repository.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap()
+    }
+
+    fn commit_file(repository: &Repository, path: &str, message: &str, time: i64) -> Oid {
+        let workdir = repository.workdir().unwrap();
+        fs::create_dir_all(workdir.join(path).parent().unwrap()).unwrap();
+        fs::write(workdir.join(path), message).unwrap();
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        commit(repository, message, time)
+    }
+
+    fn tag(repository: &Repository, name: &str, oid: Oid) {
+        let object = repository.find_object(oid, None).unwrap();
+        repository.tag_lightweight(name, &object, false).unwrap();
+    }
+
+    fn args() -> GenerateChangeLog {
+        GenerateChangeLog {
+            config: PathBuf::from("doctavious.toml"),
+            workdir: None,
+            repository: vec![],
+            prepend: None,
+            file: None,
+            tag: None,
+            body: None,
+            strip: None,
+            latest: false,
+            unreleased: false,
+            range: None,
+            from_context: None,
+            include_paths: vec![],
+            exclude_paths: vec![],
+            tag_pattern: None,
+        }
+    }
+
+    fn generate(repository: &Repository, args: &GenerateChangeLog) -> String {
+        let mut config = default_changelog_settings().unwrap();
+        config.footer = None;
+        let releases = get_releases(repository, &config, args).unwrap();
+        let mut out = Vec::new();
+        Changelog::new(releases, &config).unwrap().generate(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn should_generate_changelog_split_by_tags() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        commit(&repository, "feat: add adr command", 1_600_000_000);
+        let v1 = commit(&repository, "fix: handle missing adr dir", 1_600_000_100);
+        tag(&repository, "v0.1.0", v1);
+        commit(&repository, "feat: add rfd command", 1_600_000_200);
+        let v2 = commit(&repository, "chore(release): prepare for v0.2.0", 1_600_000_300);
+        tag(&repository, "v0.2.0", v2);
+        commit(&repository, "docs: document changelog", 1_600_000_400);
+
+        let changelog = generate(&repository, &args());
+        assert!(changelog.starts_with("# Changelog"));
+        let unreleased = changelog.find("## [unreleased]").unwrap();
+        let v2 = changelog.find("## [0.2.0] - 2020-09-13").unwrap();
+        let v1 = changelog.find("## [0.1.0] - 2020-09-13").unwrap();
+        assert!(unreleased < v2 && v2 < v1);
+        let position = |text: &str| changelog.find(text).unwrap();
+        assert!(unreleased < position("### Documentation") && position("- Document changelog") < v2);
+        assert!(v2 < position("### Features") && position("- Add rfd command") < v1);
+        assert!(v1 < position("### Bug Fixes") && v1 < position("- Handle missing adr dir"));
+        assert!(!changelog.contains("prepare for"));
+
+        let changelog = generate(&repository, &GenerateChangeLog { latest: true, ..args() });
+        assert!(changelog.contains("## [0.2.0]"));
+        assert!(!changelog.contains("## [0.1.0]"));
+        assert!(!changelog.contains("## [unreleased]"));
+
+        let changelog = generate(&repository, &GenerateChangeLog { unreleased: true, ..args() });
+        assert!(changelog.contains("## [unreleased]"));
+        assert!(!changelog.contains("## [0.2.0]"));
+
+        let changelog = generate(&repository, &GenerateChangeLog {
+            unreleased: true,
+            tag: Some(String::from("v0.3.0")),
+            ..args()
+        });
+        assert!(changelog.contains("## [0.3.0]"));
+        assert!(!changelog.contains("## [unreleased]"));
+    }
+
+    #[test]
+    fn should_expose_contributors_to_templates() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        commit(
+            &repository,
+            "feat: add bump command (#12)\n\nFixes #3\n\nCo-authored-by: Jane Doe <JANE@example.com>",
+            1_600_000_000,
+        );
+        commit(
+            &repository,
+            "fix: typo\n\nCo-authored-by: Jane Doe <jane@example.com>",
+            1_600_000_100,
+        );
+
+        let mut config = default_changelog_settings().unwrap();
+        config.header = None;
+        config.footer = None;
+        config.body = String::from(
+            "{% for commit in commits %}{{ commit.author.name }} {% if commit.pull_request %}{{ commit.pull_request }}{% endif %} \
+             {{ commit.issues | join(sep=\",\") }}\n{% endfor %}\
+             Thanks to {% for c in contributors %}{{ c.name }};{% endfor %}",
+        );
+        let releases = get_releases(&repository, &config, &args()).unwrap();
+        let mut out = Vec::new();
+        Changelog::new(releases, &config).unwrap().generate(&mut out).unwrap();
+        let changelog = String::from_utf8(out).unwrap();
+
+        assert!(changelog.contains("Doctavious 12 3\n"));
+        assert!(changelog.contains("Thanks to Doctavious;Jane Doe;"));
+    }
+
+    #[test]
+    fn should_only_include_commits_changing_paths() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        commit_file(&repository, "packages/api/lib.rs", "feat: add api", 1_600_000_000);
+        let web = commit_file(&repository, "packages/web/index.ts", "feat: add web", 1_600_000_100);
+        tag(&repository, "web-v0.1.0", web);
+        let api = commit_file(&repository, "packages/api/docs/readme.md", "docs: document api", 1_600_000_200);
+        tag(&repository, "api-v0.1.0", api);
+        commit_file(&repository, "packages/api/routes.rs", "fix: handle missing route", 1_600_000_300);
+        commit_file(&repository, "packages/web/app.ts", "fix: render app", 1_600_000_400);
+
+        let changelog = generate(&repository, &GenerateChangeLog {
+            include_paths: vec![String::from("packages/api/**")],
+            exclude_paths: vec![String::from("packages/api/docs")],
+            tag_pattern: Some(String::from("api-v*")),
+            ..args()
+        });
+        let unreleased = changelog.find("## [unreleased]").unwrap();
+        let v1 = changelog.find("## [api-0.1.0]").unwrap();
+        assert!(unreleased < changelog.find("- Handle missing route").unwrap());
+        assert!(v1 < changelog.find("- Add api").unwrap());
+        assert!(!changelog.contains("web"));
+        assert!(!changelog.contains("Document api"));
+    }
+
+    #[test]
+    fn should_merge_releases_across_repositories() {
+        let cli_dir = TempDir::new().unwrap();
+        let cli = Repository::init(cli_dir.path()).unwrap();
+        let v1 = commit(&cli, "feat: add adr command", 1_600_000_000);
+        tag(&cli, "v0.1.0", v1);
+        commit(&cli, "fix: handle missing adr dir", 1_600_000_300);
+
+        let api_dir = TempDir::new().unwrap();
+        let api = Repository::init(api_dir.path()).unwrap();
+        commit(&api, "feat: add adr endpoint", 1_600_000_100);
+        let v1 = commit(&api, "fix: validate adr", 1_600_000_200);
+        tag(&api, "v0.1.0", v1);
+
+        let mut config = default_changelog_settings().unwrap();
+        config.header = None;
+        config.footer = None;
+        config.body = String::from(
+            "{% if version %}## {{ version }}{% else %}## unreleased{% endif %}\n\
+             {% for commit in commits %}- {{ commit.message }} ({{ commit.repository }})\n{% endfor %}",
+        );
+        let releases = merge_releases(vec![
+            get_releases(&cli, &config, &args()).unwrap(),
+            get_releases(&api, &config, &args()).unwrap(),
+        ]);
+        assert_eq!(2, releases.len());
+        assert_eq!(Some(String::from("v0.1.0")), releases[1].previous.as_ref().unwrap().version);
+
+        let mut out = Vec::new();
+        Changelog::new(releases, &config).unwrap().generate(&mut out).unwrap();
+        let cli_name = cli_dir.path().file_name().unwrap().to_string_lossy().to_string();
+        let api_name = api_dir.path().file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(
+            format!(
+                "## unreleased\n- handle missing adr dir ({cli})\n\
+                 ## v0.1.0\n- add adr command ({cli})\n- add adr endpoint ({api})\n- validate adr ({api})\n",
+                cli = cli_name,
+                api = api_name,
+            ),
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_prepend_without_duplicating_releases() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let v1 = commit(&repository, "feat: add adr command", 1_600_000_000);
+        tag(&repository, "v0.1.0", v1);
+        commit(&repository, "feat: add rfd command", 1_600_000_100);
+
+        let config = default_changelog_settings().unwrap();
+        let prepend = |existing: &str| {
+            let releases = get_releases(&repository, &config, &args()).unwrap();
+            let mut out = Vec::new();
+            Changelog::new(releases, &config)
+                .unwrap()
+                .prepend(existing.to_string(), &mut out)
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let changelog = prepend("");
+        assert_eq!(changelog, prepend(&changelog));
+        assert_eq!(1, changelog.matches("## [0.1.0]").count());
+        assert_eq!(1, changelog.matches("- Add rfd command").count());
+        assert_eq!(1, changelog.matches("<!-- generated by doctavious -->").count());
+    }
+
+    #[test]
+    fn should_prepend_custom_template_without_merging() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let v1 = commit(&repository, "feat: add adr command", 1_600_000_000);
+        tag(&repository, "v0.1.0", v1);
+
+        let mut config = default_changelog_settings().unwrap();
+        config.header = None;
+        config.footer = None;
+        config.body = String::from(
+            "{{ version }}\n{% for commit in commits %}* {{ commit.message }}\n{% endfor %}",
+        );
+        let releases = get_releases(&repository, &config, &args()).unwrap();
+        let mut out = Vec::new();
+        Changelog::new(releases, &config)
+            .unwrap()
+            .prepend(String::from("v0.0.1\n* initial commit\n"), &mut out)
+            .unwrap();
+
+        assert_eq!(
+            "v0.1.0\n* add adr command\nv0.0.1\n* initial commit\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_render_changelog_from_context() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let v1 = commit(&repository, "feat(adr): add adr command", 1_600_000_000);
+        tag(&repository, "v0.1.0", v1);
+        commit(&repository, "fix!: handle missing adr dir\n\nFixes #3", 1_600_000_100);
+
+        let config = default_changelog_settings().unwrap();
+        let releases = get_releases(&repository, &config, &args()).unwrap();
+        let changelog = Changelog::new(releases, &config).unwrap();
+        let context = changelog.context();
+        assert_eq!(SCHEMA_VERSION, context.schema_version);
+        assert_eq!(None, context.releases[0].version);
+        assert_eq!(
+            Some(String::from("v0.1.0")),
+            context.releases[0].previous.as_ref().unwrap().version
+        );
+        let fix = &context.releases[0].commits[0];
+        assert_eq!(Some(String::from("fix")), fix.type_);
+        assert!(fix.breaking);
+        assert_eq!(Some(String::from("Bug Fixes")), fix.category);
+        assert_eq!(vec![3], fix.issues);
+        assert_eq!(Some(String::from("adr")), context.releases[1].commits[0].scope);
+
+        let json = serde_json::to_string(context).unwrap();
+        assert!(json.contains("\"type\":\"fix\""));
+        let yaml = serde_yaml::to_string(context).unwrap();
+        let from_json: ChangelogContext = serde_yaml::from_str(&json).unwrap();
+        let from_yaml: ChangelogContext = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(context, &from_json);
+        assert_eq!(context, &from_yaml);
+
+        let mut expected = Vec::new();
+        changelog.generate(&mut expected).unwrap();
+        let mut out = Vec::new();
+        Changelog::from_context(from_json, &config).unwrap().generate(&mut out).unwrap();
+        assert_eq!(String::from_utf8(expected).unwrap(), String::from_utf8(out).unwrap());
     }
 }
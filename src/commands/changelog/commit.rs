@@ -1,12 +1,109 @@
 use crate::commands::changelog::CommitParser;
 use crate::doctavious_error::{DoctaviousError, Result};
-use git2::Commit as GitCommit;
+use git2::{Commit as GitCommit, Signature as GitSignature};
 use git_conventional::Commit as ConventionalCommit;
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+// Release notes as used by CockroachDB
+// https://cockroachlabs.atlassian.net/wiki/spaces/CRDB/pages/186548364/Release+notes
+// The following release note formats have been seen in the wild and are all supported:
+//
+// Release note (xxx): yyy    <- canonical
+// Release Notes: None
+// Release note (xxx) : yyy
+// Release note: (xxx): yyy
+// Release note: xxx: yyy
+// Release note: (xxx) yyy
+// Release note: yyy (no category)
+// Release note (xxx, zzz): yyy
+lazy_static! {
+    static ref NO_RELEASE_NOTE_RE: Regex =
+        RegexBuilder::new(r"^[rR]elease [nN]otes?: *[Nn]one\s*$")
+            .multi_line(true)
+            .build()
+            .unwrap();
+
+    // form1 captures :? (xxx) ?: yyy
+    // form2 captures : xxx: yyy - careful not to capture too much so only accepts one or two words
+    // form3 captures : yyy - no category
+    static ref RELEASE_NOTE_RE: Regex = Regex::new(concat!(
+        r"^[rR]elease [nN]otes? *(?:",
+        r":? *\((?P<cat1>[^)]*)\) *:?",
+        r"|: *(?P<cat2>[^ ]+(?: +[^ ]+)?) *:",
+        r"|:(?P<cat3>)",
+        r") *(?P<note>.*)$"
+    )).unwrap();
+
+    static ref RELEASE_JUSTIFICATION_RE: Regex =
+        Regex::new(r"^[rR]elease [jJ]ustification: *(?P<justification>.*)$").unwrap();
+
+    static ref CO_AUTHOR_RE: Regex =
+        RegexBuilder::new(r"^Co-authored-by: *(?P<name>[^<]*?) *<(?P<email>[^>]*)>")
+            .multi_line(true)
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+
+    // Fixes #123, Closed #45, resolves #1, #2
+    static ref ISSUE_REFERENCE_RE: Regex = RegexBuilder::new(
+        r"^(?:[fF]ix(?:es|ed)?|[cC]lose[sd]?|[rR]esolve[sd]?):? +(?P<references>#\d+(?:(?:, *| +and +| +)#\d+)*)"
+    )
+        .multi_line(true)
+        .build()
+        .unwrap();
+
+    // pull request number appended to the subject by GitHub, ex: feat: add bump command (#678)
+    static ref PULL_REQUEST_RE: Regex = Regex::new(r"\(#(?P<number>\d+)\)\s*$").unwrap();
+
+    // git trailers which end a release note, ex: Co-authored-by: or Fixes:
+    static ref TRAILER_RE: Regex =
+        Regex::new(r"^(?:[A-Za-z][A-Za-z-]*-by|Epic|Fixes|Informs|Resolves|Closes): ").unwrap();
+}
+
+/// Release note extracted from a commit message.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReleaseNote {
+    /// Category of the release note, ex: bug fix, when one is given.
+    pub category: Option<String>,
+    /// Text of the release note.
+    pub text: String,
+}
+
+/// Author or committer of a commit.
+#[derive(Debug, Clone, Default, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Signature {
+    /// Name on the signature.
+    pub name: Option<String>,
+    /// Email on the signature.
+    pub email: Option<String>,
+    /// Time of the signature in seconds since the epoch.
+    pub timestamp: i64,
+}
+
+impl<'a> From<GitSignature<'a>> for Signature {
+    fn from(signature: GitSignature<'a>) -> Self {
+        Self {
+            name: signature.name().map(String::from),
+            email: signature.email().map(String::from),
+            timestamp: signature.when().seconds(),
+        }
+    }
+}
+
+/// Person who contributed to a commit, either as its author or a co-author.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Contributor {
+    /// Name of the contributor.
+    pub name: Option<String>,
+    /// Email of the contributor.
+    pub email: Option<String>,
+}
+
 /// Common commit object that is parsed from a repository.
+/// Serialized with snake_case fields which are also expected when deserializing.
 #[derive(Debug, Clone, PartialEq, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct Commit<'a> {
     /// Commit ID.
     pub id: String,
@@ -17,27 +114,106 @@ pub struct Commit<'a> {
     pub conventional: Option<ConventionalCommit<'a>>,
     /// Commit category based on a category parser or its conventional type.
     pub category: Option<String>,
+    /// Release notes, ex: `Release note (bug fix): ...`, within the commit message.
+    #[serde(default)]
+    pub release_notes: Vec<ReleaseNote>,
+    /// Why the commit should be included in a release, ex: `Release justification: ...`.
+    #[serde(default)]
+    pub release_justification: Option<String>,
+    /// Author of the commit.
+    #[serde(default)]
+    pub author: Signature,
+    /// Committer of the commit.
+    #[serde(default)]
+    pub committer: Signature,
+    /// Co-authors from `Co-authored-by` trailers.
+    #[serde(default)]
+    pub co_authors: Vec<Contributor>,
+    /// Issues closed by the commit, ex: `Fixes #123`.
+    #[serde(default)]
+    pub issues: Vec<u64>,
+    /// Pull request the commit was merged from, ex: `(#678)` at the end of the subject.
+    #[serde(default)]
+    pub pull_request: Option<u64>,
+    /// Name of the repository the commit belongs to.
+    #[serde(default)]
+    pub repository: Option<String>,
 }
 
 impl<'a> From<&GitCommit<'a>> for Commit<'a> {
     fn from(commit: &GitCommit<'a>) -> Self {
-        Self::new(
+        let mut c = Self::new(
             commit.id().to_string(),
             commit.message().unwrap_or_default().to_string(),
-        )
+        );
+        c.author = commit.author().into();
+        c.committer = commit.committer().into();
+        c
     }
 }
 
 impl Commit<'_> {
     /// Constructs a new instance.
     pub fn new(id: String, message: String) -> Self {
-        Self { id, message, conventional: None, category: None }
+        let co_authors = CO_AUTHOR_RE
+            .captures_iter(&message)
+            .map(|c| Contributor {
+                name: Some(c["name"].to_string()).filter(|n| !n.is_empty()),
+                email: Some(c["email"].to_string()).filter(|e| !e.is_empty()),
+            })
+            .collect();
+        let mut issues: Vec<u64> = vec![];
+        for captures in ISSUE_REFERENCE_RE.captures_iter(&message) {
+            for reference in captures["references"].split('#').skip(1) {
+                let number = reference
+                    .trim_end_matches(|c: char| !c.is_ascii_digit())
+                    .parse::<u64>();
+                if let Ok(number) = number {
+                    if !issues.contains(&number) {
+                        issues.push(number);
+                    }
+                }
+            }
+        }
+        let pull_request = PULL_REQUEST_RE
+            .captures(message.lines().next().unwrap_or_default())
+            .and_then(|c| c["number"].parse().ok());
+
+        Self {
+            id,
+            message,
+            conventional: None,
+            category: None,
+            release_notes: vec![],
+            release_justification: None,
+            author: Signature::default(),
+            committer: Signature::default(),
+            co_authors,
+            issues,
+            pull_request,
+            repository: None,
+        }
+    }
+
+    /// Author followed by the co-authors of the commit.
+    pub fn contributors(&self) -> Vec<Contributor> {
+        let author = Contributor {
+            name: self.author.name.clone(),
+            email: self.author.email.clone(),
+        };
+        let mut contributors = vec![author];
+        contributors.extend(self.co_authors.iter().cloned());
+        contributors
+            .into_iter()
+            .filter(|c| c.name.is_some() || c.email.is_some())
+            .collect()
     }
 
     /// Processes the commit.
     ///
     /// * converts commit to a conventional commit
     /// * sets the group for the commit
+    /// * extracts release notes and release justification
     pub fn process(
         &self,
         parsers: Option<&Vec<CommitParser>>,
@@ -51,6 +227,7 @@ impl Commit<'_> {
         if let Some(parsers) = parsers {
             commit = commit.into_category(parsers, filter_commits)?;
         }
+        commit = commit.into_release_notes()?;
         Ok(commit)
     }
 
@@ -98,6 +275,72 @@ impl Commit<'_> {
             )))
         }
     }
+
+    /// Returns the commit with its release notes and release justification set.
+    ///
+    /// Commits with a `Release note: None` are skipped. When the commit doesn't otherwise have a
+    /// category, i.e. it isn't a conventional commit and isn't matched by a parser, its category
+    /// is that of its first release note.
+    pub fn into_release_notes(mut self) -> Result<Self> {
+        if NO_RELEASE_NOTE_RE.is_match(&self.message) {
+            return Err(DoctaviousError::CategoryError(String::from(
+                "Skipping commit without release note",
+            )));
+        }
+
+        let mut release_notes: Vec<ReleaseNote> = vec![];
+        let mut justification: Option<String> = None;
+        // a release note continues until the next release note or justification whereas a
+        // justification is a single paragraph
+        let mut in_note = false;
+        for line in self.message.lines() {
+            if let Some(captures) = RELEASE_NOTE_RE.captures(line) {
+                let category = ["cat1", "cat2", "cat3"]
+                    .iter()
+                    .filter_map(|name| captures.name(name))
+                    .map(|m| m.as_str().trim().to_lowercase())
+                    .find(|c| !c.is_empty());
+                release_notes.push(ReleaseNote {
+                    category,
+                    text: captures["note"].trim().to_string(),
+                });
+                in_note = true;
+            } else if let Some(captures) = RELEASE_JUSTIFICATION_RE.captures(line) {
+                justification = Some(captures["justification"].trim().to_string());
+                in_note = false;
+            } else if TRAILER_RE.is_match(line) {
+                in_note = false;
+                if justification.is_some() {
+                    self.release_justification = justification.take();
+                }
+            } else if in_note {
+                if let Some(note) = release_notes.last_mut() {
+                    note.text.push('\n');
+                    note.text.push_str(line);
+                }
+            } else if let Some(text) = justification.as_mut() {
+                if line.trim().is_empty() {
+                    self.release_justification = justification.take();
+                } else {
+                    text.push(' ');
+                    text.push_str(line.trim());
+                }
+            }
+        }
+
+        for note in release_notes.iter_mut() {
+            note.text = note.text.trim().to_string();
+        }
+
+        if self.category.is_none() && self.conventional.is_none() {
+            self.category = release_notes.iter().find_map(|n| n.category.clone());
+        }
+        if justification.is_some() {
+            self.release_justification = justification;
+        }
+        self.release_notes = release_notes;
+        Ok(self)
+    }
 }
 
 impl Serialize for Commit<'_> {
@@ -108,7 +351,7 @@ impl Serialize for Commit<'_> {
     where
         S: Serializer,
     {
-        let mut commit = serializer.serialize_struct("Commit", 8)?;
+        let mut commit = serializer.serialize_struct("Commit", 16)?;
         commit.serialize_field("id", &self.id)?;
         match &self.conventional {
             Some(conv) => {
@@ -139,6 +382,17 @@ impl Serialize for Commit<'_> {
                 commit.serialize_field("category", &self.category)?;
             }
         }
+        commit.serialize_field("release_notes", &self.release_notes)?;
+        commit.serialize_field(
+            "release_justification",
+            &self.release_justification,
+        )?;
+        commit.serialize_field("author", &self.author)?;
+        commit.serialize_field("committer", &self.committer)?;
+        commit.serialize_field("co_authors", &self.co_authors)?;
+        commit.serialize_field("issues", &self.issues)?;
+        commit.serialize_field("pull_request", &self.pull_request)?;
+        commit.serialize_field("repository", &self.repository)?;
         commit.end()
     }
 }
@@ -146,7 +400,6 @@ impl Serialize for Commit<'_> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use regex::{Regex, RegexBuilder};
     #[test]
     fn conventional_commit() {
         let test_cases = vec![
@@ -205,45 +458,141 @@ mod test {
     // fixannot = re.compile(r'^([fF]ix(es|ed)?|[cC]lose(d|s)?) #', flags=re.M)
     #[test]
     fn commit_release_note() {
-        // Release justification: testing only
-        // Release justification: Bug fixes and low-risk updates to new
-        // functionality
-        //
-        // Release note: None
-        // Release note (enterprise change): cloud storage sinks are no longer experimental
-
-        // no release note
-        // RegexBuilder::new("^[rR]elease [nN]otes?: *[Nn]one").multi_line(true).build_mod()
-        // Captures :? (xxx) ?: yyy
-        // form1 = Regex::new(":? *\((?P<cat1>[^)]*)\) *:?").ok()
-        // Captures : xxx: yyy - this must be careful not to capture too much, we just accept one or two words
-        // form2 = r': *(?P<cat2>[^ ]+(?: +[^ ]+)?) *:'
-        // form2 = Regex::new(": *(?P<cat2>[^ ]+(?: +[^ ]+)?) *:").ok()
-        // Captures : yyy - no category
-        // form3 = r':(?P<cat3>)'
-        // formt3 = Regex::new(":(?P<cat3>)").ok()
-        // relnote = re.compile(r'(?:^|[\n\r])[rR]elease [nN]otes? *(?:' + form1 + '|' + form2 + '|' + form3 + r') *(?P<note>.*)$', flags=re.S)
-        // release_note =
-        // RegexBuilder::new("(?:^|[\n\r])[rR]elease [nN]otes? *(?:' + form1 + '|' + form2 + '|' + form3 + r").dot_matches_new_line(true).build_mod()
-        // commit_parsers:       Some(vec![
-        //     CommitParser {
-        //         message: Regex::new("^[rR]elease [nN]otes?: *[Nn]one").ok(),
-        //         body:    None,
-        //         category:   Some(String::from("New features")),
-        //         skip:    None,
-        //     },
-        //     CommitParser {
-        //         message: Regex::new("fix*").ok(),
-        //         body:    None,
-        //         category:   Some(String::from("Bug Fixes")),
-        //         skip:    None,
-        //     },
-        //     CommitParser {
-        //         message: Regex::new(".*").ok(),
-        //         body:    None,
-        //         category:   Some(String::from("Other")),
-        //         skip:    None,
-        //     },
-        // ])
+        let notes = |message: &str| {
+            Commit::new(String::from("123123"), String::from(message))
+                .into_release_notes()
+        };
+
+        assert!(notes("fix typo\n\nRelease note: None").is_err());
+        assert!(notes("fix typo\n\nRelease Notes: none\n").is_err());
+
+        let commit = notes(
+            "changefeedccl: graduate cloud storage sinks\n\n\
+             Release note (enterprise change): cloud storage sinks are no longer\n\
+             experimental\n\n\
+             Release justification: Bug fixes and low-risk updates to new\n\
+             functionality\n\n\
+             Co-authored-by: Jane Doe <jane@example.com>",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![ReleaseNote {
+                category: Some(String::from("enterprise change")),
+                text: String::from("cloud storage sinks are no longer\nexperimental"),
+            }],
+            commit.release_notes
+        );
+        assert_eq!(
+            Some(String::from(
+                "Bug fixes and low-risk updates to new functionality"
+            )),
+            commit.release_justification
+        );
+        assert_eq!(Some(String::from("enterprise change")), commit.category);
+
+        let categories = |message: &str| {
+            notes(message)
+                .unwrap()
+                .release_notes
+                .into_iter()
+                .map(|n| (n.category, n.text))
+                .collect::<Vec<(Option<String>, String)>>()
+        };
+        let note = |category: Option<&str>, text: &str| {
+            vec![(category.map(String::from), String::from(text))]
+        };
+        assert_eq!(note(Some("bug fix"), "yyy"), categories("Release note (Bug Fix) : yyy"));
+        assert_eq!(note(Some("bug fix"), "yyy"), categories("Release note: (bug fix): yyy"));
+        assert_eq!(note(Some("bug fix"), "yyy"), categories("Release note: bug fix: yyy"));
+        assert_eq!(note(Some("bug fix"), "yyy"), categories("Release note: (bug fix) yyy"));
+        assert_eq!(note(None, "fixed a crash"), categories("Release note: fixed a crash"));
+        assert_eq!(
+            note(Some("sql change, bug fix"), "yyy"),
+            categories("Release note (sql change, bug fix): yyy")
+        );
+        assert_eq!(
+            vec![
+                (Some(String::from("sql change")), String::from("first")),
+                (Some(String::from("bug fix")), String::from("second")),
+            ],
+            categories("Release note (sql change): first\n\nRelease note (bug fix): second")
+        );
+        assert!(categories("fix typo").is_empty());
+
+        // conventional commits keep their type as their category
+        let commit = Commit::new(
+            String::from("123123"),
+            String::from("fix: typo\n\nRelease note (bug fix): fixed a typo"),
+        )
+        .into_conventional()
+        .unwrap()
+        .into_release_notes()
+        .unwrap();
+        assert_eq!(None, commit.category);
+        assert_eq!(1, commit.release_notes.len());
+    }
+
+    #[test]
+    fn commit_references() {
+        let commit = Commit::new(
+            String::from("123123"),
+            String::from(
+                "feat(changelog): add contributors (#678)\n\n\
+                 Fixes #123, #124\n\
+                 closes #45\n\
+                 Resolves: #123\n\
+                 See #999 for details\n\n\
+                 Co-authored-by: Jane Doe <jane@example.com>\n\
+                 co-authored-by: John Smith <john@example.com>",
+            ),
+        );
+        assert_eq!(Some(678), commit.pull_request);
+        assert_eq!(vec![123, 124, 45], commit.issues);
+        assert_eq!(
+            vec![
+                Contributor {
+                    name: Some(String::from("Jane Doe")),
+                    email: Some(String::from("jane@example.com")),
+                },
+                Contributor {
+                    name: Some(String::from("John Smith")),
+                    email: Some(String::from("john@example.com")),
+                },
+            ],
+            commit.co_authors
+        );
+
+        let commit =
+            Commit::new(String::from("124124"), String::from("fix: typo\n\n(#12)"));
+        assert_eq!(None, commit.pull_request);
+        assert!(commit.issues.is_empty());
+    }
+
+    #[test]
+    fn commit_round_trip() {
+        let commit = Commit::new(
+            String::from("123123"),
+            String::from(
+                "Add contributors (#678)\n\n\
+                 Fixes #123\n\n\
+                 Release note (cli change): add contributors\n\n\
+                 Release justification: low-risk update to new functionality\n\n\
+                 Co-authored-by: Jane Doe <jane@example.com>",
+            ),
+        )
+        .into_release_notes()
+        .unwrap();
+
+        let json = serde_json::to_string(&commit).unwrap();
+        let deserialized: Commit = serde_json::from_str(&json).unwrap();
+        assert_eq!(commit.release_notes, deserialized.release_notes);
+        assert_eq!(
+            Some(String::from("low-risk update to new functionality")),
+            deserialized.release_justification
+        );
+        assert_eq!(commit.co_authors, deserialized.co_authors);
+        assert_eq!(Some(678), deserialized.pull_request);
+        assert_eq!(vec![123], deserialized.issues);
+        assert_eq!(commit, deserialized);
     }
 }
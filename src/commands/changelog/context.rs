@@ -0,0 +1,153 @@
+// Structured representation of the processed releases which templates are rendered with and which
+// `changelog generate --output json|yaml` writes for downstream tooling. The same document can be
+// passed back via `--from-context` to render a template without reading git.
+//
+// The schema is versioned by `schema_version`. Within a version fields are only ever added so
+// consumers should ignore unknown fields. Removing or changing the meaning of a field increments
+// the version.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::commands::changelog::commit::{Commit, Contributor, ReleaseNote, Signature};
+use crate::commands::changelog::release::Release;
+
+/// Current version of the changelog context schema
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+/// Processed releases, most recent first.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ChangelogContext {
+    /// Version of the schema the context conforms to
+    pub schema_version: u32,
+    pub releases: Vec<ReleaseContext>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ReleaseContext {
+    /// Tag of the release. Null for unreleased commits
+    pub version: Option<String>,
+    /// ID of the tagged commit. Null for unreleased commits
+    pub commit_id: Option<String>,
+    /// Time of the tagged commit in seconds since the epoch. 0 for unreleased commits
+    #[serde(default)]
+    pub timestamp: i64,
+    /// Release preceding this one
+    pub previous: Option<PreviousReleaseContext>,
+    pub commits: Vec<CommitContext>,
+    /// Unique authors and co-authors of the commits
+    #[serde(default)]
+    pub contributors: Vec<Contributor>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PreviousReleaseContext {
+    pub version: Option<String>,
+    pub commit_id: Option<String>,
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct CommitContext {
+    pub id: String,
+    /// Description of a conventional commit otherwise the full commit message
+    pub message: String,
+    /// Body of a conventional commit
+    pub body: Option<String>,
+    /// Footer values of a conventional commit
+    #[serde(default)]
+    pub footers: Vec<String>,
+    /// Type of a conventional commit, ex: feat
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// Scope of a conventional commit
+    pub scope: Option<String>,
+    /// Whether a conventional commit has a breaking change
+    #[serde(default)]
+    pub breaking: bool,
+    pub breaking_description: Option<String>,
+    /// Category from the commit parsers, otherwise the conventional commit type or the category
+    /// of the first release note
+    pub category: Option<String>,
+    #[serde(default)]
+    pub release_notes: Vec<ReleaseNote>,
+    pub release_justification: Option<String>,
+    #[serde(default)]
+    pub author: Signature,
+    #[serde(default)]
+    pub committer: Signature,
+    #[serde(default)]
+    pub co_authors: Vec<Contributor>,
+    /// Issues closed by the commit, ex: Fixes #123
+    #[serde(default)]
+    pub issues: Vec<u64>,
+    /// Pull request number from the commit subject, ex: (#678)
+    pub pull_request: Option<u64>,
+    /// Repository of the commit when generated from multiple repositories
+    pub repository: Option<String>,
+}
+
+impl ChangelogContext {
+    pub fn new(releases: &[Release]) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            releases: releases.iter().map(ReleaseContext::from).collect(),
+        }
+    }
+}
+
+impl From<&Release<'_>> for ReleaseContext {
+    fn from(release: &Release) -> Self {
+        Self {
+            version: release.version.clone(),
+            commit_id: release.commit_id.clone(),
+            timestamp: release.timestamp,
+            previous: release.previous.as_ref().map(|previous| PreviousReleaseContext {
+                version: previous.version.clone(),
+                commit_id: previous.commit_id.clone(),
+                timestamp: previous.timestamp,
+            }),
+            commits: release.commits.iter().map(CommitContext::from).collect(),
+            contributors: release.contributors.clone(),
+        }
+    }
+}
+
+impl From<&Commit<'_>> for CommitContext {
+    fn from(commit: &Commit) -> Self {
+        let mut context = Self {
+            id: commit.id.clone(),
+            message: commit.message.clone(),
+            body: None,
+            footers: vec![],
+            type_: None,
+            scope: None,
+            breaking: false,
+            breaking_description: None,
+            category: commit.category.clone(),
+            release_notes: commit.release_notes.clone(),
+            release_justification: commit.release_justification.clone(),
+            author: commit.author.clone(),
+            committer: commit.committer.clone(),
+            co_authors: commit.co_authors.clone(),
+            issues: commit.issues.clone(),
+            pull_request: commit.pull_request,
+            repository: commit.repository.clone(),
+        };
+
+        if let Some(conventional) = &commit.conventional {
+            context.message = conventional.description().to_string();
+            context.body = conventional.body().map(String::from);
+            context.footers = conventional.footers().iter().map(|f| f.value().to_string()).collect();
+            context.type_ = Some(conventional.type_().to_string());
+            context.scope = conventional.scope().map(|s| s.to_string());
+            context.breaking = conventional.breaking();
+            context.breaking_description = conventional.breaking_description().map(String::from);
+            if context.category.is_none() {
+                context.category = context.type_.clone();
+            }
+        }
+
+        context
+    }
+}
@@ -0,0 +1,395 @@
+// Parsing and merging of changelogs following https://keepachangelog.com/en/1.0.0/
+//
+// A changelog is made up of a header, releases, each with a `## [version] - date` heading and
+// `### Category` subsections, and link reference definitions at the bottom which typically
+// compare a release to its previous release.
+
+use std::fmt::{Display, Formatter};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::doctavious_error::{DoctaviousError, Result};
+
+const UNRELEASED: &str = "Unreleased";
+
+lazy_static! {
+    static ref RELEASE_HEADING_RE: Regex = Regex::new(
+        r"^##\s+\[?(?P<version>[^\]\s]+)\]?(?:\s+-\s+(?P<date>\d{4}-\d{2}-\d{2}))?"
+    ).unwrap();
+    static ref CATEGORY_HEADING_RE: Regex = Regex::new(r"^###\s+(?P<name>.+?)\s*$").unwrap();
+    static ref ENTRY_RE: Regex = Regex::new(r"^[-*]\s+(?P<entry>.*)$").unwrap();
+    static ref LINK_RE: Regex = Regex::new(r"^\[(?P<label>[^\]]+)\]:\s*(?P<url>\S+)\s*$").unwrap();
+    // https://github.com/doctavious/cli/compare/v0.1.0...HEAD
+    static ref COMPARE_URL_RE: Regex =
+        Regex::new(r"^(?P<base>.+/compare/)(?P<from>.+)\.\.\.(?P<to>.+)$").unwrap();
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct KeepAChangelog {
+    /// Content before the first release, ex: the title and description
+    pub header: String,
+    /// Releases, most recent first
+    pub releases: Vec<ReleaseSection>,
+    /// Link reference definitions at the bottom of the changelog
+    pub links: Vec<Link>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ReleaseSection {
+    /// Version from the heading, ex: 1.0.0 or Unreleased
+    pub version: String,
+    pub date: Option<String>,
+    pub categories: Vec<Category>,
+    /// Content of the release, including its heading, as it appears in the changelog
+    pub content: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Category {
+    pub name: String,
+    pub entries: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Link {
+    pub label: String,
+    pub url: String,
+}
+
+impl ReleaseSection {
+    fn new(version: &str, date: Option<String>, content: String) -> Self {
+        let mut categories: Vec<Category> = vec![];
+        for line in content.lines().skip(1) {
+            if let Some(captures) = CATEGORY_HEADING_RE.captures(line) {
+                categories.push(Category {
+                    name: captures["name"].to_string(),
+                    entries: vec![],
+                });
+            } else if let Some(category) = categories.last_mut() {
+                if let Some(captures) = ENTRY_RE.captures(line) {
+                    category.entries.push(captures["entry"].trim_end().to_string());
+                } else if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+                    // continuation of a multi-line entry
+                    if let Some(entry) = category.entries.last_mut() {
+                        entry.push('\n');
+                        entry.push_str(line.trim_end());
+                    }
+                }
+            }
+        }
+
+        Self {
+            version: version.to_string(),
+            date,
+            categories,
+            content,
+        }
+    }
+
+    pub fn is_unreleased(&self) -> bool {
+        self.version.eq_ignore_ascii_case(UNRELEASED)
+    }
+
+    fn key(&self) -> String {
+        self.version.trim_start_matches('v').to_lowercase()
+    }
+}
+
+impl KeepAChangelog {
+    pub fn parse(content: &str) -> Self {
+        let mut changelog = KeepAChangelog::default();
+        let mut current: Option<(String, Option<String>, String)> = None;
+        for line in content.lines() {
+            if let Some(captures) = RELEASE_HEADING_RE.captures(line) {
+                if let Some((version, date, content)) = current.take() {
+                    changelog.releases.push(ReleaseSection::new(&version, date, content));
+                }
+                current = Some((
+                    captures["version"].to_string(),
+                    captures.name("date").map(|d| d.as_str().to_string()),
+                    format!("{}\n", line),
+                ));
+                continue;
+            }
+
+            match current.as_mut() {
+                Some((_, _, content)) => {
+                    if let Some(captures) = LINK_RE.captures(line) {
+                        changelog.links.push(Link {
+                            label: captures["label"].to_string(),
+                            url: captures["url"].to_string(),
+                        });
+                    } else {
+                        content.push_str(line);
+                        content.push('\n');
+                    }
+                }
+                None => {
+                    changelog.header.push_str(line);
+                    changelog.header.push('\n');
+                }
+            }
+        }
+
+        if let Some((version, date, content)) = current.take() {
+            changelog.releases.push(ReleaseSection::new(&version, date, content));
+        }
+
+        changelog
+    }
+
+    pub fn get_release(&self, version: &str) -> Option<&ReleaseSection> {
+        let key = version.trim_start_matches('v').to_lowercase();
+        self.releases.iter().find(|r| r.key() == key)
+    }
+
+    /// Merges generated releases into the changelog so that merging the same releases again
+    /// doesn't change it.
+    ///
+    /// Releases whose categories changed are replaced, new releases are added above the existing
+    /// ones and the unreleased section is replaced, or removed when the generated changelog has
+    /// released it. The existing header and links are kept.
+    pub fn merge(mut self, generated: KeepAChangelog) -> Self {
+        if self.header.trim().is_empty() {
+            self.header = generated.header;
+        }
+
+        let has_new_releases = generated
+            .releases
+            .iter()
+            .any(|r| !r.is_unreleased() && self.get_release(&r.version).is_none());
+        let generated_unreleased = generated.releases.iter().find(|r| r.is_unreleased()).cloned();
+
+        let mut new_releases = vec![];
+        for release in generated.releases.into_iter().filter(|r| !r.is_unreleased()) {
+            match self.releases.iter_mut().find(|r| r.key() == release.key()) {
+                Some(existing) => {
+                    if existing.categories != release.categories {
+                        *existing = release;
+                    }
+                }
+                None => new_releases.push(release),
+            }
+        }
+
+        let existing_unreleased = self.releases.iter().position(|r| r.is_unreleased());
+        let unreleased = match (existing_unreleased, generated_unreleased) {
+            (Some(index), Some(generated)) => {
+                let existing = self.releases.remove(index);
+                Some(if existing.categories == generated.categories { existing } else { generated })
+            }
+            (Some(index), None) => {
+                let existing = self.releases.remove(index);
+                if has_new_releases {
+                    None
+                } else {
+                    Some(existing)
+                }
+            }
+            (None, generated) => generated,
+        };
+
+        let mut releases: Vec<ReleaseSection> = unreleased.into_iter().collect();
+        releases.extend(new_releases);
+        releases.append(&mut self.releases);
+        self.releases = releases;
+        self
+    }
+
+    /// Moves the unreleased section under a new heading for the version, adding an empty
+    /// unreleased section above it, and updates the compare links.
+    pub fn release(&mut self, version: &str, date: &str) -> Result<()> {
+        let version = version.trim_start_matches('v');
+        if self.get_release(version).is_some() {
+            return Err(DoctaviousError::ChangelogError(format!(
+                "changelog already contains release {}",
+                version
+            )));
+        }
+
+        let index = self.releases.iter().position(|r| r.is_unreleased()).ok_or_else(|| {
+            DoctaviousError::ChangelogError(String::from("changelog has no unreleased section"))
+        })?;
+
+        let unreleased = &self.releases[index];
+        let heading = format!("## [{}] - {}", version, date);
+        let content = match unreleased.content.split_once('\n') {
+            Some((_, body)) => format!("{}\n{}", heading, body),
+            None => format!("{}\n", heading),
+        };
+        let released = ReleaseSection::new(version, Some(date.to_string()), content);
+        self.releases[index] = released;
+        self.releases.insert(
+            index,
+            ReleaseSection::new(UNRELEASED, None, format!("## [{}]\n", UNRELEASED)),
+        );
+
+        self.update_compare_links(version);
+        Ok(())
+    }
+
+    fn update_compare_links(&mut self, version: &str) {
+        let index = match self.links.iter().position(|l| l.label.eq_ignore_ascii_case(UNRELEASED)) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let captures = match COMPARE_URL_RE.captures(&self.links[index].url) {
+            Some(captures) => captures,
+            None => return,
+        };
+
+        // tags share the prefix of the previous release's tag, ex: v
+        let from = captures["from"].to_string();
+        let prefix = from.find(|c: char| c.is_ascii_digit()).map(|i| &from[..i]).unwrap_or("");
+        let tag = format!("{}{}", prefix, version);
+        let base = captures["base"].to_string();
+        let to = captures["to"].to_string();
+
+        self.links[index].url = format!("{}{}...{}", base, tag, to);
+        self.links.insert(
+            index + 1,
+            Link {
+                label: version.to_string(),
+                url: format!("{}{}...{}", base, from, tag),
+            },
+        );
+    }
+}
+
+impl Display for KeepAChangelog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut sections = vec![];
+        if !self.header.trim().is_empty() {
+            sections.push(self.header.trim_end().to_string());
+        }
+        for release in &self.releases {
+            sections.push(release.content.trim_end().to_string());
+        }
+        if !self.links.is_empty() {
+            sections.push(
+                self.links
+                    .iter()
+                    .map(|l| format!("[{}]: {}", l.label, l.url))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            );
+        }
+
+        writeln!(f, "{}", sections.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::changelog::keep_a_changelog::{Category, KeepAChangelog, Link};
+
+    const CHANGELOG: &str = "# Changelog
+All notable changes to this project will be documented in this file.
+
+## [Unreleased]
+### Added
+- Bump command
+
+## [0.2.0] - 2020-09-13
+### Added
+- RFD command
+  with templates
+
+### Fixed
+- Handle missing adr dir
+
+## [0.1.0] - 2020-09-01
+### Added
+- ADR command
+
+[unreleased]: https://github.com/doctavious/cli/compare/v0.2.0...HEAD
+[0.2.0]: https://github.com/doctavious/cli/compare/v0.1.0...v0.2.0
+";
+
+    #[test]
+    fn should_parse_changelog() {
+        let changelog = KeepAChangelog::parse(CHANGELOG);
+        assert!(changelog.header.starts_with("# Changelog\nAll notable"));
+        assert_eq!(
+            vec!["Unreleased", "0.2.0", "0.1.0"],
+            changelog.releases.iter().map(|r| r.version.as_str()).collect::<Vec<&str>>()
+        );
+        let release = changelog.get_release("v0.2.0").unwrap();
+        assert_eq!(Some(String::from("2020-09-13")), release.date);
+        assert_eq!(
+            vec![
+                Category {
+                    name: String::from("Added"),
+                    entries: vec![String::from("RFD command\n  with templates")],
+                },
+                Category {
+                    name: String::from("Fixed"),
+                    entries: vec![String::from("Handle missing adr dir")],
+                },
+            ],
+            release.categories
+        );
+        assert_eq!(2, changelog.links.len());
+        assert_eq!(CHANGELOG, changelog.to_string());
+    }
+
+    #[test]
+    fn should_merge_generated_releases_idempotently() {
+        let generated = KeepAChangelog::parse(
+            "# Changelog\n\n\
+             ## [0.3.0] - 2020-09-20\n### Added\n- Bump command\n\n\
+             ## [0.2.0] - 2020-09-13\n### Added\n- RFD command\n  with templates\n\n\
+             ### Fixed\n- Handle missing adr dir\n\n\
+             ## [0.1.0] - 2020-09-01\n### Added\n- ADR command\n- Init command\n",
+        );
+
+        let merged = KeepAChangelog::parse(CHANGELOG).merge(generated.clone());
+        assert_eq!(
+            vec!["0.3.0", "0.2.0", "0.1.0"],
+            merged.releases.iter().map(|r| r.version.as_str()).collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            vec![String::from("ADR command"), String::from("Init command")],
+            merged.get_release("0.1.0").unwrap().categories[0].entries
+        );
+        assert!(merged.header.contains("All notable changes"));
+
+        let content = merged.to_string();
+        assert_eq!(content, KeepAChangelog::parse(&content).merge(generated).to_string());
+    }
+
+    #[test]
+    fn should_promote_unreleased() {
+        let mut changelog = KeepAChangelog::parse(CHANGELOG);
+        changelog.release("v0.3.0", "2020-09-20").unwrap();
+
+        assert_eq!(
+            vec!["Unreleased", "0.3.0", "0.2.0", "0.1.0"],
+            changelog.releases.iter().map(|r| r.version.as_str()).collect::<Vec<&str>>()
+        );
+        let release = changelog.get_release("0.3.0").unwrap();
+        assert_eq!("## [0.3.0] - 2020-09-20\n### Added\n- Bump command\n\n", release.content);
+        assert!(changelog.releases[0].categories.is_empty());
+        assert_eq!(
+            vec![
+                Link {
+                    label: String::from("unreleased"),
+                    url: String::from("https://github.com/doctavious/cli/compare/v0.3.0...HEAD"),
+                },
+                Link {
+                    label: String::from("0.3.0"),
+                    url: String::from("https://github.com/doctavious/cli/compare/v0.2.0...v0.3.0"),
+                },
+                Link {
+                    label: String::from("0.2.0"),
+                    url: String::from("https://github.com/doctavious/cli/compare/v0.1.0...v0.2.0"),
+                },
+            ],
+            changelog.links
+        );
+
+        assert!(changelog.release("0.3.0", "2020-09-21").is_err());
+    }
+}
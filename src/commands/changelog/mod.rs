@@ -12,8 +12,10 @@ use crate::doctavious_error::EnumError;
 use crate::DoctaviousResult;
 use crate::utils::parse_enum;
 
-mod changelog;
-mod commit;
+pub(crate) mod changelog;
+pub(crate) mod commit;
+mod context;
+mod keep_a_changelog;
 mod release;
 
 lazy_static! {
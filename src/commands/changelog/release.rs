@@ -1,4 +1,4 @@
-use crate::commands::changelog::commit::Commit;
+use crate::commands::changelog::commit::{Commit, Contributor};
 
 /// Representation of a release.
 #[derive(
@@ -22,4 +22,26 @@ pub struct Release<'a> {
     pub timestamp: i64,
     /// Previous release.
     pub previous: Option<Box<Release<'a>>>,
+    /// Authors and co-authors of the commits, in order of their first commit.
+    #[serde(default)]
+    pub contributors: Vec<Contributor>,
+}
+
+impl Release<'_> {
+    /// Unique authors and co-authors of the release's commits. Contributors are considered the
+    /// same when they share an email, ignoring case, or when neither has an email their name.
+    pub fn get_contributors(&self) -> Vec<Contributor> {
+        let mut contributors: Vec<Contributor> = vec![];
+        for contributor in self.commits.iter().flat_map(|c| c.contributors()) {
+            let exists = contributors.iter().any(|c| match (&c.email, &contributor.email) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (None, None) => c.name == contributor.name,
+                _ => false,
+            });
+            if !exists {
+                contributors.push(contributor);
+            }
+        }
+        contributors
+    }
 }
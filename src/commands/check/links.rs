@@ -0,0 +1,378 @@
+// Checks that relative links, image sources and fragments resolve to files and anchors that exist.
+// Works against built HTML output as well as Markdown and AsciiDoc sources.
+// External URLs are skipped unless they are mapped onto a local directory, for example
+// https://docs.example.com/ onto the build output, in which case they are checked like any other
+// absolute link.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::commands::check::references::{parse_document, DocumentFormat};
+use crate::doctavious_error::Result as DoctaviousResult;
+
+lazy_static! {
+    static ref SCHEME_RE: Regex = Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:").unwrap();
+}
+
+/// Directories which are never checked
+const IGNORED_DIRS: &[&str] = &[".doctavious", ".git", "node_modules"];
+
+/// Files, in order of preference, that a link to a directory resolves to
+const INDEX_FILES: &[&str] = &["index.html", "index.md", "README.md", "_index.md", "index.adoc"];
+
+/// Extensions tried for links without an extension, ex: clean URLs such as /guides/install
+const IMPLICIT_EXTENSIONS: &[&str] = &["html", "md", "adoc"];
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct LinkCheckReport {
+    /// Number of documents checked
+    pub files: usize,
+    /// Number of links checked, excluding skipped links
+    pub links: usize,
+    /// Number of external and allowlisted links which were skipped
+    pub skipped: usize,
+    pub broken: Vec<BrokenLink>,
+}
+
+impl LinkCheckReport {
+    /// Adds the results of checking another root
+    pub fn merge(&mut self, other: LinkCheckReport) {
+        self.files += other.files;
+        self.links += other.links;
+        self.skipped += other.skipped;
+        self.broken.extend(other.broken);
+    }
+}
+
+impl Display for LinkCheckReport {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for broken in &self.broken {
+            writeln!(f, "{}:{}: {} ({})", broken.file, broken.line, broken.target, broken.reason)?;
+        }
+        write!(
+            f,
+            "checked {} links in {} files. {} broken, {} skipped",
+            self.links, self.files, self.broken.len(), self.skipped
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct BrokenLink {
+    pub file: String,
+    pub line: usize,
+    pub target: String,
+    pub reason: BrokenLinkReason,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BrokenLinkReason {
+    MissingFile,
+    MissingAnchor,
+}
+
+impl Display for BrokenLinkReason {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            BrokenLinkReason::MissingFile => write!(f, "file not found"),
+            BrokenLinkReason::MissingAnchor => write!(f, "anchor not found"),
+        }
+    }
+}
+
+enum Outcome {
+    Valid,
+    Skipped,
+    Broken(BrokenLinkReason),
+}
+
+pub(crate) struct LinkChecker {
+    root: PathBuf,
+    allowlist: Vec<Regex>,
+    /// URL prefixes and the local directory they map onto
+    url_mappings: Vec<(String, PathBuf)>,
+    /// Anchors of documents keyed by their normalized path. Parsed lazily as targets are resolved
+    anchors: HashMap<PathBuf, Vec<String>>,
+}
+
+impl LinkChecker {
+    pub fn new(root: PathBuf, allowlist: Vec<Regex>, url_mappings: Vec<(String, PathBuf)>) -> Self {
+        Self { root, allowlist, url_mappings, anchors: HashMap::new() }
+    }
+
+    /// Checks every HTML, Markdown and AsciiDoc document within the root
+    pub fn check(&mut self) -> DoctaviousResult<LinkCheckReport> {
+        let mut report = LinkCheckReport::default();
+        let walker = WalkDir::new(&self.root)
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_ignored(e.file_name().to_str()));
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let format = match DocumentFormat::from_path(entry.path()) {
+                Some(format) => format,
+                None => continue,
+            };
+
+            let content = fs::read_to_string(entry.path())?;
+            let document = parse_document(format, &content);
+            self.anchors.insert(normalize(entry.path()), document.anchors.into_iter().collect());
+            report.files += 1;
+
+            for reference in document.references {
+                match self.check_reference(entry.path(), &reference.target) {
+                    Outcome::Valid => report.links += 1,
+                    Outcome::Skipped => report.skipped += 1,
+                    Outcome::Broken(reason) => {
+                        report.links += 1;
+                        report.broken.push(BrokenLink {
+                            file: entry.path().to_string_lossy().to_string(),
+                            line: reference.line,
+                            target: reference.target,
+                            reason,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn check_reference(&mut self, file: &Path, target: &str) -> Outcome {
+        let target = target.trim();
+        if target.is_empty() || self.allowlist.iter().any(|a| a.is_match(target)) {
+            return Outcome::Skipped;
+        }
+
+        // external links are resolved against the local directory they are mapped to, if any,
+        // as though they were absolute links
+        let (base, target) = if SCHEME_RE.is_match(target) || target.starts_with("//") {
+            // the most specific mapping wins, ex: https://docs.example.com/api/ over
+            // https://docs.example.com/
+            let mapping = self
+                .url_mappings
+                .iter()
+                .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+                .max_by_key(|(prefix, _)| prefix.len());
+            match mapping {
+                Some((prefix, dir)) => (dir.to_path_buf(), format!("/{}", &target[prefix.len()..])),
+                None => return Outcome::Skipped,
+            }
+        } else {
+            (self.root.to_path_buf(), target.to_string())
+        };
+
+        let (path, fragment) = match target.split_once('#') {
+            Some((path, fragment)) => (path, Some(decode(fragment))),
+            None => (target.as_str(), None),
+        };
+        let path = decode(path.split('?').next().unwrap_or_default());
+
+        let resolved = if path.is_empty() {
+            Some(file.to_path_buf())
+        } else if let Some(absolute) = path.strip_prefix('/') {
+            resolve(&base.join(absolute))
+        } else {
+            resolve(&file.parent().unwrap_or(&self.root).join(&path))
+        };
+
+        let resolved = match resolved {
+            Some(resolved) => resolved,
+            None => return Outcome::Broken(BrokenLinkReason::MissingFile),
+        };
+
+        match fragment {
+            Some(fragment) if !fragment.is_empty() && fragment != "top" => {
+                match self.get_anchors(&resolved) {
+                    Some(anchors) if !anchors.contains(&fragment) => {
+                        Outcome::Broken(BrokenLinkReason::MissingAnchor)
+                    }
+                    _ => Outcome::Valid,
+                }
+            }
+            _ => Outcome::Valid,
+        }
+    }
+
+    // anchors of documents outside of the root, or not yet checked, are parsed on demand.
+    // Returns None for files we can't determine anchors for, such as images and PDFs
+    fn get_anchors(&mut self, path: &Path) -> Option<&Vec<String>> {
+        let path = normalize(path);
+        if !self.anchors.contains_key(&path) {
+            let format = DocumentFormat::from_path(&path)?;
+            let content = fs::read_to_string(&path).ok()?;
+            let document = parse_document(format, &content);
+            self.anchors.insert(path.to_path_buf(), document.anchors.into_iter().collect());
+        }
+        self.anchors.get(&path)
+    }
+}
+
+/// Resolves a path to the file it refers to, including directory index files and paths without
+/// an extension. Directories without an index file are also considered valid targets
+fn resolve(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    if path.is_dir() {
+        let index = INDEX_FILES.iter().map(|i| path.join(i)).find(|i| i.is_file());
+        return Some(index.unwrap_or_else(|| path.to_path_buf()));
+    }
+
+    IMPLICIT_EXTENSIONS.iter()
+        .map(|extension| {
+            let mut file = path.as_os_str().to_os_string();
+            file.push(".");
+            file.push(extension);
+            PathBuf::from(file)
+        })
+        .find(|file| file.is_file())
+}
+
+/// Lexically removes `.` and `..` components so that paths can be compared
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Decodes percent-encoded characters, ex: %20
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let byte = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = byte {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn is_ignored(file_name: Option<&str>) -> bool {
+    file_name.map(|n| IGNORED_DIRS.contains(&n)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use regex::Regex;
+    use tempfile::TempDir;
+    use crate::commands::check::links::{BrokenLinkReason, LinkChecker};
+
+    #[test]
+    fn should_report_broken_links_in_markdown() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("guides")).unwrap();
+        fs::create_dir_all(root.join("images")).unwrap();
+        fs::write(root.join("images/logo.png"), "").unwrap();
+        fs::write(root.join("guides/install.md"), "# Install\n\n## Linux\n\n[Home](../README.md)\n").unwrap();
+        fs::write(root.join("README.md"), "# Docs\n\n\
+            [Install](guides/install.md#linux)\n\
+            [Windows](guides/install.md#windows)\n\
+            [Missing](guides/upgrade.md)\n\
+            ![Logo](/images/logo.png)\n\
+            [Section](#docs)\n\
+            [Example](https://example.com)\n\
+            [Changelog](CHANGELOG.md)\n").unwrap();
+
+        let allowlist = vec![Regex::new("^CHANGELOG").unwrap()];
+        let mut checker = LinkChecker::new(root.to_path_buf(), allowlist, vec![]);
+        let report = checker.check().unwrap();
+
+        assert_eq!(report.files, 2);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.links, 6);
+        assert_eq!(report.broken.len(), 2);
+        assert_eq!(report.broken[0].target, "guides/install.md#windows");
+        assert_eq!(report.broken[0].line, 4);
+        assert_eq!(report.broken[0].reason, BrokenLinkReason::MissingAnchor);
+        assert_eq!(report.broken[1].target, "guides/upgrade.md");
+        assert_eq!(report.broken[1].reason, BrokenLinkReason::MissingFile);
+    }
+
+    #[test]
+    fn should_check_html_output_with_clean_urls_and_url_mappings() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("guides")).unwrap();
+        fs::write(root.join("guides/index.html"), "<h1 id=\"guides\">Guides</h1>").unwrap();
+        fs::write(root.join("about.html"), "<h1 id=\"about\">About</h1>").unwrap();
+        fs::write(root.join("index.html"), "<html><body>\n\
+            <a href=\"/guides/#guides\">Guides</a>\n\
+            <a href=\"about\">About</a>\n\
+            <a href=\"https://docs.example.com/about#team\">Team</a>\n\
+            <a href=\"https://docs.example.com/pricing\">Pricing</a>\n\
+            <a href=\"mailto:docs@example.com\">Email</a>\n\
+            </body></html>").unwrap();
+
+        let url_mappings = vec![(String::from("https://docs.example.com/"), root.to_path_buf())];
+        let mut checker = LinkChecker::new(root.to_path_buf(), vec![], url_mappings);
+        let report = checker.check().unwrap();
+
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.broken.len(), 2);
+        assert_eq!(report.broken[0].target, "https://docs.example.com/about#team");
+        assert_eq!(report.broken[0].reason, BrokenLinkReason::MissingAnchor);
+        assert_eq!(report.broken[1].target, "https://docs.example.com/pricing");
+        assert_eq!(report.broken[1].line, 5);
+    }
+
+    #[test]
+    fn should_use_longest_matching_url_mapping() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(root.join("api/client.html"), "<h1>Client</h1>").unwrap();
+        fs::write(
+            root.join("docs/index.html"),
+            "<a href=\"https://docs.example.com/api/client\">Client</a>",
+        )
+        .unwrap();
+
+        let url_mappings = vec![
+            (String::from("https://docs.example.com/"), root.join("docs")),
+            (String::from("https://docs.example.com/api/"), root.join("api")),
+        ];
+        let mut checker = LinkChecker::new(root.join("docs"), vec![], url_mappings);
+        let report = checker.check().unwrap();
+
+        assert_eq!(report.links, 1);
+        assert!(report.broken.is_empty());
+    }
+}
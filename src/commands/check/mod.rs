@@ -0,0 +1,200 @@
+mod links;
+mod references;
+
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use regex::Regex;
+
+use crate::commands::build::staging::read_manifest;
+use crate::commands::check::links::{LinkCheckReport, LinkChecker};
+use crate::constants::DEFAULT_BUILD_OUTPUT_DIR;
+use crate::doctavious_error::{DoctaviousError, Result as DoctaviousResult};
+use crate::output::{get_output, print_output, Output};
+use crate::settings::{LinkCheckSettings, SETTINGS};
+
+#[derive(Parser, Debug)]
+#[command(about = "Check documentation for problems")]
+pub(crate) struct CheckCommand {
+    #[command(subcommand)]
+    pub check_command: CheckSubCommand,
+}
+
+#[derive(Parser, Debug)]
+pub(crate) enum CheckSubCommand {
+    Links(CheckLinks),
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Check for broken relative links, image sources and anchors")]
+pub(crate) struct CheckLinks {
+    #[arg(
+        help = "Directory of built HTML output or Markdown and AsciiDoc sources. Defaults to the \
+                build output when present otherwise the current directory"
+    )]
+    pub dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Regex of link targets to skip. Can be provided multiple times"
+    )]
+    pub allow: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PREFIX=DIR",
+        value_parser = parse_url_mapping,
+        help = "Check external URLs starting with the prefix against files within the local \
+                directory, ex: https://docs.example.com/=.doctavious/output/docs"
+    )]
+    pub map_url: Vec<(String, String)>,
+}
+
+pub(crate) fn handle_check_command(
+    command: CheckCommand,
+    output: Option<Output>,
+) -> DoctaviousResult<()> {
+    match command.check_command {
+        CheckSubCommand::Links(params) => {
+            let settings = SETTINGS.check_settings.as_ref().and_then(|s| s.links.as_ref());
+            let allowlist = get_allowlist(settings, &params.allow)?;
+            let url_mappings = get_url_mappings(settings, params.map_url);
+            let mut report = LinkCheckReport::default();
+            for root in get_roots(params.dir, Path::new(DEFAULT_BUILD_OUTPUT_DIR)) {
+                let mut checker = LinkChecker::new(root, allowlist.clone(), url_mappings.clone());
+                report.merge(checker.check()?);
+            }
+            let broken = report.broken.len();
+            print_output(get_output(output), report)?;
+
+            if broken > 0 {
+                return Err(DoctaviousError::Msg(format!("found {} broken links", broken)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Directories to check, each of which absolute links are resolved against. Without a directory
+/// each project in the build output is checked on its own as that is the root it is served from
+fn get_roots(dir: Option<PathBuf>, build_output: &Path) -> Vec<PathBuf> {
+    if let Some(dir) = dir {
+        return vec![dir];
+    }
+
+    if !build_output.is_dir() {
+        return vec![PathBuf::from(".")];
+    }
+
+    let projects: Vec<PathBuf> = read_manifest(build_output)
+        .map(|manifest| {
+            manifest
+                .builds
+                .iter()
+                .map(|build| build_output.join(&build.project))
+                .filter(|dir| dir.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if projects.is_empty() {
+        vec![build_output.to_path_buf()]
+    } else {
+        projects
+    }
+}
+
+fn parse_url_mapping(value: &str) -> Result<(String, String), String> {
+    match value.rsplit_once('=') {
+        Some((prefix, dir)) if !prefix.is_empty() && !dir.is_empty() => {
+            Ok((prefix.to_string(), dir.to_string()))
+        }
+        _ => Err(format!("invalid URL mapping {}. Expected PREFIX=DIR", value)),
+    }
+}
+
+/// Allowlist from settings along with those provided as arguments
+fn get_allowlist(
+    settings: Option<&LinkCheckSettings>,
+    allow: &[String],
+) -> DoctaviousResult<Vec<Regex>> {
+    let mut allowlist = vec![];
+    let configured = settings.and_then(|s| s.allowlist.as_ref()).into_iter().flatten();
+    for pattern in configured.chain(allow.iter()) {
+        let regex = Regex::new(pattern).map_err(|e| {
+            DoctaviousError::Msg(format!("invalid allowlist pattern {}: {}", pattern, e))
+        })?;
+        allowlist.push(regex);
+    }
+    Ok(allowlist)
+}
+
+/// URL mappings from settings along with those provided as arguments, which take precedence
+fn get_url_mappings(
+    settings: Option<&LinkCheckSettings>,
+    map_url: Vec<(String, String)>,
+) -> Vec<(String, PathBuf)> {
+    let configured = settings
+        .and_then(|s| s.url_mappings.clone())
+        .unwrap_or_default();
+    map_url
+        .into_iter()
+        .chain(configured.into_iter())
+        .map(|(prefix, dir)| (prefix, PathBuf::from(dir)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use crate::commands::build::context::BuildContext;
+    use crate::commands::build::staging::{write_manifest, BuildResult, BuildsManifest};
+    use crate::commands::check::links::LinkChecker;
+    use crate::commands::check::{get_roots, parse_url_mapping};
+
+    #[test]
+    fn should_check_each_project_of_build_output() {
+        let dir = TempDir::new().unwrap();
+        let build_output = dir.path().join(".doctavious/output");
+        let project = build_output.join("docs");
+        fs::create_dir_all(project.join("guide")).unwrap();
+        fs::write(project.join("guide/intro.html"), "<h1 id=\"intro\">Intro</h1>").unwrap();
+        fs::write(
+            project.join("index.html"),
+            "<a href=\"/guide/intro#intro\">Intro</a>\n<a href=\"/guide/setup\">Setup</a>",
+        )
+        .unwrap();
+
+        let mut manifest = BuildsManifest::new(BuildContext::Production);
+        manifest.builds.push(BuildResult {
+            project: String::from("docs"),
+            success: true,
+            ..Default::default()
+        });
+        write_manifest(&build_output, &manifest).unwrap();
+
+        let roots = get_roots(None, &build_output);
+        assert_eq!(vec![project.clone()], roots);
+        assert_eq!(vec![PathBuf::from("site")], get_roots(Some(PathBuf::from("site")), &build_output));
+
+        let report = LinkChecker::new(roots[0].clone(), vec![], vec![]).check().unwrap();
+        assert_eq!(report.links, 2);
+        assert_eq!(report.broken.len(), 1);
+        assert_eq!(report.broken[0].target, "/guide/setup");
+    }
+
+    #[test]
+    fn should_parse_url_mapping() {
+        assert_eq!(
+            parse_url_mapping("https://docs.example.com/=public"),
+            Ok((String::from("https://docs.example.com/"), String::from("public")))
+        );
+        assert!(parse_url_mapping("https://docs.example.com/").is_err());
+    }
+}
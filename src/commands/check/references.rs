@@ -0,0 +1,363 @@
+// Extracts references (links and image sources) and anchors from documents.
+// HTML and AsciiDoc are scanned with regular expressions which is good enough for link checking
+// and avoids pulling in full parsers. Markdown is parsed with pulldown-cmark so that reference
+// style links and code blocks are handled correctly.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+
+lazy_static! {
+    static ref HTML_COMMENT_RE: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    static ref HTML_TAG_RE: Regex = Regex::new(r"(?i)<([a-z][a-z0-9-]*)\b([^>]*)>").unwrap();
+    static ref HTML_ATTRIBUTE_RE: Regex = Regex::new(
+        r#"(?i)(?:^|\s)(href|src|id|name)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#
+    ).unwrap();
+
+    static ref ASCIIDOC_HEADING_RE: Regex = Regex::new(r"^(={1,6})\s+(\S.*)$").unwrap();
+    static ref ASCIIDOC_ANCHOR_RE: Regex = Regex::new(
+        r"\[\[([A-Za-z_][\w:.-]*)(?:,[^\]]*)?\]\]|\[#([A-Za-z_][\w:.-]*)[^\]]*\]|anchor:([A-Za-z_][\w:.-]*)\["
+    ).unwrap();
+    static ref ASCIIDOC_MACRO_RE: Regex = Regex::new(
+        r"\b(link|xref|image)::?([^\[\s]+)\["
+    ).unwrap();
+    static ref ASCIIDOC_CROSS_REFERENCE_RE: Regex = Regex::new(r"<<([^,>\s]+)(?:,[^>]*)?>>").unwrap();
+}
+
+/// Link or image source found within a document
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Reference {
+    pub target: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Document {
+    pub references: Vec<Reference>,
+    /// Ids, including generated heading ids, that fragments can refer to
+    pub anchors: HashSet<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum DocumentFormat {
+    AsciiDoc,
+    Html,
+    Markdown,
+}
+
+impl DocumentFormat {
+    pub(crate) fn from_path(path: &Path) -> Option<DocumentFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "adoc" | "asciidoc" | "asc" => Some(DocumentFormat::AsciiDoc),
+            "html" | "htm" => Some(DocumentFormat::Html),
+            "md" | "markdown" | "mdx" => Some(DocumentFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn parse_document(format: DocumentFormat, content: &str) -> Document {
+    match format {
+        DocumentFormat::AsciiDoc => parse_asciidoc(content),
+        DocumentFormat::Html => parse_html(content),
+        DocumentFormat::Markdown => parse_markdown(content),
+    }
+}
+
+fn parse_html(content: &str) -> Document {
+    let mut document = Document::default();
+    parse_html_into(content, 0, &mut document);
+    document
+}
+
+// `line_offset` is the number of lines preceding the HTML which allows HTML embedded within
+// Markdown to be parsed
+fn parse_html_into(content: &str, line_offset: usize, document: &mut Document) {
+    // blank out comments, keeping new lines, so that commented out links are ignored
+    let content = HTML_COMMENT_RE.replace_all(content, |c: &regex::Captures| {
+        c[0].chars().map(|ch| if ch == '\n' { '\n' } else { ' ' }).collect::<String>()
+    });
+    let lines = LineIndex::new(&content);
+
+    for tag in HTML_TAG_RE.captures_iter(&content) {
+        let name = tag[1].to_lowercase();
+        let attributes = tag.get(2).unwrap();
+        for attribute in HTML_ATTRIBUTE_RE.captures_iter(attributes.as_str()) {
+            let value = attribute.get(2)
+                .or_else(|| attribute.get(3))
+                .or_else(|| attribute.get(4))
+                .map(|v| v.as_str().replace("&amp;", "&"))
+                .unwrap_or_default();
+            match attribute[1].to_lowercase().as_str() {
+                "id" => {
+                    document.anchors.insert(value);
+                }
+                "name" if name == "a" => {
+                    document.anchors.insert(value);
+                }
+                "href" | "src" if !value.is_empty() => {
+                    let offset = attributes.start() + attribute.get(0).unwrap().start();
+                    document.references.push(Reference {
+                        target: value,
+                        line: line_offset + lines.line(offset),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn parse_markdown(content: &str) -> Document {
+    let mut document = Document::default();
+    let lines = LineIndex::new(content);
+    let mut headings = HeadingIds::new('-', 1);
+    let mut heading: Option<(Option<String>, String)> = None;
+
+    let parser = Parser::new_ext(content, Options::all());
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading(_, id, _)) => {
+                heading = Some((id.map(|id| id.to_string()), String::new()));
+            }
+            Event::End(Tag::Heading(..)) => {
+                if let Some((id, text)) = heading.take() {
+                    let id = id.unwrap_or_else(|| headings.unique(markdown_heading_id(&text)));
+                    document.anchors.insert(id);
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                }
+            }
+            Event::Start(Tag::Link(_, destination, _)) | Event::Start(Tag::Image(_, destination, _)) => {
+                document.references.push(Reference {
+                    target: destination.to_string(),
+                    line: lines.line(range.start),
+                });
+            }
+            Event::Html(html) => {
+                parse_html_into(&html, lines.line(range.start) - 1, &mut document);
+            }
+            _ => {}
+        }
+    }
+
+    document
+}
+
+/// Generates heading ids the same way as GitHub, ex: "Getting Started!" becomes getting-started
+pub(crate) fn markdown_heading_id(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c)
+            } else if c.is_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_asciidoc(content: &str) -> Document {
+    let mut document = Document::default();
+    let mut headings = HeadingIds::new('_', 2);
+    let mut delimiter: Option<&str> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim_end();
+
+        // links within listing, literal and comment blocks aren't rendered
+        if let Some(current) = delimiter {
+            if trimmed == current {
+                delimiter = None;
+            }
+            continue;
+        }
+        if trimmed == "----" || trimmed == "...." || trimmed == "////" {
+            delimiter = Some(trimmed);
+            continue;
+        }
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(heading) = ASCIIDOC_HEADING_RE.captures(trimmed) {
+            document.anchors.insert(headings.unique(asciidoc_heading_id(&heading[2])));
+        }
+
+        for anchor in ASCIIDOC_ANCHOR_RE.captures_iter(trimmed) {
+            if let Some(id) = anchor.get(1).or_else(|| anchor.get(2)).or_else(|| anchor.get(3)) {
+                document.anchors.insert(id.as_str().to_string());
+            }
+        }
+
+        for reference in ASCIIDOC_MACRO_RE.captures_iter(trimmed) {
+            let target = &reference[2];
+            let target = if &reference[1] == "xref" {
+                asciidoc_cross_reference_target(target)
+            } else {
+                target.to_string()
+            };
+            document.references.push(Reference { target, line: line_number });
+        }
+
+        for reference in ASCIIDOC_CROSS_REFERENCE_RE.captures_iter(trimmed) {
+            document.references.push(Reference {
+                target: asciidoc_cross_reference_target(&reference[1]),
+                line: line_number,
+            });
+        }
+    }
+
+    document
+}
+
+// cross references are either to an id within the document or to another document, optionally
+// with a fragment, ex: <<install>>, <<install.adoc#>>, xref:install.adoc#linux[]
+fn asciidoc_cross_reference_target(target: &str) -> String {
+    if target.contains('#') || target.ends_with(".adoc") {
+        target.to_string()
+    } else {
+        format!("#{}", target)
+    }
+}
+
+/// Generates heading ids the same way as Asciidoctor, ex: "Getting Started" becomes _getting_started
+///
+/// Invalid characters are removed while spaces, hyphens and periods are replaced by underscores.
+pub(crate) fn asciidoc_heading_id(text: &str) -> String {
+    let mut id = String::from("_");
+    for c in text.trim().to_lowercase().chars() {
+        if c == ' ' || c == '-' || c == '.' || c == '_' {
+            if !id.ends_with('_') {
+                id.push('_');
+            }
+        } else if c.is_alphanumeric() {
+            id.push(c);
+        }
+    }
+    if id.len() > 1 {
+        id.trim_end_matches('_').to_string()
+    } else {
+        id
+    }
+}
+
+/// Makes heading ids unique by suffixing duplicates with a counter.
+/// GitHub suffixes duplicates starting at 1, ex: example-1, whereas Asciidoctor starts at 2,
+/// ex: _example_2
+struct HeadingIds {
+    seen: HashMap<String, usize>,
+    separator: char,
+    start: usize,
+}
+
+impl HeadingIds {
+    fn new(separator: char, start: usize) -> Self {
+        Self { seen: HashMap::new(), separator, start }
+    }
+
+    fn unique(&mut self, id: String) -> String {
+        let count = self.seen.entry(id.to_string()).or_insert(0);
+        let unique = if *count == 0 {
+            id
+        } else {
+            format!("{}{}{}", id, self.separator, *count + self.start - 1)
+        };
+        *count += 1;
+        unique
+    }
+}
+
+/// Maps byte offsets to 1-based line numbers
+struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self { starts }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        match self.starts.binary_search(&offset) {
+            Ok(line) => line + 1,
+            Err(line) => line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::check::references::{
+        asciidoc_heading_id, markdown_heading_id, parse_document, DocumentFormat, Reference
+    };
+
+    #[test]
+    fn should_parse_html() {
+        let html = "<html>\n<body>\n<h2 id=\"install\">Install</h2>\n<a name=\"legacy\"></a>\n\
+            <a href=\"guide.html#setup\">Guide</a>\n<!-- <a href=\"old.html\">Old</a> -->\n\
+            <img src='images/logo.png'>\n</body>\n</html>";
+        let document = parse_document(DocumentFormat::Html, html);
+
+        assert!(document.anchors.contains("install"));
+        assert!(document.anchors.contains("legacy"));
+        assert_eq!(document.references, vec![
+            Reference { target: String::from("guide.html#setup"), line: 5 },
+            Reference { target: String::from("images/logo.png"), line: 7 },
+        ]);
+    }
+
+    #[test]
+    fn should_parse_markdown() {
+        let markdown = "# Getting Started\n\nSee [install](install.md#linux) and [faq][].\n\n\
+            ## Example\n\n## Example\n\n```\n[not a link](missing.md)\n```\n\n\
+            ![logo](images/logo.png)\n\n[faq]: faq.md\n";
+        let document = parse_document(DocumentFormat::Markdown, markdown);
+
+        assert!(document.anchors.contains("getting-started"));
+        assert!(document.anchors.contains("example"));
+        assert!(document.anchors.contains("example-1"));
+        assert_eq!(document.references, vec![
+            Reference { target: String::from("install.md#linux"), line: 3 },
+            Reference { target: String::from("faq.md"), line: 3 },
+            Reference { target: String::from("images/logo.png"), line: 13 },
+        ]);
+    }
+
+    #[test]
+    fn should_parse_asciidoc() {
+        let asciidoc = "= Guide\n\n== Getting Started\n\n[[custom-id]]\nSee <<install>> and xref:faq.adoc#billing[FAQ].\n\n\
+            ----\nlink:missing.adoc[Missing]\n----\n\nimage::images/logo.png[Logo]\n";
+        let document = parse_document(DocumentFormat::AsciiDoc, asciidoc);
+
+        assert!(document.anchors.contains("_getting_started"));
+        assert!(document.anchors.contains("custom-id"));
+        assert_eq!(document.references, vec![
+            Reference { target: String::from("faq.adoc#billing"), line: 6 },
+            Reference { target: String::from("#install"), line: 6 },
+            Reference { target: String::from("images/logo.png"), line: 12 },
+        ]);
+    }
+
+    #[test]
+    fn should_generate_heading_ids() {
+        assert_eq!(markdown_heading_id("Getting Started!"), "getting-started");
+        assert_eq!(markdown_heading_id("`build` command"), "build-command");
+        assert_eq!(asciidoc_heading_id("Getting Started"), "_getting_started");
+        assert_eq!(asciidoc_heading_id("What's new?"), "_whats_new");
+    }
+}
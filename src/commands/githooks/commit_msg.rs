@@ -0,0 +1,269 @@
+// Built-in commit-msg hook which rejects messages that would silently drop out of the changelog.
+// Configure it as a command of the commit-msg hook
+// [githook.hooks.commit-msg]
+// commands = [{ name = "commit-msg", run = "doctavious githooks builtin commit-msg {1}" }]
+
+use git_conventional::Commit as ConventionalCommit;
+
+use crate::commands::changelog::commit::Commit;
+use crate::commands::changelog::CommitParser;
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::settings::CommitMsgSettings;
+
+const CONVENTIONAL_FORMAT: &str =
+    "expected <type>[(<scope>)][!]: <description>, ex: feat(changelog): add release command";
+
+/// Prefixes of messages generated by git which are allowed as is
+const GENERATED_PREFIXES: [&str; 4] = ["Merge ", "Revert \"", "fixup! ", "squash! "];
+
+/// How the changelog parses commits, from the changelog git settings
+pub(crate) struct CommitMsgRules<'a> {
+    pub settings: &'a CommitMsgSettings,
+    pub conventional_commits: bool,
+    pub commit_parsers: Option<&'a [CommitParser]>,
+    pub filter_commits: bool,
+}
+
+/// Strips the comments and trailing whitespace git removes from the message when committing
+pub(crate) fn clean_message(message: &str) -> String {
+    let lines: Vec<&str> = message
+        .lines()
+        // everything below the scissors line of `git commit --verbose` is removed
+        .take_while(|line| !line.starts_with("# ------------------------ >8"))
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.trim_end())
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Validates the message returning an error which lists each problem found
+pub(crate) fn validate(message: &str, rules: &CommitMsgRules) -> Result<()> {
+    let message = clean_message(message);
+    if message.is_empty() {
+        return Err(DoctaviousError::Msg(String::from("commit message is empty")));
+    }
+
+    let subject = message.lines().next().unwrap_or_default();
+    if GENERATED_PREFIXES.iter().any(|prefix| subject.starts_with(prefix)) {
+        return Ok(());
+    }
+
+    let mut problems = vec![];
+    if rules.conventional_commits {
+        match ConventionalCommit::parse(&message) {
+            Ok(commit) => problems.extend(check_type_and_scope(&commit, rules.settings)),
+            Err(e) => problems.push(format!(
+                "\"{}\" is not a conventional commit: {}. {}",
+                subject, e, CONVENTIONAL_FORMAT
+            )),
+        }
+    }
+
+    if let Some(parsers) = rules.commit_parsers {
+        // without conventional commits the parsers are the only way a commit is categorized
+        if rules.filter_commits || !rules.conventional_commits {
+            let matched = parsers.iter().any(|parser| {
+                [parser.message.as_ref(), parser.body.as_ref()]
+                    .iter()
+                    .flatten()
+                    .any(|regex| regex.is_match(&message))
+            });
+            if !matched {
+                let patterns: Vec<String> = parsers
+                    .iter()
+                    .filter_map(|p| p.message.as_ref().map(|r| r.as_str().to_string()))
+                    .collect();
+                problems.push(format!(
+                    "\"{}\" does not match any of the changelog commit_parsers so it would be left out of the changelog. Messages must match one of: {}",
+                    subject,
+                    patterns.join(", ")
+                ));
+            }
+        }
+    }
+
+    if rules.settings.require_release_note {
+        problems.extend(check_release_note(&message));
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(DoctaviousError::Msg(format!(
+            "invalid commit message:\n{}",
+            problems
+                .iter()
+                .map(|p| format!("  - {}", p))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )))
+    }
+}
+
+fn check_type_and_scope(commit: &ConventionalCommit, settings: &CommitMsgSettings) -> Vec<String> {
+    let mut problems = vec![];
+    let type_ = commit.type_().as_str();
+    if let Some(types) = &settings.types {
+        if !types.iter().any(|t| t == type_) {
+            problems.push(format!(
+                "type \"{}\" is not allowed. Allowed types are {}",
+                type_,
+                types.join(", ")
+            ));
+        }
+    }
+
+    match (commit.scope(), &settings.scopes) {
+        (Some(scope), Some(scopes)) if !scopes.iter().any(|s| s == scope.as_str()) => {
+            problems.push(format!(
+                "scope \"{}\" is not allowed. Allowed scopes are {}",
+                scope.as_str(),
+                scopes.join(", ")
+            ));
+        }
+        (None, scopes) if settings.require_scope => {
+            let allowed = scopes
+                .as_ref()
+                .map(|s| format!(", one of {}", s.join(", ")))
+                .unwrap_or_default();
+            problems.push(format!(
+                "a scope is required, ex: {}(<scope>): {}{}",
+                type_,
+                commit.description(),
+                allowed
+            ));
+        }
+        _ => {}
+    }
+
+    problems
+}
+
+fn check_release_note(message: &str) -> Vec<String> {
+    match Commit::new(String::new(), message.to_string()).into_release_notes() {
+        // Release note: None
+        Err(_) => vec![],
+        Ok(commit) if commit.release_notes.is_empty() => vec![String::from(
+            "a release note is required. Add a \"Release note (<category>): <description>\" trailer, or \"Release note: None\" when the change isn't user facing",
+        )],
+        Ok(commit) => commit
+            .release_notes
+            .iter()
+            .filter(|note| note.text.is_empty())
+            .map(|note| match &note.category {
+                Some(category) => format!("release note ({}) is missing a description", category),
+                None => String::from("release note is missing a description"),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::commands::changelog::CommitParser;
+    use crate::commands::githooks::commit_msg::{clean_message, validate, CommitMsgRules};
+    use crate::settings::CommitMsgSettings;
+
+    fn settings() -> CommitMsgSettings {
+        CommitMsgSettings {
+            types: Some(vec![String::from("feat"), String::from("fix")]),
+            scopes: Some(vec![String::from("changelog"), String::from("githooks")]),
+            require_scope: false,
+            require_release_note: false,
+        }
+    }
+
+    fn error(message: &str, rules: &CommitMsgRules) -> String {
+        validate(message, rules).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn should_validate_conventional_commits() {
+        let allowed = settings();
+        let rules = CommitMsgRules {
+            settings: &allowed,
+            conventional_commits: true,
+            commit_parsers: None,
+            filter_commits: false,
+        };
+
+        assert!(validate("feat(changelog): add release command\n# Please enter the commit message\n", &rules).is_ok());
+        assert!(validate("fix: handle empty tags", &rules).is_ok());
+        assert!(validate("Merge branch 'main' into release", &rules).is_ok());
+
+        assert!(error("add release command", &rules).contains(
+            "\"add release command\" is not a conventional commit"
+        ));
+        assert!(error("feet(changelog): add release command", &rules)
+            .contains("type \"feet\" is not allowed. Allowed types are feat, fix"));
+        assert!(error("feat(bump): add increment", &rules)
+            .contains("scope \"bump\" is not allowed. Allowed scopes are changelog, githooks"));
+        assert_eq!(
+            "commit message is empty",
+            error("# Please enter the commit message\n\n", &rules)
+        );
+
+        let scoped = CommitMsgSettings {
+            require_scope: true,
+            ..settings()
+        };
+        let rules = CommitMsgRules {
+            settings: &scoped,
+            ..rules
+        };
+        assert!(error("fix: handle empty tags", &rules)
+            .contains("a scope is required, ex: fix(<scope>): handle empty tags, one of changelog, githooks"));
+    }
+
+    #[test]
+    fn should_validate_against_commit_parsers() {
+        let settings = CommitMsgSettings::default();
+        let parsers = vec![CommitParser {
+            message: Regex::new("^(Add|Fix)").ok(),
+            body: None,
+            category: Some(String::from("Changes")),
+            skip: None,
+        }];
+        let rules = CommitMsgRules {
+            settings: &settings,
+            conventional_commits: false,
+            commit_parsers: Some(&parsers),
+            filter_commits: false,
+        };
+
+        assert!(validate("Add release command", &rules).is_ok());
+        assert!(error("Update readme", &rules).contains(
+            "\"Update readme\" does not match any of the changelog commit_parsers so it would be left out of the changelog. Messages must match one of: ^(Add|Fix)"
+        ));
+    }
+
+    #[test]
+    fn should_require_release_note() {
+        let settings = CommitMsgSettings {
+            require_release_note: true,
+            ..CommitMsgSettings::default()
+        };
+        let rules = CommitMsgRules {
+            settings: &settings,
+            conventional_commits: true,
+            commit_parsers: None,
+            filter_commits: false,
+        };
+
+        assert!(validate("feat: add bump\n\nRelease note (cli change): add bump command", &rules).is_ok());
+        assert!(validate("chore: update ci\n\nRelease note: None", &rules).is_ok());
+        assert!(error("feat: add bump", &rules).contains("a release note is required"));
+        assert!(error("feat: add bump\n\nRelease note (cli change):", &rules)
+            .contains("release note (cli change) is missing a description"));
+    }
+
+    #[test]
+    fn should_clean_message() {
+        assert_eq!(
+            "fix: tags\n\nbody",
+            clean_message("fix: tags  \n\nbody\n# comment\n# ------------------------ >8 ------------------------\ndiff --git a/b")
+        );
+    }
+}
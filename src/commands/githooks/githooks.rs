@@ -1,12 +1,10 @@
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-
 // idea from rusty-hook and left-hook
 // TODO: flush this out more
 
 // add hook
 // execute hook
 
-const HOOK_NAMES: [&str; 21] = [
+pub(crate) const HOOK_NAMES: [&str; 21] = [
     "applypatch-msg",
     "pre-applypatch",
     "post-applypatch",
@@ -35,15 +33,47 @@ struct GitHooks {
     hooks: Vec<Hook>,
 }
 
-#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+// hooks are configured by name within githook settings, ex: [githook.hooks.pre-commit]
+#[derive(Debug, Clone, Default, serde_derive::Serialize, serde_derive::Deserialize)]
 pub struct Hook {
-    name: String,
-    parallel: bool,
-    piped: bool, // If any command in the sequence fails, the other will not be executed.
-    glob: String,
-    exclude: String, //regex
-    root: String, // execute in a sub directory "api/" # Careful to have only trailing slash
-    commands: Vec<HookCommand>,
+    #[serde(default)]
+    pub parallel: bool,
+    #[serde(default)]
+    pub piped: bool, // If any command in the sequence fails, the other will not be executed.
+    pub glob: Option<String>,
+    pub exclude: Option<String>, //regex
+    pub root: Option<String>, // execute in a sub directory "api/" # Careful to have only trailing slash
+    pub skip: Option<Skip>,
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    #[serde(default)]
+    pub commands: Vec<HookCommand>,
+}
+
+/// Skips a hook or command always, `skip = true`, or while a merge or rebase is in progress,
+/// `skip = "merge"` or `skip = ["merge", "rebase"]`
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(untagged)]
+pub enum Skip {
+    Always(bool),
+    State(String),
+    States(Vec<String>),
+}
+
+impl Skip {
+    /// Reason to skip given the state of the repository, ex: merge, if any
+    pub fn reason(&self, state: Option<&str>) -> Option<String> {
+        let skipped = |s: &String| Some(s.as_str()) == state;
+        match self {
+            Skip::Always(true) => Some(String::from("skip is set")),
+            Skip::Always(false) => None,
+            Skip::State(s) if skipped(s) => Some(format!("{} in progress", s)),
+            Skip::States(states) if states.iter().any(skipped) => {
+                state.map(|s| format!("{} in progress", s))
+            }
+            _ => None,
+        }
+    }
 }
 
 // If one line commands are not enough, you can execute files..
@@ -82,12 +112,14 @@ struct Script {
 //   exclude_tags:
 //     - frontend
 
-#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
-struct HookCommand {
-    name: String,
-    tags: Vec<String>,
-    glob: String, //Use glob patterns to choose what files you want to check
-    run: String,
+#[derive(Debug, Clone, Default, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct HookCommand {
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub glob: Option<String>, //Use glob patterns to choose what files you want to check
+    pub exclude: Option<String>, // regex, overrides the hook's exclude
+    pub root: Option<String>, // overrides the hook's root
+    pub skip: Option<Skip>,
+    pub run: String,
 }
-
-fn init() {}
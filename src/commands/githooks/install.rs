@@ -0,0 +1,226 @@
+// Installs thin shims into the hooks directory which defer to `doctavious githooks run`.
+// Existing hooks are renamed with a .old suffix and chained, run before the shim's commands, so
+// that installing doesn't break hooks set up by other tools. Uninstalling restores them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use serde::Serialize;
+
+use crate::commands::githooks::githooks::HOOK_NAMES;
+use crate::doctavious_error::{DoctaviousError, Result};
+
+/// Identifies hooks written by doctavious so they can be refreshed and uninstalled
+const SHIM_MARKER: &str = "# doctavious githook shim";
+
+const BACKUP_EXTENSION: &str = "old";
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct HookStatus {
+    pub name: String,
+    pub configured: bool,
+    pub installed: bool,
+    /// Whether a pre-existing hook is chained before the configured commands
+    pub chained: bool,
+    pub commands: Vec<String>,
+}
+
+/// Hooks directory from core.hooksPath, relative to the working directory, otherwise .git/hooks
+pub(crate) fn hooks_dir(repo: &Repository) -> Result<PathBuf> {
+    let hooks_path = repo.config()?.get_path("core.hooksPath").ok();
+    Ok(match hooks_path {
+        Some(path) if path.is_absolute() => path,
+        Some(path) => repo.workdir().unwrap_or_else(|| repo.path()).join(path),
+        None => repo.path().join("hooks"),
+    })
+}
+
+/// Writes a shim for each hook returning the path of each shim
+pub(crate) fn install(hooks_dir: &Path, hooks: &[String]) -> Result<Vec<PathBuf>> {
+    for hook in hooks {
+        if !HOOK_NAMES.contains(&hook.as_str()) {
+            return Err(DoctaviousError::Msg(format!("{} is not a git hook", hook)));
+        }
+    }
+
+    fs::create_dir_all(hooks_dir)?;
+    let mut installed = vec![];
+    for hook in hooks {
+        let path = hooks_dir.join(hook);
+        if path.exists() && !is_shim(&path) {
+            let backup = backup_path(&path);
+            if backup.exists() {
+                return Err(DoctaviousError::Msg(format!(
+                    "cannot back up {} as {} already exists",
+                    path.to_string_lossy(),
+                    backup.to_string_lossy()
+                )));
+            }
+            fs::rename(&path, &backup)?;
+        }
+
+        fs::write(&path, shim(hook))?;
+        make_executable(&path)?;
+        installed.push(path);
+    }
+
+    Ok(installed)
+}
+
+/// Removes the shims restoring any hooks they replaced
+pub(crate) fn uninstall(hooks_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut uninstalled = vec![];
+    for hook in HOOK_NAMES.iter() {
+        let path = hooks_dir.join(hook);
+        if !is_shim(&path) {
+            continue;
+        }
+
+        fs::remove_file(&path)?;
+        let backup = backup_path(&path);
+        if backup.exists() {
+            fs::rename(&backup, &path)?;
+        }
+        uninstalled.push(path);
+    }
+
+    Ok(uninstalled)
+}
+
+/// Status of configured hooks along with any installed hooks which are no longer configured
+pub(crate) fn list(hooks_dir: &Path, configured: &[(String, Vec<String>)]) -> Vec<HookStatus> {
+    HOOK_NAMES
+        .iter()
+        .filter_map(|hook| {
+            let path = hooks_dir.join(hook);
+            let commands = configured.iter().find(|(name, _)| name == hook).map(|(_, c)| c);
+            let installed = is_shim(&path);
+            if commands.is_none() && !installed {
+                return None;
+            }
+
+            Some(HookStatus {
+                name: hook.to_string(),
+                configured: commands.is_some(),
+                installed,
+                chained: installed && backup_path(&path).exists(),
+                commands: commands.cloned().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+fn shim(hook: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+{marker}
+# Runs the {hook} commands configured in doctavious.toml. Generated by `doctavious githooks install`
+
+hook_dir=$(dirname "$0")
+if [ -x "$hook_dir/{hook}.{backup}" ]; then
+    "$hook_dir/{hook}.{backup}" "$@" || exit $?
+fi
+
+exec doctavious --output text githooks run {hook} "$@"
+"#,
+        marker = SHIM_MARKER,
+        hook = hook,
+        backup = BACKUP_EXTENSION
+    )
+}
+
+fn is_shim(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.contains(SHIM_MARKER))
+        .unwrap_or(false)
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".");
+    backup.push(BACKUP_EXTENSION);
+    PathBuf::from(backup)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    use crate::commands::githooks::install::{
+        hooks_dir, install, list, uninstall, HookStatus
+    };
+
+    #[test]
+    fn should_install_and_uninstall_chained_hooks() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let hooks = hooks_dir(&repository).unwrap();
+        assert_eq!(repository.path().join("hooks"), hooks);
+
+        fs::create_dir_all(&hooks).unwrap();
+        fs::write(hooks.join("pre-commit"), "#!/bin/sh\nexit 0\n").unwrap();
+
+        let configured = vec![String::from("pre-commit"), String::from("commit-msg")];
+        install(&hooks, &configured).unwrap();
+        // reinstalling refreshes the shims rather than backing them up
+        install(&hooks, &configured).unwrap();
+        assert!(fs::read_to_string(hooks.join("pre-commit"))
+            .unwrap()
+            .contains("exec doctavious --output text githooks run pre-commit \"$@\""));
+        assert_eq!("#!/bin/sh\nexit 0\n", fs::read_to_string(hooks.join("pre-commit.old")).unwrap());
+        assert!(!hooks.join("commit-msg.old").exists());
+
+        assert_eq!(
+            vec![
+                HookStatus {
+                    name: String::from("pre-commit"),
+                    configured: true,
+                    installed: true,
+                    chained: true,
+                    commands: vec![String::from("lint")],
+                },
+                HookStatus {
+                    name: String::from("commit-msg"),
+                    configured: false,
+                    installed: true,
+                    chained: false,
+                    commands: vec![],
+                },
+            ],
+            list(&hooks, &[(String::from("pre-commit"), vec![String::from("lint")])])
+        );
+
+        assert_eq!(2, uninstall(&hooks).unwrap().len());
+        assert_eq!("#!/bin/sh\nexit 0\n", fs::read_to_string(hooks.join("pre-commit")).unwrap());
+        assert!(!hooks.join("commit-msg").exists());
+
+        assert!(install(&hooks, &[String::from("pre-comit")]).is_err());
+    }
+
+    #[test]
+    fn should_use_core_hooks_path() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        repository.config().unwrap().set_str("core.hooksPath", ".githooks").unwrap();
+        assert_eq!(
+            repository.workdir().unwrap().join(".githooks"),
+            hooks_dir(&repository).unwrap()
+        );
+    }
+}
@@ -1 +1,207 @@
+mod commit_msg;
 pub mod githooks;
+mod install;
+mod run;
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use git2::Repository;
+use serde::Serialize;
+
+use crate::commands::changelog::changelog::default_changelog_settings;
+use crate::commands::githooks::commit_msg::{validate, CommitMsgRules};
+use crate::commands::githooks::install::{hooks_dir, install, list, uninstall, HookStatus};
+use crate::commands::githooks::run::{run_hook, HookContext};
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::output::{get_output, print_output, Output};
+use crate::settings::SETTINGS;
+
+#[derive(Parser, Debug)]
+#[command(about = "Manage git hooks configured in doctavious.toml")]
+pub(crate) struct GithooksCommand {
+    #[command(subcommand)]
+    pub sub_command: GithooksSubCommand,
+}
+
+#[derive(Parser, Debug)]
+pub(crate) enum GithooksSubCommand {
+    Install(InstallHooks),
+    Uninstall(UninstallHooks),
+    List(ListHooks),
+    Run(RunHook),
+    Builtin(BuiltinHook),
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Install shims for the configured hooks. Existing hooks are backed up and chained")]
+pub(crate) struct InstallHooks {}
+
+#[derive(Parser, Debug)]
+#[command(about = "Remove installed shims restoring any hooks they replaced")]
+pub(crate) struct UninstallHooks {}
+
+#[derive(Parser, Debug)]
+#[command(about = "List configured and installed hooks")]
+pub(crate) struct ListHooks {}
+
+#[derive(Parser, Debug)]
+#[command(about = "Run the commands configured for a hook")]
+pub(crate) struct RunHook {
+    #[arg(help = "Name of the hook to run, ex: pre-commit")]
+    pub hook: String,
+
+    #[arg(
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        help = "Arguments git passed to the hook"
+    )]
+    pub args: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Run a hook built into doctavious")]
+pub(crate) struct BuiltinHook {
+    #[command(subcommand)]
+    pub sub_command: BuiltinHookCommand,
+}
+
+#[derive(Parser, Debug)]
+pub(crate) enum BuiltinHookCommand {
+    CommitMsg(CommitMsgHook),
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Validate a commit message against the changelog's conventional commit and commit_parsers settings"
+)]
+pub(crate) struct CommitMsgHook {
+    #[arg(help = "File containing the commit message, ex: .git/COMMIT_EDITMSG")]
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct HookStatuses(Vec<HookStatus>);
+
+impl Display for HookStatuses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for status in &self.0 {
+            let state = match (status.configured, status.installed) {
+                (true, true) => "installed",
+                (true, false) => "not installed",
+                (false, _) => "installed but not configured",
+            };
+            write!(f, "{} ({})", status.name, state)?;
+            if status.chained {
+                write!(f, " chained with existing hook")?;
+            }
+            writeln!(f)?;
+            for command in &status.commands {
+                writeln!(f, "  - {}", command)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HookPaths(Vec<String>);
+
+impl Display for HookPaths {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join("\n"))
+    }
+}
+
+pub(crate) fn handle_githooks_command(command: GithooksCommand, output: Option<Output>) -> Result<()> {
+    let repository = Repository::discover(env::current_dir()?)?;
+    let hooks_dir = hooks_dir(&repository)?;
+    match command.sub_command {
+        GithooksSubCommand::Install(_) => {
+            let hooks = configured_hooks();
+            if hooks.is_empty() {
+                return Err(DoctaviousError::Msg(String::from(
+                    "no hooks configured. Add hooks to [githook.hooks] in doctavious.toml",
+                )));
+            }
+            let names: Vec<String> = hooks.into_iter().map(|(name, _)| name).collect();
+            let installed = install(&hooks_dir, &names)?;
+            print_output(get_output(output), HookPaths(to_strings(installed)))
+        }
+        GithooksSubCommand::Uninstall(_) => {
+            let uninstalled = uninstall(&hooks_dir)?;
+            print_output(get_output(output), HookPaths(to_strings(uninstalled)))
+        }
+        GithooksSubCommand::List(_) => {
+            print_output(get_output(output), HookStatuses(list(&hooks_dir, &configured_hooks())))
+        }
+        GithooksSubCommand::Run(cmd) => {
+            let hook = SETTINGS
+                .githook_settings
+                .as_ref()
+                .and_then(|settings| settings.hooks.get(&cmd.hook))
+                .ok_or_else(|| {
+                    DoctaviousError::Msg(format!("{} is not configured in doctavious.toml", cmd.hook))
+                })?;
+            let context = HookContext::new(&repository, cmd.args)?;
+            let result = run_hook(&cmd.hook, hook, &context)?;
+            let success = result.is_success();
+            print_output(get_output(output), result)?;
+            if !success {
+                return Err(DoctaviousError::Msg(format!("{} hook failed", cmd.hook)));
+            }
+            Ok(())
+        }
+        GithooksSubCommand::Builtin(BuiltinHook {
+            sub_command: BuiltinHookCommand::CommitMsg(cmd),
+        }) => {
+            // paths given by git are relative to the repository root
+            let path = repository.workdir().map(|w| w.join(&cmd.file)).unwrap_or(cmd.file);
+            let message = fs::read_to_string(&path)?;
+            let changelog = match &SETTINGS.changelog_settings {
+                Some(settings) => settings.clone(),
+                None => default_changelog_settings()?,
+            };
+            let settings = SETTINGS
+                .githook_settings
+                .as_ref()
+                .and_then(|s| s.commit_msg.clone())
+                .unwrap_or_default();
+            validate(
+                &message,
+                &CommitMsgRules {
+                    settings: &settings,
+                    conventional_commits: changelog.git.conventional_commits,
+                    commit_parsers: changelog.git.commit_parsers.as_deref(),
+                    filter_commits: changelog.git.filter_commits,
+                },
+            )
+        }
+    }
+}
+
+/// Configured hook names along with the names of their commands, sorted by hook name
+fn configured_hooks() -> Vec<(String, Vec<String>)> {
+    let mut hooks: Vec<(String, Vec<String>)> = SETTINGS
+        .githook_settings
+        .as_ref()
+        .map(|settings| {
+            settings
+                .hooks
+                .iter()
+                .map(|(name, hook)| {
+                    (name.to_string(), hook.commands.iter().map(|c| c.name.to_string()).collect())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    hooks.sort_by(|a, b| a.0.cmp(&b.0));
+    hooks
+}
+
+fn to_strings(paths: Vec<std::path::PathBuf>) -> Vec<String> {
+    paths.iter().map(|p| p.to_string_lossy().to_string()).collect()
+}
@@ -0,0 +1,486 @@
+// Runs the commands of a configured hook, similar to lefthook
+// https://github.com/evilmartians/lefthook/blob/master/docs/full_guide.md
+//
+// Commands are run with sh, or cmd on Windows, from the repository root or the hook's root after
+// expanding the following placeholders
+// {staged_files} - staged files which match the glob and exclude
+// {all_files} - tracked files which match the glob and exclude
+// {0} - git hook arguments joined by a space
+// {i} - i-th git hook argument, ex: {1} for the commit message file of commit-msg
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output as ProcessOutput, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use git2::{Delta, Repository, RepositoryState};
+use glob::Pattern;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::commands::githooks::githooks::{Hook, HookCommand};
+use crate::doctavious_error::{DoctaviousError, Result};
+
+const STAGED_FILES: &str = "{staged_files}";
+const ALL_FILES: &str = "{all_files}";
+
+lazy_static! {
+    static ref ARGUMENT_RE: Regex = Regex::new(r"\{(\d+)\}").unwrap();
+}
+
+/// State of the repository and arguments the hook was called with
+#[derive(Debug, Default)]
+pub(crate) struct HookContext {
+    pub workdir: PathBuf,
+    pub args: Vec<String>,
+    pub staged_files: Vec<String>,
+    pub all_files: Vec<String>,
+    /// merge or rebase when either is in progress
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CommandStatus {
+    Success,
+    Failure,
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CommandResult {
+    pub name: String,
+    pub status: CommandStatus,
+    /// Why the command was skipped or failed
+    pub reason: Option<String>,
+    pub duration_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct HookResult {
+    pub hook: String,
+    pub commands: Vec<CommandResult>,
+    pub duration_ms: u128,
+}
+
+impl HookResult {
+    pub fn is_success(&self) -> bool {
+        !self.commands.iter().any(|c| c.status == CommandStatus::Failure)
+    }
+}
+
+impl Display for HookResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} ({})", self.hook, format_duration(self.duration_ms))?;
+        for command in &self.commands {
+            let status = match command.status {
+                CommandStatus::Success => "✔",
+                CommandStatus::Failure => "✘",
+                CommandStatus::Skipped => "-",
+            };
+            write!(f, "  {} {}", status, command.name)?;
+            match (&command.status, &command.reason) {
+                (CommandStatus::Skipped, Some(reason)) => write!(f, " (skipped: {})", reason)?,
+                (_, Some(reason)) => write!(f, " ({}, {})", reason, format_duration(command.duration_ms))?,
+                (_, None) => write!(f, " ({})", format_duration(command.duration_ms))?,
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl HookContext {
+    pub fn new(repository: &Repository, args: Vec<String>) -> Result<Self> {
+        let workdir = repository.workdir().ok_or_else(|| {
+            DoctaviousError::Msg(String::from("cannot run hooks in a bare repository"))
+        })?;
+
+        let index = repository.index()?;
+        let all_files = index
+            .iter()
+            .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+            .collect();
+
+        let head = repository.head().ok().and_then(|h| h.peel_to_tree().ok());
+        let diff = repository.diff_tree_to_index(head.as_ref(), Some(&index), None)?;
+        let staged_files = diff
+            .deltas()
+            .filter(|d| d.status() != Delta::Deleted)
+            .filter_map(|d| d.new_file().path().map(|p| p.to_string_lossy().to_string()))
+            .collect();
+
+        let state = match repository.state() {
+            RepositoryState::Merge => Some(String::from("merge")),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some(String::from("rebase")),
+            _ => None,
+        };
+
+        Ok(Self {
+            workdir: workdir.to_path_buf(),
+            args,
+            staged_files,
+            all_files,
+            state,
+        })
+    }
+}
+
+/// Command ready to run, with its placeholders expanded, or the reason it's skipped
+enum Prepared {
+    Run { run: String, dir: PathBuf },
+    Skip(String),
+}
+
+pub(crate) fn run_hook(name: &str, hook: &Hook, context: &HookContext) -> Result<HookResult> {
+    let start = Instant::now();
+    if let Some(reason) = hook.skip.as_ref().and_then(|s| s.reason(context.state.as_deref())) {
+        return Ok(HookResult {
+            hook: name.to_string(),
+            commands: hook
+                .commands
+                .iter()
+                .map(|c| skipped(&c.name, reason.to_string()))
+                .collect(),
+            duration_ms: 0,
+        });
+    }
+
+    let mut prepared = vec![];
+    for command in &hook.commands {
+        prepared.push((command.name.to_string(), prepare(hook, command, context)?));
+    }
+
+    let mut results = vec![];
+    if hook.parallel && !hook.piped {
+        // output is captured so that the output of each command isn't interleaved
+        let handles: Vec<_> = prepared
+            .into_iter()
+            .map(|(name, prepared)| {
+                thread::spawn(move || match prepared {
+                    Prepared::Run { run, dir } => {
+                        let start = Instant::now();
+                        let output = shell(&run, &dir)
+                            .stdout(Stdio::piped())
+                            .stderr(Stdio::piped())
+                            .output();
+                        (name, Outcome::Ran(output, start.elapsed()))
+                    }
+                    Prepared::Skip(reason) => (name, Outcome::Skipped(reason)),
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (name, outcome) = handle.join().map_err(|_| {
+                DoctaviousError::Msg(String::from("hook command panicked"))
+            })?;
+            results.push(match outcome {
+                Outcome::Ran(output, elapsed) => {
+                    if let Ok(output) = &output {
+                        print!("{}", String::from_utf8_lossy(&output.stdout));
+                        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                    }
+                    completed(&name, output.map(|o| o.status.code()), elapsed)
+                }
+                Outcome::Skipped(reason) => skipped(&name, reason),
+            });
+        }
+    } else {
+        let mut failed = false;
+        for (name, prepared) in prepared {
+            if failed && hook.piped {
+                results.push(skipped(&name, String::from("previous command failed")));
+                continue;
+            }
+
+            let result = match prepared {
+                Prepared::Run { run, dir } => {
+                    let start = Instant::now();
+                    let status = shell(&run, &dir).status().map(|s| s.code());
+                    completed(&name, status, start.elapsed())
+                }
+                Prepared::Skip(reason) => skipped(&name, reason),
+            };
+            failed = failed || result.status == CommandStatus::Failure;
+            results.push(result);
+        }
+    }
+
+    Ok(HookResult {
+        hook: name.to_string(),
+        commands: results,
+        duration_ms: start.elapsed().as_millis(),
+    })
+}
+
+enum Outcome {
+    Ran(std::io::Result<ProcessOutput>, Duration),
+    Skipped(String),
+}
+
+fn prepare(hook: &Hook, command: &HookCommand, context: &HookContext) -> Result<Prepared> {
+    if let Some(reason) = command.skip.as_ref().and_then(|s| s.reason(context.state.as_deref())) {
+        return Ok(Prepared::Skip(reason));
+    }
+
+    if let Some(tag) = command.tags.iter().find(|t| hook.exclude_tags.contains(t)) {
+        return Ok(Prepared::Skip(format!("tag {} is excluded", tag)));
+    }
+
+    let root = command.root.as_ref().or(hook.root.as_ref()).map(|r| r.trim_end_matches('/'));
+    let glob = command.glob.as_ref().or(hook.glob.as_ref()).map(|g| Pattern::new(g)).transpose()?;
+    let exclude = command
+        .exclude
+        .as_ref()
+        .or(hook.exclude.as_ref())
+        .map(|e| {
+            Regex::new(e).map_err(|err| {
+                DoctaviousError::Msg(format!("invalid exclude pattern {}: {}", e, err))
+            })
+        })
+        .transpose()?;
+    let filter = |files: &[String]| -> Vec<String> {
+        files
+            .iter()
+            .filter(|f| glob.as_ref().map(|g| g.matches(f)).unwrap_or(true))
+            .filter(|f| !exclude.as_ref().map(|e| e.is_match(f)).unwrap_or(false))
+            // files are relative to the root the command runs in
+            .filter_map(|f| match root {
+                Some(root) if !root.is_empty() => f
+                    .strip_prefix(root)
+                    .and_then(|f| f.strip_prefix('/'))
+                    .map(String::from),
+                _ => Some(f.to_string()),
+            })
+            .collect()
+    };
+
+    let mut run = command.run.to_string();
+    for (placeholder, files) in [(STAGED_FILES, &context.staged_files), (ALL_FILES, &context.all_files)] {
+        if run.contains(placeholder) {
+            let files = filter(files);
+            if files.is_empty() {
+                return Ok(Prepared::Skip(String::from("no matching files")));
+            }
+            let files: Vec<String> = files.iter().map(|f| shell_escape(f)).collect();
+            run = run.replace(placeholder, &files.join(" "));
+        }
+    }
+
+    let run = ARGUMENT_RE
+        .replace_all(&run, |captures: &regex::Captures| {
+            match captures[1].parse::<usize>().unwrap_or_default() {
+                0 => context.args.iter().map(|a| shell_escape(a)).collect::<Vec<String>>().join(" "),
+                i => context.args.get(i - 1).map(|a| shell_escape(a)).unwrap_or_default(),
+            }
+        })
+        .to_string();
+
+    let dir = match root {
+        Some(root) => context.workdir.join(root),
+        None => context.workdir.clone(),
+    };
+
+    Ok(Prepared::Run { run, dir })
+}
+
+fn shell(run: &str, dir: &Path) -> Command {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", run]);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.args(["-c", run]);
+        command
+    };
+    command.current_dir(dir);
+    command
+}
+
+/// Quotes the value when it contains characters the shell would interpret
+fn shell_escape(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ',' | ':' | '@' | '+' | '=');
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+fn completed(name: &str, status: std::io::Result<Option<i32>>, elapsed: Duration) -> CommandResult {
+    let (status, reason) = match status {
+        Ok(Some(0)) => (CommandStatus::Success, None),
+        Ok(Some(code)) => (CommandStatus::Failure, Some(format!("exit code {}", code))),
+        Ok(None) => (CommandStatus::Failure, Some(String::from("terminated by signal"))),
+        Err(e) => (CommandStatus::Failure, Some(e.to_string())),
+    };
+    CommandResult {
+        name: name.to_string(),
+        status,
+        reason,
+        duration_ms: elapsed.as_millis(),
+    }
+}
+
+fn skipped(name: &str, reason: String) -> CommandResult {
+    CommandResult {
+        name: name.to_string(),
+        status: CommandStatus::Skipped,
+        reason: Some(reason),
+        duration_ms: 0,
+    }
+}
+
+fn format_duration(millis: u128) -> String {
+    format!("{:.2}s", millis as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    use crate::commands::githooks::githooks::{Hook, HookCommand, Skip};
+    use crate::commands::githooks::run::{run_hook, CommandStatus, HookContext};
+
+    fn command(name: &str, run: &str) -> HookCommand {
+        HookCommand {
+            name: name.to_string(),
+            run: run.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn context(dir: &TempDir) -> HookContext {
+        HookContext {
+            workdir: dir.path().to_path_buf(),
+            args: vec![String::from(".git/COMMIT_EDITMSG"), String::from("message")],
+            staged_files: vec![
+                String::from("api/main.rs"),
+                String::from("api/generated.rs"),
+                String::from("web/index.js"),
+            ],
+            all_files: vec![],
+            state: None,
+        }
+    }
+
+    #[test]
+    fn should_expand_placeholders() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("api")).unwrap();
+        let hook = Hook {
+            root: Some(String::from("api/")),
+            exclude: Some(String::from("generated")),
+            commands: vec![
+                command("files", "echo {staged_files} > files.txt"),
+                command("args", "echo {1} {0} > args.txt"),
+                command("all", "echo {all_files}"),
+            ],
+            ..Default::default()
+        };
+
+        let result = run_hook("pre-commit", &hook, &context(&dir)).unwrap();
+        assert!(result.is_success());
+        assert_eq!("main.rs\n", fs::read_to_string(dir.path().join("api/files.txt")).unwrap());
+        assert_eq!(
+            ".git/COMMIT_EDITMSG .git/COMMIT_EDITMSG message\n",
+            fs::read_to_string(dir.path().join("api/args.txt")).unwrap()
+        );
+        assert_eq!(CommandStatus::Skipped, result.commands[2].status);
+        assert_eq!(Some(String::from("no matching files")), result.commands[2].reason);
+    }
+
+    #[test]
+    fn should_stop_piped_hook_on_failure() {
+        let dir = TempDir::new().unwrap();
+        let hook = Hook {
+            piped: true,
+            commands: vec![command("fail", "exit 3"), command("touch", "touch touched")],
+            ..Default::default()
+        };
+
+        let result = run_hook("pre-push", &hook, &context(&dir)).unwrap();
+        assert!(!result.is_success());
+        assert_eq!(Some(String::from("exit code 3")), result.commands[0].reason);
+        assert_eq!(CommandStatus::Skipped, result.commands[1].status);
+        assert!(!dir.path().join("touched").exists());
+    }
+
+    #[test]
+    fn should_run_parallel_commands() {
+        let dir = TempDir::new().unwrap();
+        let hook = Hook {
+            parallel: true,
+            commands: vec![command("a", "touch a"), command("b", "exit 1")],
+            ..Default::default()
+        };
+
+        let result = run_hook("pre-commit", &hook, &context(&dir)).unwrap();
+        assert_eq!(CommandStatus::Success, result.commands[0].status);
+        assert_eq!(CommandStatus::Failure, result.commands[1].status);
+        assert!(dir.path().join("a").exists());
+    }
+
+    #[test]
+    fn should_skip_by_state_and_tags() {
+        let dir = TempDir::new().unwrap();
+        let mut context = context(&dir);
+        context.state = Some(String::from("rebase"));
+        let hook = Hook {
+            exclude_tags: vec![String::from("frontend")],
+            commands: vec![
+                HookCommand {
+                    tags: vec![String::from("frontend")],
+                    ..command("eslint", "touch eslint")
+                },
+                HookCommand {
+                    skip: Some(Skip::States(vec![String::from("merge"), String::from("rebase")])),
+                    ..command("fmt", "touch fmt")
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = run_hook("pre-commit", &hook, &context).unwrap();
+        assert_eq!(Some(String::from("tag frontend is excluded")), result.commands[0].reason);
+        assert_eq!(Some(String::from("rebase in progress")), result.commands[1].reason);
+
+        let hook = Hook {
+            skip: Some(Skip::State(String::from("rebase"))),
+            commands: vec![command("fmt", "touch fmt")],
+            ..Default::default()
+        };
+        assert_eq!(
+            CommandStatus::Skipped,
+            run_hook("pre-commit", &hook, &context).unwrap().commands[0].status
+        );
+        assert!(!dir.path().join("fmt").exists());
+    }
+
+    #[test]
+    fn should_collect_staged_files() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("a.rs"), "fn main() {}").unwrap();
+        let mut index = repository.index().unwrap();
+        index.add_path(std::path::Path::new("a.rs")).unwrap();
+        index.write().unwrap();
+
+        let context = HookContext::new(&repository, vec![]).unwrap();
+        assert_eq!(vec![String::from("a.rs")], context.staged_files);
+        assert_eq!(vec![String::from("a.rs")], context.all_files);
+        assert_eq!(None, context.state);
+    }
+}
@@ -7,16 +7,19 @@ use walkdir::WalkDir;
 
 
 pub mod build;
-mod bump;
+pub mod bump;
+pub mod cache;
 mod cdg;
 pub mod changelog;
+pub mod check;
 mod deploy;
 pub mod design_decisions;
 pub mod githooks;
 pub mod init;
 pub mod login;
-mod presentation;
-mod release;
+pub mod presentation;
+pub mod release;
+pub mod serve;
 mod service_directory;
 mod snippets;
 mod software_template;
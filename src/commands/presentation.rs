@@ -1,2 +0,0 @@
-// TODO: flush this out more
-// we can call out to marp for the time being if we want to
@@ -0,0 +1,332 @@
+// Renders a deck to a single self-contained HTML file.
+// Theme CSS, viewer script and local images are all inlined so the file can be opened offline or
+// shared without any accompanying assets.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use lazy_static::lazy_static;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use regex::Regex;
+
+use crate::commands::presentation::slides::{Deck, Slide};
+use crate::commands::serve::server::content_type;
+use crate::doctavious_error::Result;
+
+const DEFAULT_THEME_NAME: &str = "default";
+const DEFAULT_THEME: &str = include_str!("../../../templates/presentation/default.css");
+const VIEWER_CSS: &str = include_str!("../../../templates/presentation/viewer.css");
+const VIEWER_SCRIPT: &str = include_str!("../../../templates/presentation/viewer.js");
+
+lazy_static! {
+    static ref THEME_NAME_RE: Regex = Regex::new(r"@theme\s+([^\s*]+)").unwrap();
+}
+
+/// Renders the deck as HTML. Relative images are resolved against `base_dir`, typically the
+/// directory of the markdown file, and themes are looked up within `theme_dir`
+pub(crate) fn render_html(deck: &Deck, base_dir: &Path, theme_dir: &Path) -> Result<String> {
+    let (width, height) = slide_size(deck.size.as_deref());
+    let theme = get_theme_css(deck.theme.as_deref(), theme_dir)?;
+
+    let mut sections = String::new();
+    for slide in &deck.slides {
+        sections.push_str(&render_slide(slide, base_dir));
+    }
+
+    let title = deck.title.clone().unwrap_or_else(|| get_title(deck));
+
+    Ok(format!(
+        "<!DOCTYPE html>\n\
+        <html lang=\"en\">\n\
+        <head>\n\
+        <meta charset=\"utf-8\">\n\
+        <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+        <title>{title}</title>\n\
+        <style>\n{viewer}\n</style>\n\
+        <style>\n{theme}\n{style}\n</style>\n\
+        </head>\n\
+        <body>\n\
+        <div class=\"deck\" data-width=\"{width}\" data-height=\"{height}\" \
+        style=\"--slide-width: {width}px; --slide-height: {height}px; width: {width}px; height: {height}px;\">\n\
+        {sections}\
+        </div>\n\
+        <div class=\"notes-panel\"></div>\n\
+        <script>\n{script}\n</script>\n\
+        </body>\n\
+        </html>\n",
+        title = escape_html(&title),
+        viewer = VIEWER_CSS,
+        theme = theme,
+        style = deck.style.as_deref().unwrap_or_default(),
+        width = width,
+        height = height,
+        sections = sections,
+        script = VIEWER_SCRIPT,
+    ))
+}
+
+/// Width and height, in pixels, for the size directive
+fn slide_size(size: Option<&str>) -> (u32, u32) {
+    match size {
+        Some("4:3") => (960, 720),
+        _ => (1280, 720),
+    }
+}
+
+/// Title of the document when not provided via the title directive, the first heading
+fn get_title(deck: &Deck) -> String {
+    deck.slides
+        .iter()
+        .flat_map(|s| s.markdown.lines())
+        .find_map(|l| l.trim_start().strip_prefix('#').map(|h| h.trim_start_matches('#').trim()))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// CSS for the named theme.
+///
+/// Like Marp, a theme is any CSS file within the theme directory declaring `@theme <name>` in a
+/// comment. The file name, without extension, is also accepted for convenience. Unknown themes
+/// fall back to the default theme.
+pub(crate) fn get_theme_css(theme: Option<&str>, theme_dir: &Path) -> Result<String> {
+    let name = match theme {
+        None | Some(DEFAULT_THEME_NAME) => return Ok(DEFAULT_THEME.to_string()),
+        Some(name) => name,
+    };
+
+    if theme_dir.is_dir() {
+        let mut css_files: Vec<PathBuf> = fs::read_dir(theme_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "css").unwrap_or(false))
+            .collect();
+        css_files.sort();
+
+        let mut by_file_name = None;
+        for css_file in css_files {
+            let css = fs::read_to_string(&css_file)?;
+            let declared = THEME_NAME_RE.captures(&css).map(|c| c[1].to_string());
+            if declared.as_deref() == Some(name) {
+                return Ok(css);
+            }
+            if by_file_name.is_none() && css_file.file_stem().map(|s| s == name).unwrap_or(false) {
+                by_file_name = Some(css);
+            }
+        }
+
+        if let Some(css) = by_file_name {
+            return Ok(css);
+        }
+    }
+
+    eprintln!("theme {} not found in {}. Using default theme", name, theme_dir.to_string_lossy());
+    Ok(DEFAULT_THEME.to_string())
+}
+
+fn render_slide(slide: &Slide, base_dir: &Path) -> String {
+    let directives = &slide.directives;
+    let (content, background) = render_markdown(&slide.markdown, base_dir);
+
+    let mut style = String::new();
+    if let Some(color) = &directives.background_color {
+        style.push_str(&format!("background-color: {};", color));
+    }
+    if let Some(color) = &directives.color {
+        style.push_str(&format!("color: {};", color));
+    }
+    match background {
+        Some(image) => style.push_str(&format!("background-image: url(\"{}\");", image)),
+        None => {
+            if let Some(image) = &directives.background_image {
+                style.push_str(&format!("background-image: {};", image));
+            }
+        }
+    }
+
+    let class = match &directives.class {
+        Some(class) => format!("slide {}", class),
+        None => String::from("slide"),
+    };
+
+    let mut html = format!(
+        "<section id=\"{}\" class=\"{}\"",
+        slide.number,
+        escape_html(&class)
+    );
+    if !style.is_empty() {
+        html.push_str(&format!(" style=\"{}\"", escape_html(&style)));
+    }
+    html.push_str(">\n");
+
+    if let Some(header) = &directives.header {
+        html.push_str(&format!("<header>{}</header>\n", render_inline(header, base_dir)));
+    }
+    html.push_str(&content);
+    if let Some(footer) = &directives.footer {
+        html.push_str(&format!("<footer>{}</footer>\n", render_inline(footer, base_dir)));
+    }
+    if directives.paginate {
+        html.push_str(&format!("<span class=\"pagination\">{}</span>\n", slide.number));
+    }
+    if !slide.notes.is_empty() {
+        html.push_str(&format!(
+            "<aside class=\"notes\">{}</aside>\n",
+            escape_html(&slide.notes.join("\n\n"))
+        ));
+    }
+    html.push_str("</section>\n");
+
+    html
+}
+
+/// Renders markdown to HTML returning the image, if any, marked as the slide background via
+/// Marp's `![bg](image.png)` syntax
+fn render_markdown(markdown: &str, base_dir: &Path) -> (String, Option<String>) {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let parsed: Vec<Event> = Parser::new_ext(markdown, options).collect();
+    let mut events = Vec::with_capacity(parsed.len());
+    let mut background = None;
+
+    let mut index = 0;
+    while index < parsed.len() {
+        if let Event::Start(Tag::Image(link_type, dest, title)) = &parsed[index] {
+            let end = parsed[index..]
+                .iter()
+                .position(|e| matches!(e, Event::End(Tag::Image(..))))
+                .map(|p| index + p)
+                .unwrap_or(parsed.len() - 1);
+            let alt: String = parsed[index + 1..end]
+                .iter()
+                .filter_map(|e| match e {
+                    Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                    _ => None,
+                })
+                .collect();
+
+            let source = embed_image(dest, base_dir);
+            if alt.split_whitespace().next() == Some("bg") {
+                background = Some(source);
+            } else {
+                let tag = Tag::Image(*link_type, CowStr::from(source), title.clone());
+                events.push(Event::Start(tag.clone()));
+                events.extend(parsed[index + 1..end].iter().cloned());
+                events.push(Event::End(tag));
+            }
+
+            index = end + 1;
+            continue;
+        }
+
+        events.push(parsed[index].clone());
+        index += 1;
+    }
+
+    let mut output = String::new();
+    html::push_html(&mut output, events.into_iter());
+    (output, background)
+}
+
+/// Renders a single line of markdown, as used by header and footer directives, without the
+/// surrounding paragraph
+fn render_inline(markdown: &str, base_dir: &Path) -> String {
+    let (html, _) = render_markdown(markdown, base_dir);
+    let html = html.trim();
+    html.strip_prefix("<p>")
+        .and_then(|h| h.strip_suffix("</p>"))
+        .unwrap_or(html)
+        .to_string()
+}
+
+/// Inlines local images as data URIs. Remote images and images which can't be read are left as is
+fn embed_image(source: &str, base_dir: &Path) -> String {
+    if source.contains("://") || source.starts_with("data:") || source.starts_with("//") {
+        return source.to_string();
+    }
+
+    let path = base_dir.join(source);
+    match fs::read(&path) {
+        Ok(bytes) => format!("data:{};base64,{}", content_type(&path), BASE64.encode(bytes)),
+        Err(e) => {
+            eprintln!("unable to embed image {}: {}", path.to_string_lossy(), e);
+            source.to_string()
+        }
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::commands::presentation::html::{get_theme_css, render_html};
+    use crate::commands::presentation::slides::parse_deck;
+
+    #[test]
+    fn should_render_slides_with_directives_and_notes() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("logo.png"), [137, 80, 78, 71]).unwrap();
+
+        let deck = parse_deck("---\n\
+            paginate: true\n\
+            ---\n\
+            <!-- _class: lead -->\n\
+            # Doctavious\n\
+            \n\
+            ![bg](logo.png)\n\
+            \n\
+            <!-- Introduce the team -->\n\
+            \n\
+            ---\n\
+            ## Agenda\n\
+            \n\
+            ![logo](logo.png)\n");
+
+        let html = render_html(&deck, dir.path(), dir.path()).unwrap();
+
+        assert!(html.contains("<title>Doctavious</title>"));
+        assert!(html.contains("<section id=\"1\" class=\"slide lead\" style=\"background-image: url(&quot;data:image/png;base64,iVBORw==&quot;);\">"));
+        assert!(html.contains("<aside class=\"notes\">Introduce the team</aside>"));
+        assert!(html.contains("<section id=\"2\" class=\"slide\">"));
+        assert!(html.contains("<img src=\"data:image/png;base64,iVBORw==\" alt=\"logo\" />"));
+        assert!(html.contains("<span class=\"pagination\">2</span>"));
+        assert!(!html.contains("<!-- Introduce the team -->"));
+    }
+
+    #[test]
+    fn should_find_theme_in_input_dir() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("brand.css"), "/* @theme corporate */\nsection { color: red; }").unwrap();
+        fs::write(dir.path().join("other.css"), "section { color: blue; }").unwrap();
+
+        let css = get_theme_css(Some("corporate"), dir.path()).unwrap();
+        assert!(css.contains("color: red"));
+
+        let css = get_theme_css(Some("other"), dir.path()).unwrap();
+        assert!(css.contains("color: blue"));
+
+        let css = get_theme_css(Some("missing"), dir.path()).unwrap();
+        assert!(css.contains("@theme default"));
+    }
+}
@@ -0,0 +1,314 @@
+// Marp compatible presentations
+// https://marpit.marp.app/
+// https://github.com/marp-team/marp-cli#options
+//
+// Marp CLI can be configured options with file, such as marp.config.js, marp.config.cjs, .marprc
+// (JSON / YAML), and marp section of package.json. We don't support these yet and only support
+// the equivalent command line options.
+
+mod html;
+mod pptx;
+mod slides;
+
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use clap::{Parser, ValueEnum};
+use walkdir::WalkDir;
+
+use crate::commands::presentation::html::render_html;
+use crate::commands::presentation::pptx::render_pptx;
+use crate::commands::presentation::slides::parse_deck;
+use crate::commands::serve::server::{serve, LiveReload};
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::output::Output;
+use crate::watcher::FileWatcher;
+
+/// How often markdown and themes are checked for changes when watching
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Parser, Debug)]
+#[command(about = "Convert Marp flavoured markdown into presentations")]
+pub(crate) struct PresentationCommand {
+    #[arg(help = "Markdown file to convert. Not required when input-dir is passed")]
+    pub file: Option<PathBuf>,
+
+    #[arg(long, help = "Output file path (or directory input-dir is passed)")]
+    pub output_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = PresentationFormat::Html,
+        help = "Format to convert markdown into"
+    )]
+    pub format: PresentationFormat,
+
+    #[arg(
+        long,
+        short,
+        help = "The base directory to find markdown and theme CSS"
+    )]
+    pub input_dir: Option<PathBuf>,
+
+    #[arg(long, short, help = "Watch input markdowns for changes")]
+    pub watch: bool,
+
+    #[arg(long, short, help = "Enable server mode. Implies watch")]
+    pub server: bool,
+
+    #[arg(long, default_value_t = 8080, help = "Port to listen on in server mode")]
+    pub port: u16,
+
+    #[arg(long, short, help = "Open preview window")]
+    pub preview: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PresentationFormat {
+    /// Single self-contained HTML file
+    Html,
+    /// PowerPoint (Office Open XML)
+    Pptx,
+}
+
+impl PresentationFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            PresentationFormat::Html => "html",
+            PresentationFormat::Pptx => "pptx",
+        }
+    }
+}
+
+/// Markdown file to convert along with where to write the output
+#[derive(Debug, PartialEq)]
+struct Conversion {
+    input: PathBuf,
+    output: PathBuf,
+    theme_dir: PathBuf,
+    format: PresentationFormat,
+}
+
+pub(crate) fn handle_presentation_command(
+    command: PresentationCommand,
+    _output: Option<Output>,
+) -> Result<()> {
+    let conversions = get_conversions(&command)?;
+    for conversion in &conversions {
+        convert(conversion)?;
+    }
+
+    let watch_root = command.input_dir.clone().unwrap_or_else(|| {
+        parent_dir(command.file.as_deref().unwrap_or_else(|| Path::new(".")))
+    });
+
+    let live_reload = if command.server {
+        let root = match &command.output_dir {
+            Some(output_dir) if command.input_dir.is_some() => output_dir.clone(),
+            _ => watch_root.clone(),
+        };
+        let listener = TcpListener::bind(("127.0.0.1", command.port))?;
+        let url = format!("http://127.0.0.1:{}/", command.port);
+        println!("serving {} at {}", root.to_string_lossy(), url);
+
+        if command.preview {
+            let page = conversions
+                .first()
+                .and_then(|c| c.output.strip_prefix(&root).ok())
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            open_preview(&format!("{}{}", url, page))?;
+        }
+
+        let live_reload = Arc::new(LiveReload::default());
+        let server_live_reload = live_reload.clone();
+        thread::spawn(move || serve(listener, root, Some(server_live_reload)));
+        Some(live_reload)
+    } else {
+        if command.preview {
+            if let Some(conversion) = conversions.first() {
+                open_preview(&conversion.output.to_string_lossy())?;
+            }
+        }
+        None
+    };
+
+    if !command.watch && !command.server {
+        return Ok(());
+    }
+
+    // outputs are excluded otherwise writing them would trigger another conversion
+    let outputs = |conversions: &[Conversion]| -> Vec<PathBuf> {
+        conversions.iter().map(|c| c.output.clone()).collect()
+    };
+    let mut watcher = FileWatcher::new(vec![watch_root], outputs(&conversions));
+    println!("watching for changes");
+    loop {
+        watcher.wait_for_change(WATCH_INTERVAL);
+
+        // markdown files may have been added or removed
+        let conversions = get_conversions(&command)?;
+        for conversion in &conversions {
+            if let Err(e) = convert(conversion) {
+                eprintln!("failed to convert {}: {}", conversion.input.to_string_lossy(), e);
+            }
+        }
+        watcher.set_excluded(outputs(&conversions));
+
+        if let Some(live_reload) = &live_reload {
+            live_reload.reload();
+        }
+    }
+}
+
+fn get_conversions(command: &PresentationCommand) -> Result<Vec<Conversion>> {
+    if let Some(input_dir) = &command.input_dir {
+        let output_dir = command.output_dir.as_ref().unwrap_or(input_dir);
+        let mut conversions = vec![];
+        for entry in WalkDir::new(input_dir)
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.path().extension().map(|e| e == "md").unwrap_or(false))
+        {
+            let relative = entry.path().strip_prefix(input_dir).unwrap_or(entry.path());
+            conversions.push(Conversion {
+                input: entry.path().to_path_buf(),
+                output: output_dir.join(relative).with_extension(command.format.extension()),
+                theme_dir: input_dir.clone(),
+                format: command.format,
+            });
+        }
+        return Ok(conversions);
+    }
+
+    match &command.file {
+        Some(file) => Ok(vec![Conversion {
+            input: file.clone(),
+            output: command.output_dir
+                .clone()
+                .unwrap_or_else(|| file.with_extension(command.format.extension())),
+            theme_dir: parent_dir(file),
+            format: command.format,
+        }]),
+        None => Err(DoctaviousError::Msg(String::from(
+            "a markdown file or --input-dir is required"
+        ))),
+    }
+}
+
+fn convert(conversion: &Conversion) -> Result<()> {
+    let content = fs::read_to_string(&conversion.input)?;
+    let deck = parse_deck(&content);
+    let base_dir = parent_dir(&conversion.input);
+    let rendered = match conversion.format {
+        PresentationFormat::Html => render_html(&deck, &base_dir, &conversion.theme_dir)?.into_bytes(),
+        PresentationFormat::Pptx => render_pptx(&deck, &base_dir)?,
+    };
+
+    if let Some(parent) = conversion.output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&conversion.output, rendered)?;
+    println!(
+        "{} => {}",
+        conversion.input.to_string_lossy(),
+        conversion.output.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Opens the file or URL with the platform's default handler
+fn open_preview(target: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut opener = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut opener = {
+        let mut opener = Command::new("cmd");
+        opener.args(["/C", "start", ""]);
+        opener
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut opener = Command::new("xdg-open");
+
+    opener.arg(target).spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use crate::commands::presentation::{
+        get_conversions, Conversion, PresentationCommand, PresentationFormat
+    };
+
+    #[test]
+    fn should_convert_markdown_within_input_dir() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("slides/talks")).unwrap();
+        fs::write(dir.path().join("slides/intro.md"), "# Intro").unwrap();
+        fs::write(dir.path().join("slides/talks/roadmap.md"), "# Roadmap").unwrap();
+        fs::write(dir.path().join("slides/theme.css"), "/* @theme brand */").unwrap();
+
+        let command = PresentationCommand {
+            file: None,
+            output_dir: Some(dir.path().join("public")),
+            format: PresentationFormat::Html,
+            input_dir: Some(dir.path().join("slides")),
+            watch: false,
+            server: false,
+            port: 8080,
+            preview: false,
+        };
+
+        let conversions = get_conversions(&command).unwrap();
+        assert_eq!(
+            conversions,
+            vec![
+                Conversion {
+                    input: dir.path().join("slides/intro.md"),
+                    output: dir.path().join("public/intro.html"),
+                    theme_dir: dir.path().join("slides"),
+                    format: PresentationFormat::Html,
+                },
+                Conversion {
+                    input: dir.path().join("slides/talks/roadmap.md"),
+                    output: dir.path().join("public/talks/roadmap.html"),
+                    theme_dir: dir.path().join("slides"),
+                    format: PresentationFormat::Html,
+                },
+            ]
+        );
+
+        let command = PresentationCommand {
+            file: Some(PathBuf::from("deck.md")),
+            input_dir: None,
+            output_dir: None,
+            format: PresentationFormat::Pptx,
+            ..command
+        };
+        let conversions = get_conversions(&command).unwrap();
+        assert_eq!(conversions[0].output, PathBuf::from("deck.pptx"));
+        assert_eq!(conversions[0].theme_dir, PathBuf::from("."));
+    }
+}
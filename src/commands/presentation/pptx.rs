@@ -0,0 +1,1044 @@
+// Renders a deck as an Office Open XML (PPTX) presentation.
+// A PPTX file is a zip of XML parts linked together by relationship parts. We only write what
+// PowerPoint, Keynote and LibreOffice require: a single slide master, layout and theme along with a
+// notes master so speaker notes can be attached to slides.
+// https://learn.microsoft.com/en-us/openspecs/office_standards/ms-pptx
+//
+// Slides aren't laid out by a browser so Markdown is reduced to titles, text, bullet lists, code
+// and images which are stacked top to bottom. Text boxes use normAutofit so PowerPoint shrinks text
+// that doesn't fit our estimated heights.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::commands::presentation::slides::{Deck, Slide};
+use crate::doctavious_error::Result;
+
+const EMU_PER_INCH: i64 = 914_400;
+const MARGIN: i64 = EMU_PER_INCH / 2;
+const TITLE_TOP: i64 = EMU_PER_INCH * 3 / 10;
+const TITLE_HEIGHT: i64 = EMU_PER_INCH * 11 / 10;
+const CONTENT_TOP: i64 = EMU_PER_INCH * 16 / 10;
+const BLOCK_SPACING: i64 = EMU_PER_INCH / 10;
+const TEXT_LINE_HEIGHT: i64 = EMU_PER_INCH * 4 / 10;
+const CODE_LINE_HEIGHT: i64 = EMU_PER_INCH / 4;
+const BULLET_INDENT: i64 = 342_900;
+const NOTES_WIDTH: i64 = 6_858_000;
+const NOTES_HEIGHT: i64 = 9_144_000;
+
+const NAMESPACES: &str = "xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" \
+    xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\" \
+    xmlns:p=\"http://schemas.openxmlformats.org/presentationml/2006/main\"";
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+const RELATIONSHIP_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.presentationml";
+const COLOR_MAP: &str = "bg1=\"lt1\" tx1=\"dk1\" bg2=\"lt2\" tx2=\"dk2\" accent1=\"accent1\" \
+    accent2=\"accent2\" accent3=\"accent3\" accent4=\"accent4\" accent5=\"accent5\" \
+    accent6=\"accent6\" hlink=\"hlink\" folHlink=\"folHlink\"";
+const GROUP_SHAPE_PROPERTIES: &str = "<p:nvGrpSpPr><p:cNvPr id=\"1\" name=\"\"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>\
+    <p:grpSpPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"0\" cy=\"0\"/><a:chOff x=\"0\" y=\"0\"/>\
+    <a:chExt cx=\"0\" cy=\"0\"/></a:xfrm></p:grpSpPr>";
+const THEME: &str = include_str!("../../../templates/presentation/pptx/theme.xml");
+
+/// Text with the formatting we carry over from Markdown
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Run {
+    text: String,
+    bold: bool,
+    italic: bool,
+    code: bool,
+}
+
+type Paragraph = Vec<Run>;
+
+#[derive(Debug, PartialEq)]
+struct ListItem {
+    level: usize,
+    ordered: bool,
+    runs: Paragraph,
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Text(Vec<Paragraph>),
+    List(Vec<ListItem>),
+    Code(Vec<String>),
+    Image { source: String, alt: String },
+}
+
+/// Slide markdown reduced to what can be represented as PowerPoint shapes
+#[derive(Debug, Default, PartialEq)]
+struct SlideContent {
+    title: Option<Paragraph>,
+    blocks: Vec<Block>,
+    /// Image marked as the background via `![bg](image.png)`
+    background: Option<String>,
+}
+
+struct Media {
+    name: String,
+    bytes: Vec<u8>,
+    dimensions: Option<(i64, i64)>,
+}
+
+struct Relationship {
+    rel_type: &'static str,
+    target: String,
+}
+
+/// Renders the deck as a PPTX package. Relative images are resolved against `base_dir`
+pub(crate) fn render_pptx(deck: &Deck, base_dir: &Path) -> Result<Vec<u8>> {
+    let mut builder = PptxBuilder::new(deck, base_dir);
+    builder.build()
+}
+
+struct PptxBuilder<'a> {
+    deck: &'a Deck,
+    base_dir: &'a Path,
+    width: i64,
+    height: i64,
+    media: Vec<Media>,
+    media_by_path: HashMap<PathBuf, Option<usize>>,
+}
+
+impl<'a> PptxBuilder<'a> {
+    fn new(deck: &'a Deck, base_dir: &'a Path) -> Self {
+        let width = match deck.size.as_deref() {
+            Some("4:3") => 9_144_000,
+            _ => 12_192_000,
+        };
+        Self {
+            deck,
+            base_dir,
+            width,
+            height: 6_858_000,
+            media: vec![],
+            media_by_path: HashMap::new(),
+        }
+    }
+
+    fn build(&mut self) -> Result<Vec<u8>> {
+        let mut parts: Vec<(String, Vec<u8>)> = vec![];
+        let mut notes_count = 0;
+
+        let deck = self.deck;
+        for slide in &deck.slides {
+            let content = parse_slide_content(&slide.markdown);
+            let (slide_xml, mut relationships) = self.render_slide(slide, &content);
+
+            if !slide.notes.is_empty() {
+                notes_count += 1;
+                relationships.push(Relationship {
+                    rel_type: "notesSlide",
+                    target: format!("../notesSlides/notesSlide{}.xml", slide.number),
+                });
+                parts.push((
+                    format!("ppt/notesSlides/notesSlide{}.xml", slide.number),
+                    render_notes_slide(&slide.notes).into_bytes(),
+                ));
+                parts.push((
+                    format!("ppt/notesSlides/_rels/notesSlide{}.xml.rels", slide.number),
+                    render_relationships(&[
+                        Relationship {
+                            rel_type: "notesMaster",
+                            target: String::from("../notesMasters/notesMaster1.xml"),
+                        },
+                        Relationship {
+                            rel_type: "slide",
+                            target: format!("../slides/slide{}.xml", slide.number),
+                        },
+                    ]).into_bytes(),
+                ));
+            }
+
+            parts.push((format!("ppt/slides/slide{}.xml", slide.number), slide_xml.into_bytes()));
+            parts.push((
+                format!("ppt/slides/_rels/slide{}.xml.rels", slide.number),
+                render_relationships(&relationships).into_bytes(),
+            ));
+        }
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut write = |name: &str, content: &[u8]| -> Result<()> {
+            zip.start_file(name, options)?;
+            zip.write_all(content)?;
+            Ok(())
+        };
+
+        // content types must be the first entry of the package
+        write("[Content_Types].xml", self.render_content_types().as_bytes())?;
+        write("_rels/.rels", render_relationships(&[
+            Relationship { rel_type: "officeDocument", target: String::from("ppt/presentation.xml") },
+            Relationship {
+                rel_type: "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties",
+                target: String::from("docProps/core.xml"),
+            },
+            Relationship { rel_type: "extended-properties", target: String::from("docProps/app.xml") },
+        ]).as_bytes())?;
+        write("docProps/core.xml", self.render_core_properties().as_bytes())?;
+        write("docProps/app.xml", self.render_app_properties(notes_count).as_bytes())?;
+        write("ppt/presentation.xml", self.render_presentation().as_bytes())?;
+        write("ppt/_rels/presentation.xml.rels", self.render_presentation_relationships().as_bytes())?;
+        write("ppt/presProps.xml", format!("{}<p:presentationPr {}/>", XML_DECLARATION, NAMESPACES).as_bytes())?;
+        write("ppt/viewProps.xml", format!("{}<p:viewPr {}/>", XML_DECLARATION, NAMESPACES).as_bytes())?;
+        write("ppt/tableStyles.xml", format!(
+            "{}<a:tblStyleLst xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" \
+            def=\"{{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}}\"/>",
+            XML_DECLARATION
+        ).as_bytes())?;
+        write("ppt/theme/theme1.xml", THEME.as_bytes())?;
+        write("ppt/theme/theme2.xml", THEME.as_bytes())?;
+        write("ppt/slideMasters/slideMaster1.xml", self.render_slide_master().as_bytes())?;
+        write("ppt/slideMasters/_rels/slideMaster1.xml.rels", render_relationships(&[
+            Relationship { rel_type: "slideLayout", target: String::from("../slideLayouts/slideLayout1.xml") },
+            Relationship { rel_type: "theme", target: String::from("../theme/theme1.xml") },
+        ]).as_bytes())?;
+        write("ppt/slideLayouts/slideLayout1.xml", render_slide_layout().as_bytes())?;
+        write("ppt/slideLayouts/_rels/slideLayout1.xml.rels", render_relationships(&[
+            Relationship { rel_type: "slideMaster", target: String::from("../slideMasters/slideMaster1.xml") },
+        ]).as_bytes())?;
+        write("ppt/notesMasters/notesMaster1.xml", render_notes_master().as_bytes())?;
+        write("ppt/notesMasters/_rels/notesMaster1.xml.rels", render_relationships(&[
+            Relationship { rel_type: "theme", target: String::from("../theme/theme2.xml") },
+        ]).as_bytes())?;
+        for (name, content) in &parts {
+            write(name, content)?;
+        }
+        for media in &self.media {
+            write(&format!("ppt/media/{}", media.name), &media.bytes)?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    }
+
+    fn render_content_types(&self) -> String {
+        let mut xml = format!(
+            "{}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+            <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+            <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+            <Default Extension=\"png\" ContentType=\"image/png\"/>\
+            <Default Extension=\"jpeg\" ContentType=\"image/jpeg\"/>\
+            <Default Extension=\"gif\" ContentType=\"image/gif\"/>",
+            XML_DECLARATION
+        );
+
+        let mut overrides = vec![
+            ("/docProps/core.xml", String::from("application/vnd.openxmlformats-package.core-properties+xml")),
+            ("/docProps/app.xml", String::from("application/vnd.openxmlformats-officedocument.extended-properties+xml")),
+            ("/ppt/presentation.xml", format!("{}.presentation.main+xml", CONTENT_TYPE)),
+            ("/ppt/presProps.xml", format!("{}.presProps+xml", CONTENT_TYPE)),
+            ("/ppt/viewProps.xml", format!("{}.viewProps+xml", CONTENT_TYPE)),
+            ("/ppt/tableStyles.xml", format!("{}.tableStyles+xml", CONTENT_TYPE)),
+            ("/ppt/theme/theme1.xml", String::from("application/vnd.openxmlformats-officedocument.theme+xml")),
+            ("/ppt/theme/theme2.xml", String::from("application/vnd.openxmlformats-officedocument.theme+xml")),
+            ("/ppt/slideMasters/slideMaster1.xml", format!("{}.slideMaster+xml", CONTENT_TYPE)),
+            ("/ppt/slideLayouts/slideLayout1.xml", format!("{}.slideLayout+xml", CONTENT_TYPE)),
+            ("/ppt/notesMasters/notesMaster1.xml", format!("{}.notesMaster+xml", CONTENT_TYPE)),
+        ].into_iter()
+            .map(|(name, content_type)| (name.to_string(), content_type))
+            .collect::<Vec<(String, String)>>();
+
+        for slide in &self.deck.slides {
+            overrides.push((
+                format!("/ppt/slides/slide{}.xml", slide.number),
+                format!("{}.slide+xml", CONTENT_TYPE),
+            ));
+            if !slide.notes.is_empty() {
+                overrides.push((
+                    format!("/ppt/notesSlides/notesSlide{}.xml", slide.number),
+                    format!("{}.notesSlide+xml", CONTENT_TYPE),
+                ));
+            }
+        }
+        for (name, content_type) in overrides {
+            xml.push_str(&format!("<Override PartName=\"{}\" ContentType=\"{}\"/>", name, content_type));
+        }
+        xml.push_str("</Types>");
+        xml
+    }
+
+    fn render_core_properties(&self) -> String {
+        let title = self.deck.title.clone().unwrap_or_else(|| {
+            self.deck.slides
+                .iter()
+                .find_map(|s| parse_slide_content(&s.markdown).title)
+                .map(|runs| paragraph_text(&runs))
+                .unwrap_or_default()
+        });
+        format!(
+            "{}<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" \
+            xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" \
+            xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\
+            <dc:title>{}</dc:title><dc:creator>Doctavious</dc:creator></cp:coreProperties>",
+            XML_DECLARATION,
+            escape_xml(&title)
+        )
+    }
+
+    fn render_app_properties(&self, notes_count: usize) -> String {
+        format!(
+            "{}<Properties xmlns=\"http://schemas.openxmlformats.org/officeDocument/2006/extended-properties\">\
+            <Application>Doctavious</Application><Slides>{}</Slides><Notes>{}</Notes></Properties>",
+            XML_DECLARATION,
+            self.deck.slides.len(),
+            notes_count
+        )
+    }
+
+    // relationship ids: rId1 slide master, rId2 notes master, rId3.. slides followed by the
+    // presentation properties and theme
+    fn render_presentation(&self) -> String {
+        let mut slide_ids = String::new();
+        for (index, _) in self.deck.slides.iter().enumerate() {
+            slide_ids.push_str(&format!("<p:sldId id=\"{}\" r:id=\"rId{}\"/>", 256 + index, index + 3));
+        }
+
+        format!(
+            "{}<p:presentation {} saveSubsetFonts=\"1\">\
+            <p:sldMasterIdLst><p:sldMasterId id=\"2147483648\" r:id=\"rId1\"/></p:sldMasterIdLst>\
+            <p:notesMasterIdLst><p:notesMasterId r:id=\"rId2\"/></p:notesMasterIdLst>\
+            <p:sldIdLst>{}</p:sldIdLst>\
+            <p:sldSz cx=\"{}\" cy=\"{}\"/><p:notesSz cx=\"{}\" cy=\"{}\"/>\
+            </p:presentation>",
+            XML_DECLARATION, NAMESPACES, slide_ids, self.width, self.height, NOTES_WIDTH, NOTES_HEIGHT
+        )
+    }
+
+    fn render_presentation_relationships(&self) -> String {
+        let mut relationships = vec![
+            Relationship { rel_type: "slideMaster", target: String::from("slideMasters/slideMaster1.xml") },
+            Relationship { rel_type: "notesMaster", target: String::from("notesMasters/notesMaster1.xml") },
+        ];
+        for slide in &self.deck.slides {
+            relationships.push(Relationship {
+                rel_type: "slide",
+                target: format!("slides/slide{}.xml", slide.number),
+            });
+        }
+        relationships.push(Relationship { rel_type: "presProps", target: String::from("presProps.xml") });
+        relationships.push(Relationship { rel_type: "viewProps", target: String::from("viewProps.xml") });
+        relationships.push(Relationship { rel_type: "theme", target: String::from("theme/theme1.xml") });
+        relationships.push(Relationship { rel_type: "tableStyles", target: String::from("tableStyles.xml") });
+        render_relationships(&relationships)
+    }
+
+    fn render_slide_master(&self) -> String {
+        let content_width = self.width - 2 * MARGIN;
+        let body_height = self.height - CONTENT_TOP - MARGIN;
+        let text_style = |size: u32, bold: bool, font: &str| {
+            format!(
+                "<a:defRPr sz=\"{}\"{}><a:solidFill><a:schemeClr val=\"tx1\"/></a:solidFill>\
+                <a:latin typeface=\"{}\"/></a:defRPr>",
+                size,
+                if bold { " b=\"1\"" } else { "" },
+                font
+            )
+        };
+
+        format!(
+            "{}<p:sldMaster {}><p:cSld><p:bg><p:bgRef idx=\"1001\"><a:schemeClr val=\"bg1\"/></p:bgRef></p:bg>\
+            <p:spTree>{}{}{}</p:spTree></p:cSld>\
+            <p:clrMap {}/>\
+            <p:sldLayoutIdLst><p:sldLayoutId id=\"2147483649\" r:id=\"rId1\"/></p:sldLayoutIdLst>\
+            <p:txStyles>\
+            <p:titleStyle><a:lvl1pPr algn=\"l\">{}</a:lvl1pPr></p:titleStyle>\
+            <p:bodyStyle><a:lvl1pPr marL=\"{}\" indent=\"-{}\"><a:buFont typeface=\"Arial\"/><a:buChar char=\"&#8226;\"/>{}</a:lvl1pPr></p:bodyStyle>\
+            <p:otherStyle><a:lvl1pPr>{}</a:lvl1pPr></p:otherStyle>\
+            </p:txStyles></p:sldMaster>",
+            XML_DECLARATION,
+            NAMESPACES,
+            GROUP_SHAPE_PROPERTIES,
+            placeholder_shape(2, "Title Placeholder 1", "type=\"title\"", Some((MARGIN, TITLE_TOP, content_width, TITLE_HEIGHT))),
+            placeholder_shape(3, "Text Placeholder 2", "type=\"body\" idx=\"1\"", Some((MARGIN, CONTENT_TOP, content_width, body_height))),
+            COLOR_MAP,
+            text_style(4000, true, "+mj-lt"),
+            BULLET_INDENT,
+            BULLET_INDENT,
+            text_style(2400, false, "+mn-lt"),
+            text_style(1800, false, "+mn-lt"),
+        )
+    }
+
+    /// Renders the slide returning its XML along with its relationships
+    fn render_slide(&mut self, slide: &Slide, content: &SlideContent) -> (String, Vec<Relationship>) {
+        let directives = &slide.directives;
+        let color = directives.color.as_deref().and_then(hex_color);
+        let mut relationships = vec![Relationship {
+            rel_type: "slideLayout",
+            target: String::from("../slideLayouts/slideLayout1.xml"),
+        }];
+        let mut shapes = String::new();
+        let mut shape_id = 1;
+        let mut next_id = || {
+            shape_id += 1;
+            shape_id
+        };
+
+        if let Some(media) = content.background.as_ref().and_then(|s| self.add_media(s)) {
+            relationships.push(Relationship {
+                rel_type: "image",
+                target: format!("../media/{}", self.media[media].name),
+            });
+            shapes.push_str(&picture_shape(
+                next_id(), "", relationships.len(), (0, 0, self.width, self.height)
+            ));
+        }
+
+        if let Some(title) = &content.title {
+            let id = next_id();
+            shapes.push_str(&format!(
+                "<p:sp><p:nvSpPr><p:cNvPr id=\"{}\" name=\"Title {}\"/><p:cNvSpPr><a:spLocks noGrp=\"1\"/></p:cNvSpPr>\
+                <p:nvPr><p:ph type=\"title\"/></p:nvPr></p:nvSpPr><p:spPr/>\
+                <p:txBody><a:bodyPr><a:normAutofit/></a:bodyPr><a:lstStyle/><a:p>{}</a:p></p:txBody></p:sp>",
+                id,
+                id - 1,
+                render_runs(title, None, color.as_deref())
+            ));
+        }
+
+        let content_width = self.width - 2 * MARGIN;
+        let mut top = if content.title.is_some() { CONTENT_TOP } else { MARGIN };
+        let bottom = self.height - MARGIN;
+
+        // text is sized by estimating how many lines it wraps to with images sharing what remains
+        let text_heights: Vec<Option<i64>> = content.blocks
+            .iter()
+            .map(|b| estimate_height(b, content_width))
+            .collect();
+        let image_count = text_heights.iter().filter(|h| h.is_none()).count() as i64;
+        let text_total: i64 = text_heights.iter().flatten().sum::<i64>()
+            + BLOCK_SPACING * (content.blocks.len() as i64 - 1).max(0);
+        let image_height = if image_count > 0 {
+            ((bottom - top - text_total) / image_count).max(EMU_PER_INCH)
+        } else {
+            0
+        };
+
+        for (block, height) in content.blocks.iter().zip(text_heights) {
+            match block {
+                Block::Text(paragraphs) => {
+                    let body: String = paragraphs
+                        .iter()
+                        .map(|p| format!("<a:p>{}</a:p>", render_runs(p, Some(2000), color.as_deref())))
+                        .collect();
+                    shapes.push_str(&text_box(next_id(), (MARGIN, top, content_width, height.unwrap_or(0)), false, &body));
+                }
+                Block::List(items) => {
+                    let body: String = items
+                        .iter()
+                        .map(|item| {
+                            let bullet = if item.ordered {
+                                String::from("<a:buAutoNum type=\"arabicPeriod\"/>")
+                            } else {
+                                String::from("<a:buFont typeface=\"Arial\"/><a:buChar char=\"&#8226;\"/>")
+                            };
+                            format!(
+                                "<a:p><a:pPr marL=\"{}\" lvl=\"{}\" indent=\"-{}\">{}</a:pPr>{}</a:p>",
+                                BULLET_INDENT * (item.level as i64 + 1),
+                                item.level,
+                                BULLET_INDENT,
+                                bullet,
+                                render_runs(&item.runs, Some(2000), color.as_deref())
+                            )
+                        })
+                        .collect();
+                    shapes.push_str(&text_box(next_id(), (MARGIN, top, content_width, height.unwrap_or(0)), false, &body));
+                }
+                Block::Code(lines) => {
+                    let body: String = lines
+                        .iter()
+                        .map(|line| {
+                            let run = Run { text: line.to_string(), code: true, ..Run::default() };
+                            format!("<a:p>{}</a:p>", render_runs(&[run], Some(1400), None))
+                        })
+                        .collect();
+                    shapes.push_str(&text_box(next_id(), (MARGIN, top, content_width, height.unwrap_or(0)), true, &body));
+                }
+                Block::Image { source, alt } => {
+                    if let Some(media) = self.add_media(source) {
+                        let (width, height) = fit(
+                            self.media[media].dimensions, content_width, image_height
+                        );
+                        relationships.push(Relationship {
+                            rel_type: "image",
+                            target: format!("../media/{}", self.media[media].name),
+                        });
+                        let left = MARGIN + (content_width - width) / 2;
+                        shapes.push_str(&picture_shape(
+                            next_id(), alt, relationships.len(), (left, top, width, height)
+                        ));
+                        top += height + BLOCK_SPACING;
+                    }
+                    continue;
+                }
+            }
+            top += height.unwrap_or(0) + BLOCK_SPACING;
+        }
+
+        let small_text = |text: &str| vec![Run { text: text.to_string(), ..Run::default() }];
+        let small_height = EMU_PER_INCH * 4 / 10;
+        if let Some(header) = &directives.header {
+            let body = format!("<a:p>{}</a:p>", render_runs(&small_text(header), Some(1200), color.as_deref()));
+            shapes.push_str(&text_box(next_id(), (MARGIN, EMU_PER_INCH / 10, content_width, small_height), false, &body));
+        }
+        if let Some(footer) = &directives.footer {
+            let body = format!("<a:p>{}</a:p>", render_runs(&small_text(footer), Some(1200), color.as_deref()));
+            shapes.push_str(&text_box(next_id(), (MARGIN, self.height - small_height - EMU_PER_INCH / 10, content_width, small_height), false, &body));
+        }
+        if directives.paginate {
+            let body = format!(
+                "<a:p><a:pPr algn=\"r\"/>{}</a:p>",
+                render_runs(&small_text(&slide.number.to_string()), Some(1200), color.as_deref())
+            );
+            shapes.push_str(&text_box(next_id(), (self.width - MARGIN - EMU_PER_INCH, self.height - small_height - EMU_PER_INCH / 10, EMU_PER_INCH, small_height), false, &body));
+        }
+
+        let background = directives.background_color
+            .as_deref()
+            .and_then(hex_color)
+            .map(|c| format!(
+                "<p:bg><p:bgPr><a:solidFill><a:srgbClr val=\"{}\"/></a:solidFill><a:effectLst/></p:bgPr></p:bg>",
+                c
+            ))
+            .unwrap_or_default();
+
+        let xml = format!(
+            "{}<p:sld {}><p:cSld>{}<p:spTree>{}{}</p:spTree></p:cSld>\
+            <p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>",
+            XML_DECLARATION, NAMESPACES, background, GROUP_SHAPE_PROPERTIES, shapes
+        );
+
+        (xml, relationships)
+    }
+
+    /// Adds the image to the package returning its index. Each file is only embedded once no
+    /// matter how many slides it appears on
+    fn add_media(&mut self, source: &str) -> Option<usize> {
+        if source.contains("://") || source.starts_with("data:") || source.starts_with("//") {
+            eprintln!("skipping remote image {}. Only local images can be embedded in PPTX", source);
+            return None;
+        }
+
+        let path = self.base_dir.join(source);
+        if let Some(index) = self.media_by_path.get(&path) {
+            return *index;
+        }
+
+        let index = match fs::read(&path) {
+            Ok(bytes) => match image_extension(&bytes) {
+                Some(extension) => {
+                    self.media.push(Media {
+                        name: format!("image{}.{}", self.media.len() + 1, extension),
+                        dimensions: image_dimensions(&bytes),
+                        bytes,
+                    });
+                    Some(self.media.len() - 1)
+                }
+                None => {
+                    eprintln!("skipping image {}. Only PNG, JPEG and GIF are supported", path.to_string_lossy());
+                    None
+                }
+            },
+            Err(e) => {
+                eprintln!("unable to embed image {}: {}", path.to_string_lossy(), e);
+                None
+            }
+        };
+
+        self.media_by_path.insert(path, index);
+        index
+    }
+}
+
+/// Reduces slide markdown to a title, the first heading, followed by content blocks
+fn parse_slide_content(markdown: &str) -> SlideContent {
+    let mut content = SlideContent::default();
+    let mut runs: Paragraph = vec![];
+    let mut bold = 0;
+    let mut italic = 0;
+    let mut lists: Vec<bool> = vec![];
+    let mut items: Vec<ListItem> = vec![];
+    let mut code: Option<String> = None;
+    let mut image: Option<(String, String)> = None;
+
+    fn flush_item(runs: &mut Paragraph, lists: &[bool], items: &mut Vec<ListItem>) {
+        if runs.iter().any(|r| !r.text.trim().is_empty()) {
+            items.push(ListItem {
+                level: lists.len().saturating_sub(1),
+                ordered: lists.last().copied().unwrap_or(false),
+                runs: std::mem::take(runs),
+            });
+        }
+        runs.clear();
+    }
+
+    fn flush_text(runs: &mut Paragraph, blocks: &mut Vec<Block>) {
+        if runs.iter().any(|r| !r.text.trim().is_empty()) {
+            let paragraph = std::mem::take(runs);
+            match blocks.last_mut() {
+                Some(Block::Text(paragraphs)) => paragraphs.push(paragraph),
+                _ => blocks.push(Block::Text(vec![paragraph])),
+            }
+        }
+        runs.clear();
+    }
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::Heading(..)) | Event::Start(Tag::Paragraph) if lists.is_empty() => {
+                runs.clear();
+            }
+            Event::End(Tag::Heading(..)) => {
+                if content.title.is_none() && runs.iter().any(|r| !r.text.trim().is_empty()) {
+                    content.title = Some(std::mem::take(&mut runs));
+                } else {
+                    for run in runs.iter_mut() {
+                        run.bold = true;
+                    }
+                    flush_text(&mut runs, &mut content.blocks);
+                }
+            }
+            Event::End(Tag::Paragraph) => {
+                if lists.is_empty() {
+                    flush_text(&mut runs, &mut content.blocks);
+                } else {
+                    flush_item(&mut runs, &lists, &mut items);
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                // text of the parent item precedes its nested list
+                flush_item(&mut runs, &lists, &mut items);
+                lists.push(start.is_some());
+            }
+            Event::End(Tag::List(_)) => {
+                lists.pop();
+                if lists.is_empty() && !items.is_empty() {
+                    content.blocks.push(Block::List(std::mem::take(&mut items)));
+                }
+            }
+            Event::End(Tag::Item) => flush_item(&mut runs, &lists, &mut items),
+            Event::Start(Tag::CodeBlock(_)) => code = Some(String::new()),
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(code) = code.take() {
+                    let lines = code.trim_end_matches('\n').lines().map(String::from).collect();
+                    content.blocks.push(Block::Code(lines));
+                }
+            }
+            Event::Start(Tag::Image(_, dest, _)) => image = Some((dest.to_string(), String::new())),
+            Event::End(Tag::Image(..)) => {
+                if let Some((source, alt)) = image.take() {
+                    if alt.split_whitespace().next() == Some("bg") {
+                        content.background = Some(source);
+                    } else {
+                        if lists.is_empty() {
+                            flush_text(&mut runs, &mut content.blocks);
+                        }
+                        content.blocks.push(Block::Image { source, alt });
+                    }
+                }
+            }
+            Event::Start(Tag::Strong) => bold += 1,
+            Event::End(Tag::Strong) => bold -= 1,
+            Event::Start(Tag::Emphasis) => italic += 1,
+            Event::End(Tag::Emphasis) => italic -= 1,
+            Event::Text(text) | Event::Code(text) if image.is_some() => {
+                if let Some((_, alt)) = image.as_mut() {
+                    alt.push_str(&text);
+                }
+            }
+            Event::Text(text) if code.is_some() => {
+                if let Some(code) = code.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::Text(text) => runs.push(Run {
+                text: text.to_string(),
+                bold: bold > 0,
+                italic: italic > 0,
+                code: false,
+            }),
+            Event::Code(text) => runs.push(Run {
+                text: text.to_string(),
+                bold: bold > 0,
+                italic: italic > 0,
+                code: true,
+            }),
+            Event::SoftBreak | Event::HardBreak => runs.push(Run {
+                text: String::from(" "),
+                ..Run::default()
+            }),
+            _ => {}
+        }
+    }
+
+    content
+}
+
+/// Height of text blocks. None for images which share the remaining space
+fn estimate_height(block: &Block, width: i64) -> Option<i64> {
+    // roughly 7 characters per inch at 20pt
+    let lines = |text: &str, indent: i64| {
+        let chars_per_line = ((width - indent) * 7 / EMU_PER_INCH).max(1) as usize;
+        ((text.chars().count() + chars_per_line - 1) / chars_per_line).max(1) as i64
+    };
+
+    match block {
+        Block::Text(paragraphs) => Some(
+            paragraphs.iter().map(|p| lines(&paragraph_text(p), 0)).sum::<i64>() * TEXT_LINE_HEIGHT
+                + BLOCK_SPACING
+        ),
+        Block::List(items) => Some(
+            items
+                .iter()
+                .map(|i| lines(&paragraph_text(&i.runs), BULLET_INDENT * (i.level as i64 + 1)))
+                .sum::<i64>() * TEXT_LINE_HEIGHT
+                + BLOCK_SPACING
+        ),
+        Block::Code(code) => Some(code.len().max(1) as i64 * CODE_LINE_HEIGHT + 2 * BLOCK_SPACING),
+        Block::Image { .. } => None,
+    }
+}
+
+/// Scales the image to fit within the bounds preserving its aspect ratio
+fn fit(dimensions: Option<(i64, i64)>, max_width: i64, max_height: i64) -> (i64, i64) {
+    let (width, height) = dimensions.filter(|(w, h)| *w > 0 && *h > 0).unwrap_or((4, 3));
+    if width * max_height > height * max_width {
+        (max_width, max_width * height / width)
+    } else {
+        (max_height * width / height, max_height)
+    }
+}
+
+fn paragraph_text(runs: &[Run]) -> String {
+    runs.iter().map(|r| r.text.as_str()).collect::<String>().trim().to_string()
+}
+
+fn render_runs(runs: &[Run], size: Option<u32>, color: Option<&str>) -> String {
+    let mut xml = String::new();
+    for run in runs {
+        let mut attributes = String::from(" lang=\"en-US\"");
+        if let Some(size) = size {
+            attributes.push_str(&format!(" sz=\"{}\"", size));
+        }
+        if run.bold {
+            attributes.push_str(" b=\"1\"");
+        }
+        if run.italic {
+            attributes.push_str(" i=\"1\"");
+        }
+
+        let mut properties = String::new();
+        if let Some(color) = color {
+            properties.push_str(&format!("<a:solidFill><a:srgbClr val=\"{}\"/></a:solidFill>", color));
+        }
+        if run.code {
+            properties.push_str("<a:latin typeface=\"Courier New\"/>");
+        }
+
+        xml.push_str(&format!(
+            "<a:r><a:rPr{} dirty=\"0\">{}</a:rPr><a:t>{}</a:t></a:r>",
+            attributes,
+            properties,
+            escape_xml(&run.text)
+        ));
+    }
+    xml
+}
+
+fn text_box(id: u32, (x, y, cx, cy): (i64, i64, i64, i64), filled: bool, body: &str) -> String {
+    let fill = if filled {
+        "<a:solidFill><a:srgbClr val=\"F6F8FA\"/></a:solidFill>"
+    } else {
+        "<a:noFill/>"
+    };
+    format!(
+        "<p:sp><p:nvSpPr><p:cNvPr id=\"{}\" name=\"TextBox {}\"/><p:cNvSpPr txBox=\"1\"/><p:nvPr/></p:nvSpPr>\
+        <p:spPr><a:xfrm><a:off x=\"{}\" y=\"{}\"/><a:ext cx=\"{}\" cy=\"{}\"/></a:xfrm>\
+        <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom>{}</p:spPr>\
+        <p:txBody><a:bodyPr wrap=\"square\" rtlCol=\"0\"><a:normAutofit/></a:bodyPr><a:lstStyle/>{}</p:txBody></p:sp>",
+        id, id - 1, x, y, cx, cy, fill, body
+    )
+}
+
+fn picture_shape(id: u32, description: &str, relationship: usize, (x, y, cx, cy): (i64, i64, i64, i64)) -> String {
+    format!(
+        "<p:pic><p:nvPicPr><p:cNvPr id=\"{}\" name=\"Picture {}\" descr=\"{}\"/>\
+        <p:cNvPicPr><a:picLocks noChangeAspect=\"1\"/></p:cNvPicPr><p:nvPr/></p:nvPicPr>\
+        <p:blipFill><a:blip r:embed=\"rId{}\"/><a:stretch><a:fillRect/></a:stretch></p:blipFill>\
+        <p:spPr><a:xfrm><a:off x=\"{}\" y=\"{}\"/><a:ext cx=\"{}\" cy=\"{}\"/></a:xfrm>\
+        <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></p:spPr></p:pic>",
+        id, id - 1, escape_xml(description), relationship, x, y, cx, cy
+    )
+}
+
+fn placeholder_shape(id: u32, name: &str, placeholder: &str, position: Option<(i64, i64, i64, i64)>) -> String {
+    let properties = match position {
+        Some((x, y, cx, cy)) => format!(
+            "<p:spPr><a:xfrm><a:off x=\"{}\" y=\"{}\"/><a:ext cx=\"{}\" cy=\"{}\"/></a:xfrm>\
+            <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></p:spPr>",
+            x, y, cx, cy
+        ),
+        None => String::from("<p:spPr/>"),
+    };
+    format!(
+        "<p:sp><p:nvSpPr><p:cNvPr id=\"{}\" name=\"{}\"/><p:cNvSpPr><a:spLocks noGrp=\"1\"/></p:cNvSpPr>\
+        <p:nvPr><p:ph {}/></p:nvPr></p:nvSpPr>{}\
+        <p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:endParaRPr lang=\"en-US\"/></a:p></p:txBody></p:sp>",
+        id, name, placeholder, properties
+    )
+}
+
+fn render_slide_layout() -> String {
+    format!(
+        "{}<p:sldLayout {} type=\"obj\" preserve=\"1\"><p:cSld name=\"Title and Content\"><p:spTree>{}{}{}</p:spTree></p:cSld>\
+        <p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sldLayout>",
+        XML_DECLARATION,
+        NAMESPACES,
+        GROUP_SHAPE_PROPERTIES,
+        placeholder_shape(2, "Title 1", "type=\"title\"", None),
+        placeholder_shape(3, "Content Placeholder 2", "idx=\"1\"", None),
+    )
+}
+
+fn render_notes_master() -> String {
+    format!(
+        "{}<p:notesMaster {}><p:cSld><p:bg><p:bgRef idx=\"1001\"><a:schemeClr val=\"bg1\"/></p:bgRef></p:bg>\
+        <p:spTree>{}{}{}</p:spTree></p:cSld><p:clrMap {}/></p:notesMaster>",
+        XML_DECLARATION,
+        NAMESPACES,
+        GROUP_SHAPE_PROPERTIES,
+        placeholder_shape(2, "Slide Image Placeholder 1", "type=\"sldImg\" idx=\"2\"", Some((381_000, 685_800, 6_096_000, 3_429_000))),
+        placeholder_shape(3, "Notes Placeholder 2", "type=\"body\" sz=\"quarter\" idx=\"3\"", Some((685_800, 4_400_550, 5_486_400, 3_600_450))),
+        COLOR_MAP,
+    )
+}
+
+fn render_notes_slide(notes: &[String]) -> String {
+    let paragraphs: String = notes
+        .iter()
+        .flat_map(|n| n.lines())
+        .map(|line| {
+            let run = Run { text: line.to_string(), ..Run::default() };
+            format!("<a:p>{}</a:p>", render_runs(&[run], None, None))
+        })
+        .collect();
+
+    format!(
+        "{}<p:notes {}><p:cSld><p:spTree>{}\
+        <p:sp><p:nvSpPr><p:cNvPr id=\"2\" name=\"Slide Image Placeholder 1\"/>\
+        <p:cNvSpPr><a:spLocks noGrp=\"1\" noRot=\"1\" noChangeAspect=\"1\"/></p:cNvSpPr>\
+        <p:nvPr><p:ph type=\"sldImg\"/></p:nvPr></p:nvSpPr><p:spPr/></p:sp>\
+        <p:sp><p:nvSpPr><p:cNvPr id=\"3\" name=\"Notes Placeholder 2\"/><p:cNvSpPr><a:spLocks noGrp=\"1\"/></p:cNvSpPr>\
+        <p:nvPr><p:ph type=\"body\" idx=\"1\"/></p:nvPr></p:nvSpPr><p:spPr/>\
+        <p:txBody><a:bodyPr/><a:lstStyle/>{}</p:txBody></p:sp>\
+        </p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:notes>",
+        XML_DECLARATION, NAMESPACES, GROUP_SHAPE_PROPERTIES, paragraphs
+    )
+}
+
+/// Relationship part where relationship ids are the 1-based position of each relationship
+fn render_relationships(relationships: &[Relationship]) -> String {
+    let mut xml = format!(
+        "{}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+        XML_DECLARATION
+    );
+    for (index, relationship) in relationships.iter().enumerate() {
+        let rel_type = if relationship.rel_type.contains("://") {
+            relationship.rel_type.to_string()
+        } else {
+            format!("{}/{}", RELATIONSHIP_TYPE, relationship.rel_type)
+        };
+        xml.push_str(&format!(
+            "<Relationship Id=\"rId{}\" Type=\"{}\" Target=\"{}\"/>",
+            index + 1,
+            rel_type,
+            escape_xml(&relationship.target)
+        ));
+    }
+    xml.push_str("</Relationships>");
+    xml
+}
+
+/// Normalises `#rgb` and `#rrggbb` colors to the `RRGGBB` form used by DrawingML
+fn hex_color(color: &str) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        3 => Some(hex.chars().flat_map(|c| vec![c, c]).collect::<String>().to_uppercase()),
+        6 => Some(hex.to_uppercase()),
+        _ => None,
+    }
+}
+
+fn image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG") {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        Some("jpeg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else {
+        None
+    }
+}
+
+/// Width and height, in pixels, read from the PNG, GIF or JPEG header
+fn image_dimensions(bytes: &[u8]) -> Option<(i64, i64)> {
+    let be_u16 = |i: usize| i64::from(u16::from_be_bytes([bytes[i], bytes[i + 1]]));
+
+    match image_extension(bytes)? {
+        "png" if bytes.len() >= 24 => Some((
+            i64::from(u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]])),
+            i64::from(u32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]])),
+        )),
+        "gif" if bytes.len() >= 10 => Some((
+            i64::from(u16::from_le_bytes([bytes[6], bytes[7]])),
+            i64::from(u16::from_le_bytes([bytes[8], bytes[9]])),
+        )),
+        "jpeg" => {
+            // walk the segments until a start of frame marker which holds the dimensions
+            let mut i = 2;
+            while i + 9 < bytes.len() && bytes[i] == 0xFF {
+                let marker = bytes[i + 1];
+                if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                    return Some((be_u16(i + 7), be_u16(i + 5)));
+                }
+                i += 2 + be_u16(i + 2) as usize;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // characters not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !['\t', '\n', '\r'].contains(&c) => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{Cursor, Read};
+
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    use crate::commands::presentation::pptx::{
+        parse_slide_content, render_pptx, Block, ListItem, Run
+    };
+    use crate::commands::presentation::slides::parse_deck;
+
+    fn read_part(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+        let mut content = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
+
+    fn text(text: &str) -> Run {
+        Run { text: text.to_string(), ..Run::default() }
+    }
+
+    #[test]
+    fn should_parse_slide_content() {
+        let content = parse_slide_content("# Roadmap\n\n\
+            Where we are **going**\n\n\
+            - Build\n  - Cache\n- Deploy\n\n\
+            1. Plan\n\n\
+            ![bg](background.png)\n\n\
+            ![Architecture](architecture.png)\n");
+
+        assert_eq!(content.title, Some(vec![text("Roadmap")]));
+        assert_eq!(content.background, Some(String::from("background.png")));
+        assert_eq!(content.blocks, vec![
+            Block::Text(vec![vec![text("Where we are "), Run { text: String::from("going"), bold: true, ..Run::default() }]]),
+            Block::List(vec![
+                ListItem { level: 0, ordered: false, runs: vec![text("Build")] },
+                ListItem { level: 1, ordered: false, runs: vec![text("Cache")] },
+                ListItem { level: 0, ordered: false, runs: vec![text("Deploy")] },
+            ]),
+            Block::List(vec![ListItem { level: 0, ordered: true, runs: vec![text("Plan")] }]),
+            Block::Image { source: String::from("architecture.png"), alt: String::from("Architecture") },
+        ]);
+    }
+
+    #[test]
+    fn should_render_pptx_package() {
+        let dir = TempDir::new().unwrap();
+        // 1x1 PNG header, enough for the dimensions to be read
+        let png = [
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D, 0x49, 0x48, 0x44, 0x52,
+            0, 0, 0, 1, 0, 0, 0, 1,
+        ];
+        fs::write(dir.path().join("diagram.png"), png).unwrap();
+
+        let deck = parse_deck("---\n\
+            paginate: true\n\
+            ---\n\
+            # Doctavious & Friends\n\
+            \n\
+            - Presentations\n\
+            - Changelogs\n\
+            \n\
+            <!-- Thank the team -->\n\
+            \n\
+            ---\n\
+            ## Architecture\n\
+            \n\
+            ![Diagram](diagram.png)\n");
+
+        let bytes = render_pptx(&deck, dir.path()).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+        let content_types = read_part(&mut archive, "[Content_Types].xml");
+        assert!(content_types.contains("<Override PartName=\"/ppt/slides/slide2.xml\""));
+        assert!(content_types.contains("<Override PartName=\"/ppt/notesSlides/notesSlide1.xml\""));
+        assert!(!content_types.contains("notesSlide2.xml"));
+
+        let presentation = read_part(&mut archive, "ppt/presentation.xml");
+        assert!(presentation.contains("<p:sldId id=\"256\" r:id=\"rId3\"/><p:sldId id=\"257\" r:id=\"rId4\"/>"));
+        assert!(presentation.contains("<p:sldSz cx=\"12192000\" cy=\"6858000\"/>"));
+
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert!(slide.contains("<p:ph type=\"title\"/>"));
+        assert!(slide.contains("<a:t>Doctavious &amp; Friends</a:t>"));
+        assert!(slide.contains("<a:buChar char=\"&#8226;\"/></a:pPr><a:r><a:rPr lang=\"en-US\" sz=\"2000\" dirty=\"0\"></a:rPr><a:t>Presentations</a:t></a:r>"));
+        assert!(slide.contains("<a:t>Changelogs</a:t>"));
+        assert!(slide.contains("<a:t>1</a:t>"));
+
+        let slide_relationships = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+        assert!(slide_relationships.contains("Target=\"../notesSlides/notesSlide1.xml\""));
+        let notes = read_part(&mut archive, "ppt/notesSlides/notesSlide1.xml");
+        assert!(notes.contains("<a:t>Thank the team</a:t>"));
+
+        let slide = read_part(&mut archive, "ppt/slides/slide2.xml");
+        assert!(slide.contains("<a:t>Architecture</a:t>"));
+        assert!(slide.contains("descr=\"Diagram\""));
+        assert!(slide.contains("<a:blip r:embed=\"rId2\"/>"));
+        let slide_relationships = read_part(&mut archive, "ppt/slides/_rels/slide2.xml.rels");
+        assert!(slide_relationships.contains("<Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/image\" Target=\"../media/image1.png\"/>"));
+        assert!(archive.by_name("ppt/media/image1.png").is_ok());
+        assert!(archive.by_name("ppt/notesSlides/notesSlide2.xml").is_err());
+    }
+}
@@ -0,0 +1,282 @@
+// Marp compatible slide model
+// Markdown is split into slides on `---` with directives, from the frontmatter and HTML comments,
+// applied to each slide the same way as Marpit
+// https://marpit.marp.app/directives
+//
+// Global directives (theme, style, size, title) apply to the whole deck. Local directives apply to
+// the slide they are defined on and all following slides unless prefixed with an underscore, a
+// "spot" directive, which only applies to the current slide.
+// HTML comments that aren't directives become speaker notes.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+
+lazy_static! {
+    static ref COMMENT_RE: Regex = Regex::new(r"(?s)<!--(.*?)-->").unwrap();
+}
+
+const GLOBAL_DIRECTIVES: &[&str] = &["marp", "theme", "style", "size", "title", "headingDivider"];
+const LOCAL_DIRECTIVES: &[&str] = &[
+    "paginate", "class", "header", "footer", "backgroundColor", "backgroundImage", "color"
+];
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Deck {
+    pub theme: Option<String>,
+    /// Additional CSS from the style directive
+    pub style: Option<String>,
+    /// Aspect ratio of the slides, either 16:9 or 4:3. Defaults to 16:9
+    pub size: Option<String>,
+    pub title: Option<String>,
+    pub slides: Vec<Slide>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Slide {
+    /// 1-based number of the slide
+    pub number: usize,
+    /// Markdown of the slide with directives and notes removed
+    pub markdown: String,
+    pub notes: Vec<String>,
+    pub directives: SlideDirectives,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SlideDirectives {
+    pub paginate: bool,
+    pub class: Option<String>,
+    pub header: Option<String>,
+    pub footer: Option<String>,
+    pub background_color: Option<String>,
+    pub background_image: Option<String>,
+    pub color: Option<String>,
+}
+
+impl SlideDirectives {
+    fn apply(&mut self, name: &str, value: &Value) {
+        match name {
+            "paginate" => self.paginate = value.as_bool().unwrap_or(false),
+            "class" => self.class = value_to_string(value),
+            "header" => self.header = value_to_string(value),
+            "footer" => self.footer = value_to_string(value),
+            "backgroundColor" => self.background_color = value_to_string(value),
+            "backgroundImage" => self.background_image = value_to_string(value),
+            "color" => self.color = value_to_string(value),
+            _ => {}
+        }
+    }
+}
+
+/// Parses Marp flavoured Markdown into a deck of slides
+pub(crate) fn parse_deck(content: &str) -> Deck {
+    let (frontmatter, body) = split_frontmatter(content);
+
+    let mut deck = Deck::default();
+    let mut inherited = SlideDirectives::default();
+    if let Some(frontmatter) = frontmatter {
+        apply_global_directives(&mut deck, &frontmatter);
+        for (name, value) in &frontmatter {
+            if let Some(name) = name.as_str() {
+                inherited.apply(name, value);
+            }
+        }
+    }
+
+    for (index, markdown) in split_slides(body).into_iter().enumerate() {
+        let mut spot = vec![];
+        let mut notes = vec![];
+        for comment in COMMENT_RE.captures_iter(&markdown) {
+            let comment = comment[1].trim();
+            match parse_directives(comment) {
+                Some(directives) => {
+                    apply_global_directives(&mut deck, &directives);
+                    for (name, value) in directives {
+                        let name = name.as_str().unwrap_or_default().to_string();
+                        match name.strip_prefix('_') {
+                            Some(spot_name) => spot.push((spot_name.to_string(), value)),
+                            None => inherited.apply(&name, &value),
+                        }
+                    }
+                }
+                None if !comment.is_empty() => notes.push(comment.to_string()),
+                None => {}
+            }
+        }
+
+        let mut directives = inherited.clone();
+        for (name, value) in &spot {
+            directives.apply(name, value);
+        }
+
+        deck.slides.push(Slide {
+            number: index + 1,
+            markdown: COMMENT_RE.replace_all(&markdown, "").trim().to_string(),
+            notes,
+            directives,
+        });
+    }
+
+    deck
+}
+
+fn apply_global_directives(deck: &mut Deck, directives: &Mapping) {
+    let get = |name: &str| directives.get(name).and_then(value_to_string);
+    if let Some(theme) = get("theme") {
+        deck.theme = Some(theme);
+    }
+    if let Some(style) = get("style") {
+        deck.style = Some(style);
+    }
+    if let Some(size) = get("size") {
+        deck.size = Some(size);
+    }
+    if let Some(title) = get("title") {
+        deck.title = Some(title);
+    }
+}
+
+// comments are only treated as directives when every key is a known directive otherwise
+// notes such as "TODO: shorten" would be mistaken for directives
+fn parse_directives(comment: &str) -> Option<Mapping> {
+    let mapping: Mapping = serde_yaml::from_str(comment).ok()?;
+    if mapping.is_empty() {
+        return None;
+    }
+
+    let all_directives = mapping.keys().all(|key| {
+        key.as_str()
+            .map(|k| is_directive(k.strip_prefix('_').unwrap_or(k)))
+            .unwrap_or(false)
+    });
+    if all_directives {
+        Some(mapping)
+    } else {
+        None
+    }
+}
+
+fn is_directive(name: &str) -> bool {
+    GLOBAL_DIRECTIVES.contains(&name) || LOCAL_DIRECTIVES.contains(&name)
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn split_frontmatter(content: &str) -> (Option<Mapping>, &str) {
+    let content = content.trim_start_matches('\u{feff}');
+    if !content.starts_with("---") {
+        return (None, content);
+    }
+
+    let after_open = match content.find('\n') {
+        Some(i) if content[..i].trim_end() == "---" => i + 1,
+        _ => return (None, content),
+    };
+
+    let mut offset = after_open;
+    for line in content[after_open..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let frontmatter = serde_yaml::from_str(&content[after_open..offset]).ok();
+            return (frontmatter, &content[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+/// Splits Markdown into slides on `---` rulers ignoring those within fenced code blocks
+pub(crate) fn split_slides(body: &str) -> Vec<String> {
+    let mut slides = vec![];
+    let mut current = String::new();
+    let mut fence: Option<&str> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") {
+            fence = Some("```");
+        } else if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+        } else if trimmed == "---" {
+            slides.push(current);
+            current = String::new();
+            continue;
+        }
+
+        current.push_str(line);
+        current.push('\n');
+    }
+    slides.push(current);
+
+    slides
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::presentation::slides::{parse_deck, split_slides};
+
+    #[test]
+    fn should_split_slides_outside_of_code_blocks() {
+        let slides = split_slides("# One\n\n---\n\n# Two\n\n```yaml\n---\nkey: value\n```\n---\n# Three\n");
+        assert_eq!(slides.len(), 3);
+        assert!(slides[1].contains("key: value"));
+    }
+
+    #[test]
+    fn should_apply_directives() {
+        let deck = parse_deck("---\n\
+            marp: true\n\
+            theme: corporate\n\
+            paginate: true\n\
+            footer: Doctavious\n\
+            ---\n\
+            <!-- _class: lead -->\n\
+            # Title\n\
+            \n\
+            ---\n\
+            <!-- class: invert -->\n\
+            <!-- backgroundColor: '#000' -->\n\
+            ## Agenda\n\
+            \n\
+            ---\n\
+            <!-- _paginate: false -->\n\
+            ## Questions\n");
+
+        assert_eq!(deck.theme, Some(String::from("corporate")));
+        assert_eq!(deck.slides.len(), 3);
+
+        let title = &deck.slides[0];
+        assert_eq!(title.markdown, "# Title");
+        assert_eq!(title.directives.class, Some(String::from("lead")));
+        assert!(title.directives.paginate);
+        assert_eq!(title.directives.footer, Some(String::from("Doctavious")));
+
+        let agenda = &deck.slides[1];
+        assert_eq!(agenda.directives.class, Some(String::from("invert")));
+        assert_eq!(agenda.directives.background_color, Some(String::from("#000")));
+
+        let questions = &deck.slides[2];
+        assert_eq!(questions.number, 3);
+        assert_eq!(questions.directives.class, Some(String::from("invert")));
+        assert!(!questions.directives.paginate);
+    }
+
+    #[test]
+    fn should_collect_speaker_notes_from_comments() {
+        let deck = parse_deck("# Intro\n\n<!-- Welcome everyone -->\n<!--\nTODO: mention the roadmap\n-->\n");
+        assert_eq!(deck.slides.len(), 1);
+        assert_eq!(deck.slides[0].markdown, "# Intro");
+        assert_eq!(deck.slides[0].notes, vec!["Welcome everyone", "TODO: mention the roadmap"]);
+    }
+}
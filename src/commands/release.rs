@@ -5,3 +5,388 @@
 // see what semver mentions.
 
 // build_mod could be a number, git hash, timestmap
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use git2::Repository;
+use serde::Serialize;
+
+use crate::commands::bump::{
+    bump, get_changelog_settings, get_tag_prefix, get_version_files, load_repository_settings,
+    version_changes, write_version, Increment
+};
+use crate::commands::changelog::changelog::{
+    get_releases, Changelog, GenerateChangeLog, DEFAULT_CHANGELOG_FILE,
+};
+use crate::commands::tag::{create_annotated_tag, sign_by_default};
+use crate::constants::DEFAULT_CONFIG_NAME;
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::git;
+use crate::output::{get_output, print_output, Output};
+
+/// Matches the changelog's default commit parser which skips release commits
+const DEFAULT_COMMIT_MESSAGE: &str = "chore(release): prepare for {tag}";
+
+/// Tag prefix used for the first release when the tag pattern doesn't have one
+const DEFAULT_TAG_PREFIX: &str = "v";
+
+#[derive(Parser, Debug)]
+#[command(about = "Bump the version, update the changelog, commit and create an annotated tag")]
+pub(crate) struct ReleaseCommand {
+    #[arg(long, short, value_name = "PATH", help = "Repository to release")]
+    pub repository: Option<PathBuf>,
+
+    #[arg(long, value_name = "IDENTIFIER", help = "Pre-release identifier, ex: alpha or rc")]
+    pub pre_release: Option<String>,
+
+    #[arg(long, value_name = "METADATA", help = "Build metadata, ex: a build number or commit SHA")]
+    pub build: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Increment to apply instead of the one determined from commits"
+    )]
+    pub increment: Option<Increment>,
+
+    #[arg(
+        long = "file",
+        value_name = "PATH",
+        help = "Version file to write to. Overrides configured version files. Can be provided \
+                multiple times"
+    )]
+    pub files: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        default_value = DEFAULT_CHANGELOG_FILE,
+        help = "Changelog the release is added to, relative to the repository"
+    )]
+    pub changelog: PathBuf,
+
+    #[arg(long, help = "Don't update the changelog")]
+    pub no_changelog: bool,
+
+    #[arg(
+        long,
+        short,
+        default_value = DEFAULT_CONFIG_NAME,
+        help = "The configuration file with changelog settings, relative to the repository"
+    )]
+    pub config: PathBuf,
+
+    #[arg(
+        long,
+        short,
+        default_value = DEFAULT_COMMIT_MESSAGE,
+        help = "Message of the release commit. {version} and {tag} are replaced"
+    )]
+    pub message: String,
+
+    #[arg(long, help = "Sign the tag. Defaults to git's tag.gpgSign config")]
+    pub sign: bool,
+
+    #[arg(long, conflicts_with = "sign", help = "Don't sign the tag even when tag.gpgSign is set")]
+    pub no_sign: bool,
+
+    #[arg(long, help = "Shows what would be released without changing anything")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ReleaseResult {
+    pub previous_tag: Option<String>,
+    pub version: String,
+    pub tag: String,
+    /// Files updated, or that would be updated, by the release
+    pub files: Vec<String>,
+    /// ID of the release commit. None for dry runs
+    pub commit: Option<String>,
+    pub signed: bool,
+    pub dry_run: bool,
+    /// Release notes used as the tag message
+    pub notes: String,
+}
+
+impl Display for ReleaseResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.dry_run {
+            writeln!(f, "dry run: would release {}", self.tag)?;
+        } else {
+            writeln!(f, "released {}", self.tag)?;
+        }
+        for file in &self.files {
+            writeln!(f, "updated {}", file)?;
+        }
+        write!(f, "\n{}", self.notes)
+    }
+}
+
+pub(crate) fn handle_release_command(command: ReleaseCommand, output: Option<Output>) -> Result<()> {
+    let repository = match &command.repository {
+        Some(path) => Repository::open(path)?,
+        None => Repository::open(env::current_dir()?)?,
+    };
+
+    let result = release(&repository, &command)?;
+    print_output(get_output(output), result)
+}
+
+pub(crate) fn release(repository: &Repository, command: &ReleaseCommand) -> Result<ReleaseResult> {
+    let workdir = repository.workdir().ok_or_else(|| {
+        DoctaviousError::Msg(String::from("cannot release a bare repository"))
+    })?;
+
+    if git::is_dirty(repository)? {
+        return Err(DoctaviousError::Msg(String::from(
+            "working tree has uncommitted changes. Commit or stash them before releasing",
+        )));
+    }
+
+    // changelog settings and version files both come from the released repository's config
+    let settings = load_repository_settings(repository, &command.config);
+    let config = get_changelog_settings(settings.as_ref())?;
+
+    // the previous tag and the new tag are both determined by the released repository's pattern
+    let bumped = bump(
+        repository,
+        &config.git.tag_pattern,
+        command.increment,
+        command.pre_release.as_deref(),
+        command.build.as_deref(),
+    )?;
+    let tag = get_tag_name(
+        bumped.previous_tag.as_deref(),
+        config.git.tag_pattern.as_deref(),
+        &bumped.version,
+    );
+
+    // unreleased commits under the new tag which is assigned to HEAD
+    let args = GenerateChangeLog {
+        config: command.config.clone(),
+        workdir: None,
+        repository: vec![],
+        prepend: None,
+        file: None,
+        tag: Some(tag.clone()),
+        body: None,
+        strip: None,
+        latest: false,
+        unreleased: true,
+        range: None,
+        from_context: None,
+        include_paths: vec![],
+        exclude_paths: vec![],
+        tag_pattern: None,
+    };
+    let changelog = Changelog::new(get_releases(repository, &config, &args)?, &config)?;
+    let notes = changelog.render_releases()?.trim().to_string();
+
+    let root = if command.repository.is_some() { workdir } else { Path::new("") };
+    let mut files = vec![];
+    let version_files = settings.as_ref()
+        .and_then(|s| s.bump_settings.as_ref())
+        .and_then(|s| s.version_files.as_ref());
+    for file in get_version_files(&command.files, version_files, root) {
+        let changed = if command.dry_run {
+            version_changes(&file, &bumped.version)?
+        } else {
+            write_version(&file, &bumped.version)?
+        };
+        if changed {
+            files.push(file);
+        }
+    }
+
+    if !command.no_changelog {
+        let path = workdir.join(&command.changelog);
+        if !command.dry_run {
+            let existing = match fs::read_to_string(&path) {
+                Ok(existing) => existing,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+            changelog.prepend(existing, &mut File::create(&path)?)?;
+        }
+        files.push(path);
+    }
+
+    let sign = command.sign || (!command.no_sign && sign_by_default(repository));
+    let mut commit = None;
+    if !command.dry_run {
+        let paths = files
+            .iter()
+            .map(|f| relative_to_workdir(workdir, f))
+            .collect::<Result<Vec<PathBuf>>>()?;
+        let message = command.message.replace("{version}", &bumped.version).replace("{tag}", &tag);
+        commit = Some(git::commit_paths(repository, &paths, &message)?.to_string());
+        create_annotated_tag(repository, &tag, &notes, sign)?;
+    }
+
+    Ok(ReleaseResult {
+        previous_tag: bumped.previous_tag,
+        version: bumped.version,
+        tag,
+        files: files.iter().map(|f| f.to_string_lossy().to_string()).collect(),
+        commit,
+        signed: sign,
+        dry_run: command.dry_run,
+        notes,
+    })
+}
+
+/// Tag for the version using the prefix of the previous tag, ex: cli-v, otherwise the literal
+/// prefix of the tag pattern, ex: v for v[0-9]*
+fn get_tag_name(previous_tag: Option<&str>, tag_pattern: Option<&str>, version: &str) -> String {
+    let prefix = match (previous_tag, tag_pattern) {
+        (Some(tag), _) => get_tag_prefix(tag).to_string(),
+        (None, Some(pattern)) => pattern
+            .chars()
+            .take_while(|c| !matches!(c, '*' | '?' | '[') && !c.is_ascii_digit())
+            .collect(),
+        (None, None) => DEFAULT_TAG_PREFIX.to_string(),
+    };
+    format!("{}{}", prefix, version)
+}
+
+fn relative_to_workdir(workdir: &Path, path: &Path) -> Result<PathBuf> {
+    let workdir = fs::canonicalize(workdir)?;
+    let path = fs::canonicalize(path)?;
+    path.strip_prefix(&workdir).map(Path::to_path_buf).map_err(|_| {
+        DoctaviousError::Msg(format!(
+            "{} is outside of the repository {}",
+            path.to_string_lossy(),
+            workdir.to_string_lossy()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use git2::{Repository, Signature, Time};
+    use tempfile::TempDir;
+
+    use crate::commands::release::{get_tag_name, release, ReleaseCommand};
+
+    fn commit(repository: &Repository, path: &str, content: &str, message: &str, time: i64) {
+        let workdir = repository.workdir().unwrap();
+        fs::write(workdir.join(path), content).unwrap();
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let signature = Signature::new("Doctavious", "doctavious@example.com", &Time::new(time, 0)).unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repository.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let oid = repository.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+        if message.starts_with("chore(release)") {
+            let object = repository.find_object(oid, None).unwrap();
+            repository.tag_lightweight("v0.1.0", &object, false).unwrap();
+        }
+    }
+
+    fn command(dir: &Path, dry_run: bool) -> ReleaseCommand {
+        ReleaseCommand {
+            repository: Some(dir.to_path_buf()),
+            pre_release: None,
+            build: None,
+            increment: None,
+            files: vec![dir.join("Cargo.toml"), dir.join("package.json")],
+            changelog: PathBuf::from("CHANGELOG.md"),
+            no_changelog: false,
+            config: PathBuf::from("doctavious.toml"),
+            message: String::from("chore(release): prepare for {tag}"),
+            sign: false,
+            no_sign: true,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn should_release_next_version() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let mut config = repository.config().unwrap();
+        config.set_str("user.name", "Doctavious").unwrap();
+        config.set_str("user.email", "doctavious@example.com").unwrap();
+
+        let cargo = "[package]\nname = \"doctavious\"\nversion = \"0.1.0\"\n";
+        commit(&repository, "Cargo.toml", cargo, "chore(release): prepare for v0.1.0", 1_600_000_000);
+        commit(&repository, "package.json", "{\"name\": \"doctavious\"}\n", "chore: add package.json", 1_600_000_050);
+        commit(&repository, "lib.rs", "fn bump() {}", "feat: add bump command", 1_600_000_100);
+
+        let result = release(&repository, &command(dir.path(), true)).unwrap();
+        assert_eq!("v0.2.0", result.tag);
+        // package.json doesn't declare a version so wouldn't be updated
+        assert_eq!(
+            vec![
+                dir.path().join("Cargo.toml").to_string_lossy().to_string(),
+                dir.path().join("CHANGELOG.md").to_string_lossy().to_string(),
+            ],
+            result.files
+        );
+        assert!(result.notes.contains("- Add bump command"));
+        assert_eq!(None, result.commit);
+        assert!(repository.find_reference("refs/tags/v0.2.0").is_err());
+        assert!(fs::read_to_string(dir.path().join("Cargo.toml")).unwrap().contains("0.1.0"));
+
+        fs::write(dir.path().join("lib.rs"), "fn bump() { /* wip */ }").unwrap();
+        assert!(release(&repository, &command(dir.path(), false)).is_err());
+        fs::write(dir.path().join("lib.rs"), "fn bump() {}").unwrap();
+
+        let result = release(&repository, &command(dir.path(), false)).unwrap();
+        assert!(fs::read_to_string(dir.path().join("Cargo.toml")).unwrap().contains("version = \"0.2.0\""));
+        assert!(fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap().contains("## [0.2.0]"));
+
+        let head = repository.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit, Some(head.id().to_string()));
+        assert_eq!(Some("chore(release): prepare for v0.2.0"), head.message());
+        let tag = repository
+            .find_reference("refs/tags/v0.2.0")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap();
+        assert_eq!(head.id(), tag.target_id());
+        assert!(tag.message().unwrap().contains("- Add bump command"));
+    }
+
+    #[test]
+    fn should_use_version_files_of_released_repository() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+
+        commit(&repository, "VERSION", "0.1.0\n", "chore(release): prepare for v0.1.0", 1_600_000_000);
+        commit(&repository, "doctavious.toml", "[bump]\nversion_files = [\"VERSION\"]\n", "chore: add config", 1_600_000_050);
+        commit(&repository, "lib.rs", "fn bump() {}", "feat: add bump command", 1_600_000_100);
+
+        let command = ReleaseCommand {
+            files: vec![],
+            ..command(dir.path(), true)
+        };
+        let result = release(&repository, &command).unwrap();
+        assert_eq!(
+            vec![
+                dir.path().join("VERSION").to_string_lossy().to_string(),
+                dir.path().join("CHANGELOG.md").to_string_lossy().to_string(),
+            ],
+            result.files
+        );
+    }
+
+    #[test]
+    fn should_name_tag_from_previous_tag_or_pattern() {
+        assert_eq!("cli-v1.1.0", get_tag_name(Some("cli-v1.0.0"), Some("v*"), "1.1.0"));
+        assert_eq!("api-v0.1.0", get_tag_name(None, Some("api-v*"), "0.1.0"));
+        assert_eq!("v0.1.0", get_tag_name(None, Some("v[0-9]*"), "0.1.0"));
+        assert_eq!("v0.1.0", get_tag_name(None, None, "0.1.0"));
+    }
+}
@@ -0,0 +1,226 @@
+pub(crate) mod server;
+
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use clap::Parser;
+
+use crate::commands::build::cache::BuildCache;
+use crate::commands::build::context::BuildContext;
+use crate::commands::build::staging::{
+    BuildsManifest, clean_staging_dir, next_staging_dir, read_manifest, swap_staging_dir
+};
+use crate::commands::build::{build_projects, get_build_settings, get_projects, BuildOptions};
+use crate::commands::serve::server::{serve, LiveReload};
+use crate::constants::DEFAULT_BUILD_OUTPUT_DIR;
+use crate::doctavious_error::{DoctaviousError, Result};
+use crate::output::Output;
+use crate::settings::BuildSettings;
+use crate::watcher::FileWatcher;
+
+/// How often source files are checked for changes when watching
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Parser, Debug)]
+#[command(about = "Serve build output locally")]
+pub(crate) struct ServeCommand {
+    #[arg(long, short, default_value_t = 3000, help = "Port to listen on")]
+    pub port: u16,
+
+    #[arg(long, default_value = "127.0.0.1", help = "Address to listen on")]
+    pub host: String,
+
+    #[arg(
+        long,
+        help = "Name of the project to serve. Defaults to the only project built or all \
+                projects when multiple have been built"
+    )]
+    pub project: Option<String>,
+
+    #[arg(
+        long,
+        short,
+        help = "Rebuild when source files change and reload open browsers"
+    )]
+    pub watch: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = BuildContext::Production,
+        help = "Build context used when rebuilding"
+    )]
+    pub context: BuildContext,
+
+    #[arg(long, value_name = "PATH", help = "Directory of the project to build when watching")]
+    pub cwd: Option<PathBuf>,
+}
+
+pub(crate) fn handle_serve_command(command: ServeCommand, _output: Option<Output>) -> Result<()> {
+    let staging_dir = Path::new(DEFAULT_BUILD_OUTPUT_DIR);
+    let build_settings = get_build_settings();
+    let mut options = BuildOptions {
+        staging_dir,
+        context: command.context,
+        dry: false,
+        skip_install: false,
+        cache: Some(BuildCache::user()),
+    };
+
+    // always build when watching so what is served reflects the current sources
+    let manifest = if command.watch {
+        build_projects(command.cwd.as_deref(), build_settings, &options)?
+    } else {
+        read_manifest(staging_dir).map_err(|_| {
+            DoctaviousError::Msg(String::from(
+                "no build output found. Run `doctavious build` or serve with --watch"
+            ))
+        })?
+    };
+
+    let root = get_serve_dir(staging_dir, &manifest, command.project.as_deref())?;
+    let listener = TcpListener::bind((command.host.as_str(), command.port))?;
+    println!("serving {} at http://{}:{}", root.to_string_lossy(), command.host, command.port);
+
+    if !command.watch {
+        serve(listener, root, None);
+        return Ok(());
+    }
+
+    let live_reload = Arc::new(LiveReload::default());
+    let server_live_reload = live_reload.clone();
+    thread::spawn(move || serve(listener, root, Some(server_live_reload)));
+
+    let roots = get_projects(command.cwd.as_deref(), build_settings)
+        .into_iter()
+        .map(|p| PathBuf::from(p.root))
+        .collect();
+    let mut watcher = FileWatcher::new(roots, get_output_directories(&manifest));
+
+    // dependencies were installed by the initial build
+    options.skip_install = true;
+    loop {
+        watcher.wait_for_change(WATCH_INTERVAL);
+        println!("change detected. rebuilding");
+        // a failed rebuild keeps serving the previous output rather than stopping the server
+        match rebuild(command.cwd.as_deref(), build_settings, &options) {
+            Ok(manifest) => {
+                watcher.set_excluded(get_output_directories(&manifest));
+                live_reload.reload();
+            }
+            Err(e) => eprintln!("rebuild failed, serving the previous build: {}", e),
+        }
+    }
+}
+
+/// Builds into a separate staging directory which replaces the served output only when every
+/// project builds successfully
+fn rebuild(
+    cwd: Option<&Path>,
+    build_settings: Option<&'static BuildSettings>,
+    options: &BuildOptions,
+) -> Result<BuildsManifest> {
+    let next = next_staging_dir(options.staging_dir);
+    let next_options = BuildOptions {
+        staging_dir: &next,
+        context: options.context,
+        dry: options.dry,
+        skip_install: options.skip_install,
+        cache: options.cache.clone(),
+    };
+
+    let manifest = build_projects(cwd, build_settings, &next_options)?;
+    let failed = manifest.builds.iter().filter(|b| !b.success).count();
+    if failed > 0 {
+        clean_staging_dir(&next)?;
+        return Err(DoctaviousError::BuildError(format!(
+            "{} of {} builds failed", failed, manifest.builds.len()
+        )));
+    }
+
+    swap_staging_dir(&next, options.staging_dir)?;
+    Ok(manifest)
+}
+
+/// Directory to serve. The project directory when a single project has been built, or was
+/// requested, otherwise the staging directory containing every project
+fn get_serve_dir(
+    staging_dir: &Path,
+    manifest: &BuildsManifest,
+    project: Option<&str>
+) -> Result<PathBuf> {
+    let project = match project {
+        Some(project) => Some(project),
+        None if manifest.builds.len() == 1 => Some(manifest.builds[0].project.as_str()),
+        None => None,
+    };
+
+    let dir = match project {
+        Some(project) => staging_dir.join(project),
+        None => staging_dir.to_path_buf(),
+    };
+
+    if !dir.is_dir() {
+        return Err(DoctaviousError::Msg(format!(
+            "build output {} does not exist", dir.to_string_lossy()
+        )));
+    }
+
+    Ok(dir)
+}
+
+// output directories are excluded from watching otherwise every build would trigger another
+fn get_output_directories(manifest: &BuildsManifest) -> Vec<PathBuf> {
+    let mut directories = vec![];
+    for build in &manifest.builds {
+        let root = Path::new(&build.root);
+        if let Some(output_directory) = &build.output_directory {
+            directories.push(root.join(output_directory));
+        }
+        if let Some(api_reference) = &build.api_reference {
+            directories.push(root.join(&api_reference.output_directory));
+        }
+    }
+    directories
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+    use crate::commands::build::context::BuildContext;
+    use crate::commands::build::staging::{BuildResult, BuildsManifest};
+    use crate::commands::serve::{get_output_directories, get_serve_dir};
+
+    #[test]
+    fn should_serve_single_project_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::create_dir_all(dir.path().join("api")).unwrap();
+
+        let mut manifest = BuildsManifest::new(BuildContext::Production);
+        manifest.builds.push(BuildResult { project: String::from("docs"), ..Default::default() });
+        assert_eq!(get_serve_dir(dir.path(), &manifest, None).unwrap(), dir.path().join("docs"));
+
+        manifest.builds.push(BuildResult { project: String::from("api"), ..Default::default() });
+        assert_eq!(get_serve_dir(dir.path(), &manifest, None).unwrap(), dir.path().to_path_buf());
+        assert_eq!(get_serve_dir(dir.path(), &manifest, Some("api")).unwrap(), dir.path().join("api"));
+        assert!(get_serve_dir(dir.path(), &manifest, Some("guides")).is_err());
+    }
+
+    #[test]
+    fn should_exclude_output_directories_from_watching() {
+        let mut manifest = BuildsManifest::new(BuildContext::Production);
+        manifest.builds.push(BuildResult {
+            project: String::from("docs"),
+            root: String::from("docs"),
+            output_directory: Some(String::from("book")),
+            ..Default::default()
+        });
+        assert_eq!(get_output_directories(&manifest), vec![PathBuf::from("docs/book")]);
+    }
+}
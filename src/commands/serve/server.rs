@@ -0,0 +1,400 @@
+// Minimal static file server used to preview build output.
+// This intentionally doesn't use the dev server of the framework so that what is served matches
+// what would be deployed. Only GET and HEAD are supported and connections aren't kept alive which
+// is fine for local previews.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Path of the server-sent events endpoint browsers listen to for reloads
+pub(crate) const LIVE_RELOAD_PATH: &str = "/__doctavious/livereload";
+
+const TEXT_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>new EventSource("/__doctavious/livereload").onmessage = function() { location.reload(); };</script>"#;
+
+/// Notifies browsers connected to the live reload endpoint that they should reload
+#[derive(Default)]
+pub(crate) struct LiveReload {
+    version: Mutex<u64>,
+    changed: Condvar,
+}
+
+impl LiveReload {
+    pub fn reload(&self) {
+        let mut version = self.version.lock().unwrap();
+        *version += 1;
+        self.changed.notify_all();
+    }
+
+    /// Waits until a reload newer than `seen` or the timeout elapses returning the latest version
+    fn wait(&self, seen: u64, timeout: Duration) -> u64 {
+        let version = self.version.lock().unwrap();
+        let (version, _) = self.changed
+            .wait_timeout_while(version, timeout, |v| *v == seen)
+            .unwrap();
+        *version
+    }
+
+    fn version(&self) -> u64 {
+        *self.version.lock().unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Resolved {
+    File(PathBuf),
+    /// Directories are redirected to include a trailing slash so relative links resolve
+    Redirect(String),
+    /// Includes the custom 404 page when the served directory has one
+    NotFound(Option<PathBuf>),
+}
+
+/// Accepts connections serving files from `root`, handling each connection on its own thread.
+/// Live reload script is injected into HTML pages when `live_reload` is provided.
+pub(crate) fn serve(listener: TcpListener, root: PathBuf, live_reload: Option<Arc<LiveReload>>) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("failed to accept connection: {}", e);
+                continue;
+            }
+        };
+
+        let root = root.clone();
+        let live_reload = live_reload.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &root, live_reload) {
+                eprintln!("failed to handle request: {}", e);
+            }
+        });
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    root: &Path,
+    live_reload: Option<Arc<LiveReload>>
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // headers aren't used but need to be consumed before responding
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim() != "" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or("/");
+    if method != "GET" && method != "HEAD" {
+        return write_response(
+            &mut stream, "405 Method Not Allowed", TEXT_CONTENT_TYPE, &[], b"Method Not Allowed", false
+        );
+    }
+    let head_only = method == "HEAD";
+
+    let target = target.split('#').next().unwrap_or("/");
+    let (raw_path, query) = target.split_at(target.find('?').unwrap_or(target.len()));
+    let path = decode_path(raw_path);
+    if let Some(live_reload) = live_reload.as_ref().filter(|_| path == LIVE_RELOAD_PATH) {
+        return stream_reloads(stream, live_reload);
+    }
+
+    let (status, file) = match resolve(root, &path) {
+        Resolved::File(file) => ("200 OK", Some(file)),
+        Resolved::Redirect(location) => {
+            // the query is kept as sent while the decoded path needs to be encoded again
+            let location = format!("{}{}", encode_path(&location), query);
+            return write_response(
+                &mut stream, "301 Moved Permanently", TEXT_CONTENT_TYPE, &[("Location", location)], b"", head_only
+            );
+        }
+        Resolved::NotFound(page) => ("404 Not Found", page),
+    };
+
+    match file {
+        Some(file) => {
+            let content_type = content_type(&file);
+            let mut body = fs::read(&file)?;
+            if live_reload.is_some() && content_type.starts_with("text/html") {
+                body = inject_live_reload(&body);
+            }
+            write_response(&mut stream, status, content_type, &[], &body, head_only)
+        }
+        None => write_response(&mut stream, status, TEXT_CONTENT_TYPE, &[], b"Not Found", head_only),
+    }
+}
+
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    headers: &[(&str, String)],
+    body: &[u8],
+    head_only: bool,
+) -> std::io::Result<()> {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n",
+        status, content_type, body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+
+    stream.write_all(response.as_bytes())?;
+    if !head_only {
+        stream.write_all(body)?;
+    }
+    stream.flush()
+}
+
+// keeps the connection open sending an event whenever a reload is triggered. Comments are sent
+// periodically so that we notice, via a failed write, when the browser has gone away
+fn stream_reloads(mut stream: TcpStream, live_reload: &LiveReload) -> std::io::Result<()> {
+    stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n")?;
+    stream.flush()?;
+
+    let mut seen = live_reload.version();
+    loop {
+        let version = live_reload.wait(seen, Duration::from_secs(15));
+        if version == seen {
+            stream.write_all(b": keep-alive\n\n")?;
+        } else {
+            seen = version;
+            stream.write_all(b"data: reload\n\n")?;
+        }
+        stream.flush()?;
+    }
+}
+
+/// Resolves the request path to a file within root.
+///
+/// Supports clean URLs by trying `<path>.html` and `<path>/index.html`. Paths attempting to
+/// escape the root are treated as not found.
+pub(crate) fn resolve(root: &Path, path: &str) -> Resolved {
+    let not_found = || {
+        let page = root.join("404.html");
+        Resolved::NotFound(if page.is_file() { Some(page) } else { None })
+    };
+
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative.components().any(|c| !matches!(c, Component::Normal(_) | Component::CurDir)) {
+        return not_found();
+    }
+
+    let file = root.join(relative);
+    if path.ends_with('/') {
+        let index = file.join("index.html");
+        return if index.is_file() { Resolved::File(index) } else { not_found() };
+    }
+
+    if file.is_file() {
+        return Resolved::File(file);
+    }
+
+    if file.is_dir() && file.join("index.html").is_file() {
+        return Resolved::Redirect(format!("{}/", path));
+    }
+
+    let mut html = file.into_os_string();
+    html.push(".html");
+    let html = PathBuf::from(html);
+    if html.is_file() {
+        return Resolved::File(html);
+    }
+
+    not_found()
+}
+
+/// Decodes percent-encoded characters of the request path, ex: %20
+fn decode_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let byte = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = byte {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Percent-encodes characters of the path that aren't allowed within a URL path, ex: spaces
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'-' | b'.' | b'_' | b'~' | b'/' | b'!' | b'$' | b'&' | b'\'' | b'(' | b')'
+            | b'*' | b'+' | b',' | b';' | b'=' | b':' | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn inject_live_reload(body: &[u8]) -> Vec<u8> {
+    let html = String::from_utf8_lossy(body);
+    let injected = match html.rfind("</body>") {
+        Some(index) => format!("{}{}{}", &html[..index], LIVE_RELOAD_SCRIPT, &html[index..]),
+        None => format!("{}{}", html, LIVE_RELOAD_SCRIPT),
+    };
+    injected.into_bytes()
+}
+
+/// MIME type of the file based on its extension
+pub(crate) fn content_type(path: &Path) -> &'static str {
+    let extension = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "webmanifest" => "application/manifest+json",
+        "xml" => "application/xml",
+        "rss" => "application/rss+xml",
+        "atom" => "application/atom+xml",
+        "txt" => "text/plain; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "eot" => "application/vnd.ms-fontobject",
+        "pdf" => "application/pdf",
+        "wasm" => "application/wasm",
+        "zip" => "application/zip",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::TempDir;
+    use crate::commands::serve::server::{
+        content_type, decode_path, encode_path, LiveReload, resolve, Resolved, serve
+    };
+
+    fn site() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("index.html"), "<html><body>home</body></html>").unwrap();
+        fs::write(dir.path().join("about.html"), "<html><body>about</body></html>").unwrap();
+        fs::create_dir_all(dir.path().join("guides")).unwrap();
+        fs::write(dir.path().join("guides/index.html"), "<html><body>guides</body></html>").unwrap();
+        fs::write(dir.path().join("404.html"), "<html><body>not found</body></html>").unwrap();
+        fs::create_dir_all(dir.path().join("getting started")).unwrap();
+        fs::write(dir.path().join("getting started/index.html"), "<html><body>getting started</body></html>").unwrap();
+        dir
+    }
+
+    #[test]
+    fn should_resolve_clean_urls() {
+        let dir = site();
+        let root = dir.path();
+
+        assert_eq!(resolve(root, "/"), Resolved::File(root.join("index.html")));
+        assert_eq!(resolve(root, "/about"), Resolved::File(root.join("about.html")));
+        assert_eq!(resolve(root, "/about.html"), Resolved::File(root.join("about.html")));
+        assert_eq!(resolve(root, "/guides"), Resolved::Redirect(String::from("/guides/")));
+        assert_eq!(resolve(root, "/guides/"), Resolved::File(root.join("guides/index.html")));
+        assert_eq!(resolve(root, "/missing"), Resolved::NotFound(Some(root.join("404.html"))));
+        assert_eq!(resolve(root, "/../secret"), Resolved::NotFound(Some(root.join("404.html"))));
+    }
+
+    #[test]
+    fn should_not_found_without_custom_page() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(resolve(dir.path(), "/missing"), Resolved::NotFound(None));
+    }
+
+    #[test]
+    fn should_decode_paths() {
+        assert_eq!(decode_path("/getting%20started"), "/getting started");
+        assert_eq!(decode_path("/100%"), "/100%");
+        assert_eq!(encode_path("/getting started/"), "/getting%20started/");
+        assert_eq!(encode_path("/caf\u{e9}/100%"), "/caf%C3%A9/100%25");
+        assert_eq!(encode_path("/guides/"), "/guides/");
+    }
+
+    #[test]
+    fn should_determine_content_type() {
+        assert_eq!(content_type(Path::new("index.html")), "text/html; charset=utf-8");
+        assert_eq!(content_type(Path::new("fonts/inter.WOFF2")), "font/woff2");
+        assert_eq!(content_type(Path::new("logo.svg")), "image/svg+xml");
+        assert_eq!(content_type(Path::new("CNAME")), "application/octet-stream");
+    }
+
+    #[test]
+    fn should_serve_files_with_live_reload() {
+        let dir = site();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let root = dir.path().to_path_buf();
+        thread::spawn(move || serve(listener, root, Some(Arc::new(LiveReload::default()))));
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = get("/about");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(response.contains("/__doctavious/livereload"));
+
+        let response = get("/missing");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+        assert!(response.contains("not found"));
+
+        let response = get("/guides?x=1");
+        assert!(response.starts_with("HTTP/1.1 301 Moved Permanently"));
+        assert!(response.contains("Location: /guides/?x=1\r\n"));
+
+        let response = get("/getting%20started?x=1");
+        assert!(response.starts_with("HTTP/1.1 301 Moved Permanently"));
+        assert!(response.contains("Location: /getting%20started/?x=1\r\n"));
+    }
+}
@@ -1 +1,56 @@
+// Annotated tags for releases
+// git2 doesn't support signing tags so signed tags are created with git which signs them with
+// GPG, SSH or X.509 based on gpg.format and user.signingKey
 
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use git2::Repository;
+
+use crate::doctavious_error::{DoctaviousError, Result};
+
+/// Whether tags should be signed when not explicitly requested, based on tag.gpgSign
+pub(crate) fn sign_by_default(repo: &Repository) -> bool {
+    repo.config()
+        .and_then(|config| config.get_bool("tag.gpgSign"))
+        .unwrap_or(false)
+}
+
+/// Creates an annotated tag on HEAD with the message
+pub(crate) fn create_annotated_tag(
+    repo: &Repository,
+    name: &str,
+    message: &str,
+    sign: bool,
+) -> Result<()> {
+    if !sign {
+        let head = repo.head()?.peel_to_commit()?;
+        repo.tag(name, head.as_object(), &repo.signature()?, message, false)?;
+        return Ok(());
+    }
+
+    let workdir = repo.workdir().unwrap_or_else(|| repo.path());
+    let output = Command::new("git")
+        .current_dir(workdir)
+        .args(["tag", "--sign", "--annotate", "--file", "-", name])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(message.as_bytes())?;
+            }
+            child.wait_with_output()
+        })?;
+
+    if !output.status.success() {
+        return Err(DoctaviousError::Msg(format!(
+            "failed to create signed tag {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
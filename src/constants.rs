@@ -1,5 +1,6 @@
 // TODO: better way to do this? Do we want to keep a default settings file in doctavious dir?
 pub static DEFAULT_CONFIG_NAME: &str = "doctavious.toml";
+pub static DEFAULT_DOCTAVIOUS_DIR: &str = ".doctavious";
 pub static DEFAULT_ADR_DIR: &str = "docs/adr";
 pub static DEFAULT_ADR_TEMPLATE_PATH: &str = "templates/adr/template";
 pub static INIT_ADR_TEMPLATE_PATH: &str = "templates/adr/init";
@@ -8,3 +9,8 @@ pub static DEFAULT_RFD_TEMPLATE_PATH: &str = "templates/rfd/template";
 // TODO: do we want this to default to the current directory?
 pub static DEFAULT_TIL_DIR: &str = "til";
 pub static DEFAULT_TIL_TEMPLATE_PATH: &str = "templates/til/template";
+// TODO: should this be configurable?
+pub static DEFAULT_BUILD_OUTPUT_DIR: &str = ".doctavious/output";
+pub static BUILDS_MANIFEST_NAME: &str = "builds.json";
+pub static DEFAULT_API_REFERENCE_PATH: &str = "api";
+pub static DEFAULT_BUILD_CACHE_DIR: &str = "cache";
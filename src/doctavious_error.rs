@@ -32,6 +32,10 @@ pub enum DoctaviousError {
     #[error("Changelog error: `{0}`")]
     ChangelogError(String),
 
+    /// Error that may occur while building a project.
+    #[error("Build error: `{0}`")]
+    BuildError(String),
+
     /// Error that may occur while parsing the template.
     #[error("Template parse error:\n{0}")]
     TemplateParseError(String),
@@ -81,6 +85,9 @@ pub enum DoctaviousError {
     #[error("walkdir error")]
     WalkdirError(#[from] walkdir::Error),
 
+    #[error("Zip error: `{0}`")]
+    ZipError(#[from] zip::result::ZipError),
+
     // // TODO: figure out what to do here
     // #[error("not sure")]
     // AnyhowError(#[from] anyhow::Error),
@@ -1,9 +1,9 @@
 // from https://siciarz.net/24-days-rust-git2/
 
-use git2::{BranchType, Commit, Direction, Oid, Repository, Sort};
+use git2::{BranchType, Commit, Direction, Oid, Repository, Sort, StatusOptions};
 use indexmap::IndexMap;
 use regex::Regex;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // https://github.com/simeg/eureka/blob/master/src/git.rs
 
@@ -62,6 +62,31 @@ pub(crate) fn add_and_commit(
     ); // parents
 }
 
+/// Stages the paths, relative to the repository's working directory, and commits them on HEAD
+pub(crate) fn commit_paths(
+    repo: &Repository,
+    paths: &[PathBuf],
+    message: &str,
+) -> Result<Oid, git2::Error> {
+    let mut index = repo.index()?;
+    for path in paths {
+        index.add_path(path)?;
+    }
+    index.write()?;
+
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent_commit = find_last_commit(repo)?;
+    let signature = repo.signature()?;
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent_commit])
+}
+
+/// Returns true when there are staged or unstaged changes to tracked files
+pub(crate) fn is_dirty(repo: &Repository) -> Result<bool, git2::Error> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    Ok(!repo.statuses(Some(&mut options))?.is_empty())
+}
+
 pub(crate) fn push(repo: &Repository) -> Result<(), git2::Error> {
     let mut remote = repo.find_remote("origin")?;
     remote.connect(Direction::Push)?;
@@ -76,7 +101,8 @@ fn find_last_commit(repo: &Repository) -> Result<Commit, git2::Error> {
 
 /// Parses and returns the commits.
 ///
-/// Sorts the commits by their time.
+/// The range can either be a range, ex: v0.1.0..v0.2.0, or a single revision in which case the
+/// revision and all of its ancestors are returned. Sorts the commits by their time.
 pub fn commits(
     repo: &Repository,
     range: Option<String>,
@@ -84,7 +110,11 @@ pub fn commits(
     let mut revwalk = repo.revwalk()?;
     revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
     if let Some(range) = range {
-        revwalk.push_range(&range)?;
+        if range.contains("..") {
+            revwalk.push_range(&range)?;
+        } else {
+            revwalk.push(repo.revparse_single(&range)?.peel_to_commit()?.id())?;
+        }
     } else {
         revwalk.push_head()?;
     }
@@ -126,3 +156,52 @@ pub fn tags(
     tags.sort_by(|a, b| a.0.time().seconds().cmp(&b.0.time().seconds()));
     Ok(tags.into_iter().map(|(a, b)| (a.id().to_string(), b)).collect())
 }
+
+/// Returns the name of the repository's directory, ex: doctavious for /src/doctavious/.git
+pub(crate) fn repository_name(repo: &Repository) -> Option<String> {
+    repo.workdir()
+        .unwrap_or_else(|| repo.path())
+        .file_name()
+        .map(|name| name.to_string_lossy().trim_end_matches(".git").to_string())
+}
+
+/// Returns the paths of the files added, modified, deleted or renamed by the commit compared to
+/// its first parent. Every file in the tree is returned for the root commit.
+pub(crate) fn changed_paths(
+    repo: &Repository,
+    commit: &Commit,
+) -> Result<Vec<PathBuf>, git2::Error> {
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+    let mut paths = vec![];
+    for delta in diff.deltas() {
+        for path in [delta.old_file().path(), delta.new_file().path()].iter().flatten() {
+            let path = path.to_path_buf();
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Returns the name of the branch HEAD points to or None when HEAD is detached
+pub(crate) fn current_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(|s| s.to_string())
+}
+
+/// Returns the full SHA of the commit HEAD points to
+pub(crate) fn head_commit_sha(repo: &Repository) -> Option<String> {
+    repo.head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .map(|commit| commit.id().to_string())
+}
@@ -13,10 +13,18 @@ use serde::{Serialize, Serializer};
 use serde::ser::SerializeSeq;
 
 use crate::commands::build::{BuildCommand, handle_build_command};
+use crate::commands::bump::{BumpCommand, handle_bump_command};
+use crate::commands::cache::{CacheCommand, handle_cache_command};
+use crate::commands::changelog::changelog::{ChangelogOpt, handle_changelog_command};
+use crate::commands::check::{CheckCommand, handle_check_command};
 use crate::commands::design_decisions::adr::{ADR, handle_adr_command};
+use crate::commands::githooks::{GithooksCommand, handle_githooks_command};
 use crate::commands::design_decisions::rfd::{handle_rfd_command, RFD};
+use crate::commands::presentation::{handle_presentation_command, PresentationCommand};
+use crate::commands::release::{handle_release_command, ReleaseCommand};
+use crate::commands::serve::{handle_serve_command, ServeCommand};
 use crate::commands::til::{handle_til_command, Til};
-use crate::constants::{DEFAULT_ADR_TEMPLATE_PATH, DEFAULT_CONFIG_NAME};
+use crate::constants::{DEFAULT_ADR_TEMPLATE_PATH, DEFAULT_DOCTAVIOUS_DIR};
 use crate::constants::{DEFAULT_ADR_DIR, DEFAULT_RFD_DIR};
 use crate::doctavious_error::Result as DoctaviousResult;
 use crate::file_structure::FileStructure;
@@ -39,6 +47,7 @@ mod scm;
 mod settings;
 mod templates;
 mod utils;
+mod watcher;
 mod files;
 
 #[derive(Parser, Debug)]
@@ -68,7 +77,7 @@ lazy_static! {
     pub static ref DOCTAVIOUS_DIR: PathBuf = {
         let home_dir = dirs::home_dir()
             .expect("Unsupported platform: can't find home directory");
-        Path::new(&home_dir).join(DEFAULT_CONFIG_NAME)
+        Path::new(&home_dir).join(DEFAULT_DOCTAVIOUS_DIR)
     };
 }
 
@@ -76,57 +85,18 @@ lazy_static! {
 enum Command {
     Adr(ADR),
     Build(BuildCommand),
-    Presentation(Presentation),
+    Bump(BumpCommand),
+    Cache(CacheCommand),
+    Changelog(ChangelogOpt),
+    Check(CheckCommand),
+    Githooks(GithooksCommand),
+    Presentation(PresentationCommand),
+    Release(ReleaseCommand),
     RFD(RFD),
+    Serve(ServeCommand),
     Til(Til),
 }
 
-#[derive(Parser, Debug)]
-#[command(about = "Presentation commands")]
-struct Presentation {
-    #[arg(long, help = "Output file path (or directory input-dir is passed)")]
-    output_dir: Option<String>,
-
-    #[arg(
-        long,
-        short,
-        help = "The base directory to find markdown and theme CSS"
-    )]
-    input_dir: Option<String>,
-
-    // https://github.com/marp-team/marp-cli#options
-    // Marp CLI can be configured options with file, such as marp.config.js, marp.config.cjs, .marprc (JSON / YAML), and marp section of package.json
-    // package.json
-    // {
-    //     "marp": {
-    //       "inputDir": "./slides",
-    //       "output":" ./public",
-    //       "themeSet": "./themes"
-    //     }
-    //   }
-
-    // # .marprc.yml
-    // allowLocalFiles: true
-    // options:
-    //   looseYAML: false
-    //   markdown:
-    //     breaks: false
-    // pdf: true
-
-    // -c, --config-file, --config        Specify path to a configuration file
-    //                                            [string]
-    // --no-config-file, --no-config  Prevent looking up for a configuration file
-    //                                           [boolean]
-    #[arg(long, short, help = "Watch input markdowns for changes")]
-    watch: bool,
-
-    #[arg(long, short, help = "Enable server mode")]
-    server: bool,
-
-    #[arg(long, short, help = "Open preview window")]
-    preview: bool,
-}
-
 fn init_dir(dir: &str) -> DoctaviousResult<()> {
     // TODO: create_dir_all doesnt appear to throw AlreadyExists. Confirm this
     // I think this is fine just need to make sure that we dont overwrite initial file
@@ -207,21 +177,24 @@ fn main() -> DoctaviousResult<()> {
 
         Command::Build(cmd) => return handle_build_command(cmd, opt.output),
 
-        Command::Presentation(params) => {
-            // TODO: implement
-            let output_dir = match params.output_dir {
-                None => "",
-                Some(ref o) => o,
-            };
+        Command::Bump(cmd) => return handle_bump_command(cmd, opt.output),
+
+        Command::Cache(cmd) => return handle_cache_command(cmd, opt.output),
+
+        Command::Changelog(cmd) => return handle_changelog_command(cmd, opt.output),
+
+        Command::Check(cmd) => return handle_check_command(cmd, opt.output),
 
-            let input_dir = match params.input_dir {
-                None => "",
-                Some(ref i) => i,
-            };
-        },
+        Command::Githooks(cmd) => return handle_githooks_command(cmd, opt.output),
+
+        Command::Presentation(cmd) => return handle_presentation_command(cmd, opt.output),
+
+        Command::Release(cmd) => return handle_release_command(cmd, opt.output),
 
         Command::RFD(rfd) => return handle_rfd_command(rfd, opt.output),
 
+        Command::Serve(cmd) => return handle_serve_command(cmd, opt.output),
+
         Command::Til(til) => return handle_til_command(til, opt.output),
     };
 
@@ -10,6 +10,7 @@ lazy_static! {
         let mut map = HashMap::new();
         map.insert("json", Output::Json);
         map.insert("text", Output::Text);
+        map.insert("yaml", Output::Yaml);
         map
     };
 }
@@ -27,6 +28,7 @@ pub enum Output {
     Json,
     Text,
     Table,
+    Yaml,
 }
 
 impl Default for Output {
@@ -55,6 +57,10 @@ pub(crate) fn print_output<A: std::fmt::Display + Serialize>(
         Output::Table => {
             todo!()
         }
+        Output::Yaml => {
+            serde_yaml::to_writer(std::io::stdout(), &value)?;
+            Ok(())
+        }
     }
 }
 
@@ -7,6 +7,7 @@ use lazy_static::lazy_static;
 use std::fs;
 use std::str;
 
+use crate::commands::build::frameworks::custom::CustomFrameworkSettings;
 use crate::commands::changelog::CommitParser;
 use crate::markup_format::MarkupFormat;
 use regex::Regex;
@@ -54,13 +55,21 @@ pub struct Settings {
     #[serde(alias = "build")]
     pub build_settings: Option<BuildSettings>,
 
+    #[serde(rename(serialize = "bump"))]
+    #[serde(alias = "bump")]
+    pub bump_settings: Option<BumpSettings>,
+
+    #[serde(rename(serialize = "check"))]
+    #[serde(alias = "check")]
+    pub check_settings: Option<CheckSettings>,
+
     #[serde(rename(serialize = "changelog"))]
     #[serde(alias = "changelog")]
     pub changelog_settings: Option<ChangelogSettings>,
 
     #[serde(rename(serialize = "githook"))]
     #[serde(alias = "githook")]
-    pub githook_settings: Option<ChangelogSettings>,
+    pub githook_settings: Option<GithookSettings>,
 
     #[serde(rename(serialize = "rfd"))]
     #[serde(alias = "rfd")]
@@ -94,10 +103,37 @@ pub struct ChangelogGitSettings {
     #[serde(with = "serde_regex", default)]
     /// Regex to skip matched tags.
     pub skip_tags: Option<Regex>,
+    /// Globs, relative to the repository root, of the paths a commit must change to be included,
+    /// ex: packages/api/**. Used with a tag_pattern, such as api-v*, for a package's changelog
+    pub include_paths: Option<Vec<String>>,
+    /// Globs of the paths ignored when determining whether a commit changes included paths
+    pub exclude_paths: Option<Vec<String>>,
     // https://github.com/orhun/git-cliff/issues/10
     // skip intermediate tags?
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BumpSettings {
+    /// Files the version is written to, ex: Cargo.toml or VERSION. Defaults to Cargo.toml,
+    /// package.json and pyproject.toml when present
+    pub version_files: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CheckSettings {
+    pub links: Option<LinkCheckSettings>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LinkCheckSettings {
+    /// Regexes of link targets which are skipped, ex: ^/api/
+    pub allowlist: Option<Vec<String>>,
+
+    /// URL prefixes mapped onto local directories so that links to the deployed site, such as
+    /// https://docs.example.com/, are checked against local files rather than skipped
+    pub url_mappings: Option<HashMap<String, String>>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct AdrSettings {
     pub dir: Option<String>,
@@ -123,7 +159,29 @@ pub struct TilSettings {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct GithookSettings {
+    /// Hooks keyed by their git hook name, ex: pre-commit
+    #[serde(default)]
     pub hooks: HashMap<String, Hook>,
+
+    /// Rules for the built-in commit-msg hook, `doctavious githooks builtin commit-msg`
+    pub commit_msg: Option<CommitMsgSettings>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CommitMsgSettings {
+    /// Conventional commit types which are allowed, ex: feat and fix. Any type is allowed when unset
+    pub types: Option<Vec<String>>,
+
+    /// Conventional commit scopes which are allowed. Any scope is allowed when unset
+    pub scopes: Option<Vec<String>>,
+
+    /// Require a scope, ex: feat(api): rather than feat:
+    #[serde(default)]
+    pub require_scope: bool,
+
+    /// Require a `Release note:` trailer, which may be `Release note: None`
+    #[serde(default)]
+    pub require_release_note: bool,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -149,7 +207,96 @@ pub struct SnippetSource {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct BuildSettings {
-    pub command: String
+    #[serde(default)]
+    pub command: String,
+
+    /// Overrides the install command of the detected package manager.
+    /// An empty command skips installing dependencies
+    pub install_command: Option<String>,
+
+    /// Overrides the output directory of the framework, relative to the project root
+    pub output_directory: Option<String>,
+
+    /// Doc projects, typically within a monorepo, that are detected and built independently
+    pub projects: Option<Vec<BuildProjectSettings>>,
+
+    /// User-defined frameworks which are merged with, and can override, built-in frameworks
+    pub frameworks: Option<Vec<CustomFrameworkSettings>>,
+
+    /// Base URL of the deployed site exposed to build commands as DOCTAVIOUS_BASE_URL
+    pub base_url: Option<String>,
+
+    /// Environment variables passed to install and build commands
+    pub environment: Option<HashMap<String, String>>,
+
+    /// Settings for build contexts, keyed by context (production, preview, branch) or by
+    /// `branch:<name>` for a specific branch, which override the top level build settings
+    pub context: Option<HashMap<String, BuildContextSettings>>,
+
+    /// Globs, relative to the project root, of the files hashed to determine whether a build
+    /// can be restored from the build cache. Defaults to the files tracked by git
+    pub inputs: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BuildContextSettings {
+    /// Overrides the build command for the context
+    pub command: Option<String>,
+
+    /// Overrides the base URL for the context
+    pub base_url: Option<String>,
+
+    /// Environment variables for the context which take precedence over the build environment
+    pub environment: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BuildProjectSettings {
+    /// Name of the project used for its directory within the build output.
+    /// Defaults to a slug of the root
+    pub name: Option<String>,
+
+    /// Root directory of the project relative to the directory doctavious is run from, which is
+    /// expected to be the directory containing doctavious.toml
+    pub root: String,
+
+    /// Name of the framework, ex: Hugo, to use instead of detecting it
+    pub framework: Option<String>,
+
+    /// Overrides the build command of the framework. A command for the build context takes
+    /// precedence
+    pub command: Option<String>,
+
+    /// Overrides the install command of the detected package manager
+    pub install_command: Option<String>,
+
+    /// Overrides the output directory of the framework, relative to the project root
+    pub output_directory: Option<String>,
+
+    /// API reference built alongside the project and included in its output
+    pub api_reference: Option<BuildApiReferenceSettings>,
+
+    /// Overrides the globs of the files hashed for the build cache
+    pub inputs: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BuildApiReferenceSettings {
+    /// Name of the API reference generator, ex: rustdoc, to use instead of detecting it
+    pub framework: Option<String>,
+
+    /// Root directory of the source code relative to the directory doctavious is run from.
+    /// Defaults to the project root
+    pub root: Option<String>,
+
+    /// Overrides the build command of the API reference generator
+    pub command: Option<String>,
+
+    /// Overrides the output directory of the API reference generator, relative to its root
+    pub output_directory: Option<String>,
+
+    /// Directory within the project output the API reference is placed in. Defaults to api
+    pub path: Option<String>,
 }
 
 impl Settings {
@@ -270,7 +417,11 @@ impl Settings {
 }
 
 pub(crate) fn load_settings() -> Result<Settings> {
-    let contents = fs::read_to_string(SETTINGS_FILE.as_path())?;
+    load_settings_from(SETTINGS_FILE.as_path())
+}
+
+pub(crate) fn load_settings_from(path: &Path) -> Result<Settings> {
+    let contents = fs::read_to_string(path)?;
     let settings: Settings = toml::from_str(contents.as_str())?;
     Ok(settings)
 }
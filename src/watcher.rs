@@ -0,0 +1,119 @@
+// Polls directories for changes.
+// We poll modification times rather than relying on OS file system notifications as docs are
+// typically small enough that a scan is cheap and it behaves the same across platforms, Docker
+// volumes and network drives.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use walkdir::WalkDir;
+
+/// Directories which are never watched
+const IGNORED_DIRS: &[&str] = &[".doctavious", ".git", "node_modules"];
+
+pub(crate) struct FileWatcher {
+    roots: Vec<PathBuf>,
+    excluded: Vec<PathBuf>,
+    snapshot: HashMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl FileWatcher {
+    /// Watches files within the roots other than those within the excluded paths, such as build
+    /// output directories
+    pub fn new(roots: Vec<PathBuf>, excluded: Vec<PathBuf>) -> Self {
+        let mut watcher = Self { roots, excluded, snapshot: HashMap::new() };
+        watcher.snapshot = watcher.scan();
+        watcher
+    }
+
+    pub fn set_excluded(&mut self, excluded: Vec<PathBuf>) {
+        self.excluded = excluded;
+        self.snapshot = self.scan();
+    }
+
+    /// Returns true when files have been added, removed or modified since the last check
+    pub fn changed(&mut self) -> bool {
+        let current = self.scan();
+        let changed = current != self.snapshot;
+        self.snapshot = current;
+        changed
+    }
+
+    /// Blocks until a change is detected. Waits for changes to settle, for example an editor
+    /// writing multiple files, before returning
+    pub fn wait_for_change(&mut self, interval: Duration) {
+        while !self.changed() {
+            thread::sleep(interval);
+        }
+
+        loop {
+            thread::sleep(interval);
+            if !self.changed() {
+                return;
+            }
+        }
+    }
+
+    fn scan(&self) -> HashMap<PathBuf, (Option<SystemTime>, u64)> {
+        let mut files = HashMap::new();
+        for root in &self.roots {
+            let walker = WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|e| e.depth() == 0 || !self.is_excluded(e.path()));
+            for entry in walker.filter_map(|e| e.ok()) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                if let Ok(metadata) = entry.metadata() {
+                    files.insert(entry.into_path(), (metadata.modified().ok(), metadata.len()));
+                }
+            }
+        }
+        files
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        let ignored = path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| IGNORED_DIRS.contains(&n))
+            .unwrap_or(false);
+        ignored || self.excluded.iter().any(|e| path.starts_with(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::watcher::FileWatcher;
+
+    #[test]
+    fn should_detect_changes_outside_of_excluded_paths() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("index.md"), "# Home").unwrap();
+        fs::create_dir_all(dir.path().join("public")).unwrap();
+        fs::create_dir_all(dir.path().join("node_modules")).unwrap();
+
+        let mut watcher = FileWatcher::new(
+            vec![dir.path().to_path_buf()],
+            vec![dir.path().join("public")]
+        );
+        assert!(!watcher.changed());
+
+        fs::write(dir.path().join("public/index.html"), "<html></html>").unwrap();
+        fs::write(dir.path().join("node_modules/package.json"), "{}").unwrap();
+        assert!(!watcher.changed());
+
+        fs::write(dir.path().join("about.md"), "# About").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(dir.path().join("index.md"), "# Welcome home").unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_file(dir.path().join("about.md")).unwrap();
+        assert!(watcher.changed());
+    }
+}
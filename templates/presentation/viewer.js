@@ -0,0 +1,87 @@
+(function () {
+    var deck = document.querySelector(".deck");
+    var slides = Array.prototype.slice.call(deck.querySelectorAll(":scope > section"));
+    var notesPanel = document.querySelector(".notes-panel");
+    var width = parseInt(deck.getAttribute("data-width"), 10);
+    var height = parseInt(deck.getAttribute("data-height"), 10);
+    var current = 0;
+
+    function scale() {
+        var ratio = Math.min(window.innerWidth / width, window.innerHeight / height);
+        deck.style.transform = "scale(" + ratio + ") translate(-50%, -50%)";
+    }
+
+    function show(index) {
+        if (slides.length === 0) {
+            return;
+        }
+        current = Math.max(0, Math.min(index, slides.length - 1));
+        slides.forEach(function (slide, i) {
+            slide.classList.toggle("active", i === current);
+        });
+        var notes = slides[current].querySelector("aside.notes");
+        notesPanel.textContent = notes ? notes.textContent : "";
+        if (location.hash !== "#" + (current + 1)) {
+            history.replaceState(null, "", "#" + (current + 1));
+        }
+    }
+
+    function fromHash() {
+        var index = parseInt(location.hash.slice(1), 10);
+        return isNaN(index) ? 0 : index - 1;
+    }
+
+    document.addEventListener("keydown", function (event) {
+        if (event.altKey || event.ctrlKey || event.metaKey) {
+            return;
+        }
+        switch (event.key) {
+            case "ArrowRight":
+            case "ArrowDown":
+            case "PageDown":
+            case " ":
+                show(current + (event.shiftKey && event.key === " " ? -1 : 1));
+                break;
+            case "ArrowLeft":
+            case "ArrowUp":
+            case "PageUp":
+            case "Backspace":
+                show(current - 1);
+                break;
+            case "Home":
+                show(0);
+                break;
+            case "End":
+                show(slides.length - 1);
+                break;
+            case "p":
+                document.body.classList.toggle("show-notes");
+                break;
+            case "f":
+                if (document.fullscreenElement) {
+                    document.exitFullscreen();
+                } else {
+                    document.documentElement.requestFullscreen();
+                }
+                break;
+            default:
+                return;
+        }
+        event.preventDefault();
+    });
+
+    document.addEventListener("click", function (event) {
+        if (event.target.closest("a") || event.target.closest(".notes-panel")) {
+            return;
+        }
+        show(current + (event.clientX < window.innerWidth / 3 ? -1 : 1));
+    });
+
+    window.addEventListener("hashchange", function () {
+        show(fromHash());
+    });
+    window.addEventListener("resize", scale);
+
+    scale();
+    show(fromHash());
+})();
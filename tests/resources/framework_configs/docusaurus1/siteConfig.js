@@ -0,0 +1,10 @@
+const siteConfig = {
+  title: 'Test Site',
+  tagline: 'A website for testing',
+  url: 'https://your-docusaurus-test-site.com',
+  baseUrl: '/',
+  projectName: 'test-site',
+  organizationName: 'facebook',
+};
+
+module.exports = siteConfig;
@@ -0,0 +1,8 @@
+import * as path from 'path';
+import { defineConfig } from 'rspress/config';
+
+export default defineConfig({
+  root: path.join(__dirname, 'docs'),
+  title: 'My Site',
+  outDir: 'build',
+});
@@ -0,0 +1,11 @@
+import { defineConfig } from 'astro/config';
+import starlight from '@astrojs/starlight';
+
+export default defineConfig({
+  outDir: './build',
+  integrations: [
+    starlight({
+      title: 'My Docs',
+    }),
+  ],
+});